@@ -1,6 +1,10 @@
 // Copyright 2020 WeDPR Lab Project Authors. Licensed under Apache-2.0.
 
 //! Config of anonymous ciphertext voting (ACV) solution.
+
+/// Domain tag prefixed to the messages signed into blank ballots.
+pub const VOTE_MESSAGE_DOMAIN: &str = "WEDPR_ACV_VOTE_v1";
+
 #[cfg(feature = "wedpr_f_sm_signature")]
 use wedpr_l_crypto_hash_sm3::WedprSm3;
 #[cfg(feature = "wedpr_f_sm_signature")]
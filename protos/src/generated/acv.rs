@@ -186,6 +186,11 @@ pub struct PollParametersStorage {
     // message fields
     pub poll_point: ::std::vec::Vec<u8>,
     pub candidates: ::protobuf::SingularPtrField<CandidateList>,
+    pub candidate_cap: ::protobuf::RepeatedField<StringToInt64Pair>,
+    pub poll_epoch: u64,
+    pub poll_id: ::std::vec::Vec<u8>,
+    pub required_selections: u32,
+    pub authorized_counter_share: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -260,8 +265,111 @@ impl PollParametersStorage {
     pub fn take_candidates(&mut self) -> CandidateList {
         self.candidates.take().unwrap_or_else(|| CandidateList::new())
     }
+    // repeated .com.webank.wedpr.acv.proto.StringToInt64Pair candidate_cap = 3;
+
+
+    pub fn get_candidate_cap(&self) -> &[StringToInt64Pair] {
+        &self.candidate_cap
+    }
+    pub fn clear_candidate_cap(&mut self) {
+        self.candidate_cap.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_candidate_cap(&mut self, v: ::protobuf::RepeatedField<StringToInt64Pair>) {
+        self.candidate_cap = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_candidate_cap(&mut self) -> &mut ::protobuf::RepeatedField<StringToInt64Pair> {
+        &mut self.candidate_cap
+    }
+
+    // Take field
+    pub fn take_candidate_cap(&mut self) -> ::protobuf::RepeatedField<StringToInt64Pair> {
+        ::std::mem::replace(&mut self.candidate_cap, ::protobuf::RepeatedField::new())
+    }
+    // uint64 poll_epoch = 4;
+
+
+    pub fn get_poll_epoch(&self) -> u64 {
+        self.poll_epoch
+    }
+    pub fn clear_poll_epoch(&mut self) {
+        self.poll_epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_poll_epoch(&mut self, v: u64) {
+        self.poll_epoch = v;
+    }
+    // bytes poll_id = 5;
+
+
+    pub fn get_poll_id(&self) -> &[u8] {
+        &self.poll_id
+    }
+    pub fn clear_poll_id(&mut self) {
+        self.poll_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_poll_id(&mut self, v: ::std::vec::Vec<u8>) {
+        self.poll_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_poll_id(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.poll_id
+    }
+
+    // Take field
+    pub fn take_poll_id(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.poll_id, ::std::vec::Vec::new())
+    }
+    // uint32 required_selections = 6;
+
+
+    pub fn get_required_selections(&self) -> u32 {
+        self.required_selections
+    }
+    pub fn clear_required_selections(&mut self) {
+        self.required_selections = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_required_selections(&mut self, v: u32) {
+        self.required_selections = v;
+    }
+    // repeated bytes authorized_counter_share = 7;
+
+
+    pub fn get_authorized_counter_share(&self) -> &[::std::vec::Vec<u8>] {
+        &self.authorized_counter_share
+    }
+    pub fn clear_authorized_counter_share(&mut self) {
+        self.authorized_counter_share.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_authorized_counter_share(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.authorized_counter_share = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_authorized_counter_share(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.authorized_counter_share
+    }
+
+    // Take field
+    pub fn take_authorized_counter_share(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.authorized_counter_share, ::protobuf::RepeatedField::new())
+    }
 }
 
+
+
 impl ::protobuf::Message for PollParametersStorage {
     fn is_initialized(&self) -> bool {
         for v in &self.candidates {
@@ -269,6 +377,11 @@ impl ::protobuf::Message for PollParametersStorage {
                 return false;
             }
         };
+        for v in &self.candidate_cap {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -282,6 +395,29 @@ impl ::protobuf::Message for PollParametersStorage {
                 2 => {
                     ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.candidates)?;
                 },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.candidate_cap)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.poll_epoch = tmp;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.poll_id)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.required_selections = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.authorized_counter_share)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -301,6 +437,22 @@ impl ::protobuf::Message for PollParametersStorage {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        for value in &self.candidate_cap {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if self.poll_epoch != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.poll_epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.poll_id.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(5, &self.poll_id);
+        }
+        if self.required_selections != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.required_selections, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.authorized_counter_share {
+            my_size += ::protobuf::rt::bytes_size(7, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -315,6 +467,23 @@ impl ::protobuf::Message for PollParametersStorage {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        for v in &self.candidate_cap {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if self.poll_epoch != 0 {
+            os.write_uint64(4, self.poll_epoch)?;
+        }
+        if !self.poll_id.is_empty() {
+            os.write_bytes(5, &self.poll_id)?;
+        }
+        if self.required_selections != 0 {
+            os.write_uint32(6, self.required_selections)?;
+        }
+        for v in &self.authorized_counter_share {
+            os.write_bytes(7, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -363,6 +532,31 @@ impl ::protobuf::Message for PollParametersStorage {
                 |m: &PollParametersStorage| { &m.candidates },
                 |m: &mut PollParametersStorage| { &mut m.candidates },
             ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<StringToInt64Pair>>(
+                "candidate_cap",
+                |m: &PollParametersStorage| { &m.candidate_cap },
+                |m: &mut PollParametersStorage| { &mut m.candidate_cap },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "poll_epoch",
+                |m: &PollParametersStorage| { &m.poll_epoch },
+                |m: &mut PollParametersStorage| { &mut m.poll_epoch },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "poll_id",
+                |m: &PollParametersStorage| { &m.poll_id },
+                |m: &mut PollParametersStorage| { &mut m.poll_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "required_selections",
+                |m: &PollParametersStorage| { &m.required_selections },
+                |m: &mut PollParametersStorage| { &mut m.required_selections },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "authorized_counter_share",
+                |m: &PollParametersStorage| { &m.authorized_counter_share },
+                |m: &mut PollParametersStorage| { &mut m.authorized_counter_share },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<PollParametersStorage>(
                 "PollParametersStorage",
                 fields,
@@ -381,6 +575,11 @@ impl ::protobuf::Clear for PollParametersStorage {
     fn clear(&mut self) {
         self.poll_point.clear();
         self.candidates.clear();
+        self.candidate_cap.clear();
+        self.poll_epoch = 0;
+        self.poll_id.clear();
+        self.required_selections = 0;
+        self.authorized_counter_share.clear();
         self.unknown_fields.clear();
     }
 }
@@ -401,6 +600,7 @@ impl ::protobuf::reflect::ProtobufValue for PollParametersStorage {
 pub struct CounterSecret {
     // message fields
     pub poll_secret_share: ::std::vec::Vec<u8>,
+    pub share_index: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -442,6 +642,21 @@ impl CounterSecret {
     pub fn take_poll_secret_share(&mut self) -> ::std::vec::Vec<u8> {
         ::std::mem::replace(&mut self.poll_secret_share, ::std::vec::Vec::new())
     }
+
+    // uint64 share_index = 2;
+
+
+    pub fn get_share_index(&self) -> u64 {
+        self.share_index
+    }
+    pub fn clear_share_index(&mut self) {
+        self.share_index = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_share_index(&mut self, v: u64) {
+        self.share_index = v;
+    }
 }
 
 impl ::protobuf::Message for CounterSecret {
@@ -456,6 +671,13 @@ impl ::protobuf::Message for CounterSecret {
                 1 => {
                     ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.poll_secret_share)?;
                 },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.share_index = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -471,6 +693,9 @@ impl ::protobuf::Message for CounterSecret {
         if !self.poll_secret_share.is_empty() {
             my_size += ::protobuf::rt::bytes_size(1, &self.poll_secret_share);
         }
+        if self.share_index != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.share_index, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -480,6 +705,9 @@ impl ::protobuf::Message for CounterSecret {
         if !self.poll_secret_share.is_empty() {
             os.write_bytes(1, &self.poll_secret_share)?;
         }
+        if self.share_index != 0 {
+            os.write_uint64(2, self.share_index)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -523,6 +751,11 @@ impl ::protobuf::Message for CounterSecret {
                 |m: &CounterSecret| { &m.poll_secret_share },
                 |m: &mut CounterSecret| { &mut m.poll_secret_share },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "share_index",
+                |m: &CounterSecret| { &m.share_index },
+                |m: &mut CounterSecret| { &mut m.share_index },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<CounterSecret>(
                 "CounterSecret",
                 fields,
@@ -540,6 +773,7 @@ impl ::protobuf::Message for CounterSecret {
 impl ::protobuf::Clear for CounterSecret {
     fn clear(&mut self) {
         self.poll_secret_share.clear();
+        self.share_index = 0;
         self.unknown_fields.clear();
     }
 }
@@ -1661,6 +1895,7 @@ pub struct CounterParametersShareRequest {
     // message fields
     pub counter_id: ::std::string::String,
     pub poll_point_share: ::std::vec::Vec<u8>,
+    pub share_index: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -1728,6 +1963,21 @@ impl CounterParametersShareRequest {
     pub fn take_poll_point_share(&mut self) -> ::std::vec::Vec<u8> {
         ::std::mem::replace(&mut self.poll_point_share, ::std::vec::Vec::new())
     }
+
+    // uint64 share_index = 3;
+
+
+    pub fn get_share_index(&self) -> u64 {
+        self.share_index
+    }
+    pub fn clear_share_index(&mut self) {
+        self.share_index = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_share_index(&mut self, v: u64) {
+        self.share_index = v;
+    }
 }
 
 impl ::protobuf::Message for CounterParametersShareRequest {
@@ -1745,6 +1995,13 @@ impl ::protobuf::Message for CounterParametersShareRequest {
                 2 => {
                     ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.poll_point_share)?;
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.share_index = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1763,6 +2020,9 @@ impl ::protobuf::Message for CounterParametersShareRequest {
         if !self.poll_point_share.is_empty() {
             my_size += ::protobuf::rt::bytes_size(2, &self.poll_point_share);
         }
+        if self.share_index != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.share_index, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1775,6 +2035,9 @@ impl ::protobuf::Message for CounterParametersShareRequest {
         if !self.poll_point_share.is_empty() {
             os.write_bytes(2, &self.poll_point_share)?;
         }
+        if self.share_index != 0 {
+            os.write_uint64(3, self.share_index)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1823,6 +2086,11 @@ impl ::protobuf::Message for CounterParametersShareRequest {
                 |m: &CounterParametersShareRequest| { &m.poll_point_share },
                 |m: &mut CounterParametersShareRequest| { &mut m.poll_point_share },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "share_index",
+                |m: &CounterParametersShareRequest| { &m.share_index },
+                |m: &mut CounterParametersShareRequest| { &mut m.share_index },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<CounterParametersShareRequest>(
                 "CounterParametersShareRequest",
                 fields,
@@ -1841,6 +2109,7 @@ impl ::protobuf::Clear for CounterParametersShareRequest {
     fn clear(&mut self) {
         self.counter_id.clear();
         self.poll_point_share.clear();
+        self.share_index = 0;
         self.unknown_fields.clear();
     }
 }
@@ -3260,6 +3529,12 @@ pub struct VoteRequest {
     pub range_proof: ::std::vec::Vec<u8>,
     pub sum_balance_proof: ::std::vec::Vec<u8>,
     pub unlisted_ballot_proof: ::protobuf::RepeatedField<CipherPointsToBallotProofPair>,
+    pub voter_weight: u32,
+    pub weight_proof: ::std::vec::Vec<u8>,
+    pub cap_range_proof: ::std::vec::Vec<u8>,
+    pub authorization_token: ::std::vec::Vec<u8>,
+    pub poll_id: ::std::vec::Vec<u8>,
+    pub authorization_nonce: ::std::vec::Vec<u8>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -3410,8 +3685,151 @@ impl VoteRequest {
     pub fn take_unlisted_ballot_proof(&mut self) -> ::protobuf::RepeatedField<CipherPointsToBallotProofPair> {
         ::std::mem::replace(&mut self.unlisted_ballot_proof, ::protobuf::RepeatedField::new())
     }
+    // uint32 voter_weight = 6;
+
+
+    pub fn get_voter_weight(&self) -> u32 {
+        self.voter_weight
+    }
+    pub fn clear_voter_weight(&mut self) {
+        self.voter_weight = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_voter_weight(&mut self, v: u32) {
+        self.voter_weight = v;
+    }
+    // bytes weight_proof = 7;
+
+
+    pub fn get_weight_proof(&self) -> &[u8] {
+        &self.weight_proof
+    }
+    pub fn clear_weight_proof(&mut self) {
+        self.weight_proof.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_weight_proof(&mut self, v: ::std::vec::Vec<u8>) {
+        self.weight_proof = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_weight_proof(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.weight_proof
+    }
+
+    // Take field
+    pub fn take_weight_proof(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.weight_proof, ::std::vec::Vec::new())
+    }
+    // bytes cap_range_proof = 8;
+
+
+    pub fn get_cap_range_proof(&self) -> &[u8] {
+        &self.cap_range_proof
+    }
+    pub fn clear_cap_range_proof(&mut self) {
+        self.cap_range_proof.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cap_range_proof(&mut self, v: ::std::vec::Vec<u8>) {
+        self.cap_range_proof = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_cap_range_proof(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.cap_range_proof
+    }
+
+    // Take field
+    pub fn take_cap_range_proof(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.cap_range_proof, ::std::vec::Vec::new())
+    }
+    // bytes authorization_token = 9;
+
+
+    pub fn get_authorization_token(&self) -> &[u8] {
+        &self.authorization_token
+    }
+    pub fn clear_authorization_token(&mut self) {
+        self.authorization_token.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_authorization_token(&mut self, v: ::std::vec::Vec<u8>) {
+        self.authorization_token = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_authorization_token(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.authorization_token
+    }
+
+    // Take field
+    pub fn take_authorization_token(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.authorization_token, ::std::vec::Vec::new())
+    }
+    // bytes poll_id = 10;
+
+
+    pub fn get_poll_id(&self) -> &[u8] {
+        &self.poll_id
+    }
+    pub fn clear_poll_id(&mut self) {
+        self.poll_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_poll_id(&mut self, v: ::std::vec::Vec<u8>) {
+        self.poll_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_poll_id(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.poll_id
+    }
+
+    // Take field
+    pub fn take_poll_id(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.poll_id, ::std::vec::Vec::new())
+    }
+    // bytes authorization_nonce = 11;
+
+
+    pub fn get_authorization_nonce(&self) -> &[u8] {
+        &self.authorization_nonce
+    }
+    pub fn clear_authorization_nonce(&mut self) {
+        self.authorization_nonce.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_authorization_nonce(&mut self, v: ::std::vec::Vec<u8>) {
+        self.authorization_nonce = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_authorization_nonce(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.authorization_nonce
+    }
+
+    // Take field
+    pub fn take_authorization_nonce(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.authorization_nonce, ::std::vec::Vec::new())
+    }
 }
 
+
+
+
+
 impl ::protobuf::Message for VoteRequest {
     fn is_initialized(&self) -> bool {
         for v in &self.vote {
@@ -3451,6 +3869,28 @@ impl ::protobuf::Message for VoteRequest {
                 5 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.unlisted_ballot_proof)?;
                 },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.voter_weight = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.weight_proof)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.cap_range_proof)?;
+                },
+                9 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.authorization_token)?;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.poll_id)?;
+                },
+                11 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.authorization_nonce)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -3481,6 +3921,24 @@ impl ::protobuf::Message for VoteRequest {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if self.voter_weight != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.voter_weight, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.weight_proof.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(7, &self.weight_proof);
+        }
+        if !self.cap_range_proof.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(8, &self.cap_range_proof);
+        }
+        if !self.authorization_token.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(9, &self.authorization_token);
+        }
+        if !self.poll_id.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(10, &self.poll_id);
+        }
+        if !self.authorization_nonce.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(11, &self.authorization_nonce);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -3508,6 +3966,24 @@ impl ::protobuf::Message for VoteRequest {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
+        if self.voter_weight != 0 {
+            os.write_uint32(6, self.voter_weight)?;
+        }
+        if !self.weight_proof.is_empty() {
+            os.write_bytes(7, &self.weight_proof)?;
+        }
+        if !self.cap_range_proof.is_empty() {
+            os.write_bytes(8, &self.cap_range_proof)?;
+        }
+        if !self.authorization_token.is_empty() {
+            os.write_bytes(9, &self.authorization_token)?;
+        }
+        if !self.poll_id.is_empty() {
+            os.write_bytes(10, &self.poll_id)?;
+        }
+        if !self.authorization_nonce.is_empty() {
+            os.write_bytes(11, &self.authorization_nonce)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -3571,17 +4047,47 @@ impl ::protobuf::Message for VoteRequest {
                 |m: &VoteRequest| { &m.unlisted_ballot_proof },
                 |m: &mut VoteRequest| { &mut m.unlisted_ballot_proof },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VoteRequest>(
-                "VoteRequest",
-                fields,
-                file_descriptor_proto()
-            )
-        })
-    }
-
-    fn default_instance() -> &'static VoteRequest {
-        static instance: ::protobuf::rt::LazyV2<VoteRequest> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(VoteRequest::new)
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "voter_weight",
+                |m: &VoteRequest| { &m.voter_weight },
+                |m: &mut VoteRequest| { &mut m.voter_weight },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "weight_proof",
+                |m: &VoteRequest| { &m.weight_proof },
+                |m: &mut VoteRequest| { &mut m.weight_proof },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "cap_range_proof",
+                |m: &VoteRequest| { &m.cap_range_proof },
+                |m: &mut VoteRequest| { &mut m.cap_range_proof },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "authorization_token",
+                |m: &VoteRequest| { &m.authorization_token },
+                |m: &mut VoteRequest| { &mut m.authorization_token },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "poll_id",
+                |m: &VoteRequest| { &m.poll_id },
+                |m: &mut VoteRequest| { &mut m.poll_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "authorization_nonce",
+                |m: &VoteRequest| { &m.authorization_nonce },
+                |m: &mut VoteRequest| { &mut m.authorization_nonce },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VoteRequest>(
+                "VoteRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static VoteRequest {
+        static instance: ::protobuf::rt::LazyV2<VoteRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(VoteRequest::new)
     }
 }
 
@@ -3592,6 +4098,12 @@ impl ::protobuf::Clear for VoteRequest {
         self.range_proof.clear();
         self.sum_balance_proof.clear();
         self.unlisted_ballot_proof.clear();
+        self.voter_weight = 0;
+        self.weight_proof.clear();
+        self.cap_range_proof.clear();
+        self.authorization_token.clear();
+        self.poll_id.clear();
+        self.authorization_nonce.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6154,92 +6666,753 @@ impl ::protobuf::reflect::ProtobufValue for StringToInt64Pair {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct PollTranscript {
+    // message fields
+    pub poll_parameters: ::protobuf::SingularPtrField<PollParametersStorage>,
+    pub vote_sum: ::protobuf::SingularPtrField<VoteStorage>,
+    pub aggregated_decrypted_result: ::protobuf::SingularPtrField<DecryptedResultPartStorage>,
+    pub vote_result: ::protobuf::SingularPtrField<VoteResultStorage>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PollTranscript {
+    fn default() -> &'a PollTranscript {
+        <PollTranscript as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PollTranscript {
+    pub fn new() -> PollTranscript {
+        ::std::default::Default::default()
+    }
+
+    // .com.webank.wedpr.acv.proto.PollParametersStorage poll_parameters = 1;
+
+
+    pub fn get_poll_parameters(&self) -> &PollParametersStorage {
+        self.poll_parameters.as_ref().unwrap_or_else(|| <PollParametersStorage as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_poll_parameters(&mut self) {
+        self.poll_parameters.clear();
+    }
+
+    pub fn has_poll_parameters(&self) -> bool {
+        self.poll_parameters.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_poll_parameters(&mut self, v: PollParametersStorage) {
+        self.poll_parameters = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_poll_parameters(&mut self) -> &mut PollParametersStorage {
+        if self.poll_parameters.is_none() {
+            self.poll_parameters.set_default();
+        }
+        self.poll_parameters.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_poll_parameters(&mut self) -> PollParametersStorage {
+        self.poll_parameters.take().unwrap_or_else(|| PollParametersStorage::new())
+    }
+
+    // .com.webank.wedpr.acv.proto.VoteStorage vote_sum = 2;
+
+
+    pub fn get_vote_sum(&self) -> &VoteStorage {
+        self.vote_sum.as_ref().unwrap_or_else(|| <VoteStorage as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_vote_sum(&mut self) {
+        self.vote_sum.clear();
+    }
+
+    pub fn has_vote_sum(&self) -> bool {
+        self.vote_sum.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_vote_sum(&mut self, v: VoteStorage) {
+        self.vote_sum = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_vote_sum(&mut self) -> &mut VoteStorage {
+        if self.vote_sum.is_none() {
+            self.vote_sum.set_default();
+        }
+        self.vote_sum.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_vote_sum(&mut self) -> VoteStorage {
+        self.vote_sum.take().unwrap_or_else(|| VoteStorage::new())
+    }
+
+    // .com.webank.wedpr.acv.proto.DecryptedResultPartStorage aggregated_decrypted_result = 3;
+
+
+    pub fn get_aggregated_decrypted_result(&self) -> &DecryptedResultPartStorage {
+        self.aggregated_decrypted_result.as_ref().unwrap_or_else(|| <DecryptedResultPartStorage as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_aggregated_decrypted_result(&mut self) {
+        self.aggregated_decrypted_result.clear();
+    }
+
+    pub fn has_aggregated_decrypted_result(&self) -> bool {
+        self.aggregated_decrypted_result.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_aggregated_decrypted_result(&mut self, v: DecryptedResultPartStorage) {
+        self.aggregated_decrypted_result = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_aggregated_decrypted_result(&mut self) -> &mut DecryptedResultPartStorage {
+        if self.aggregated_decrypted_result.is_none() {
+            self.aggregated_decrypted_result.set_default();
+        }
+        self.aggregated_decrypted_result.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_aggregated_decrypted_result(&mut self) -> DecryptedResultPartStorage {
+        self.aggregated_decrypted_result.take().unwrap_or_else(|| DecryptedResultPartStorage::new())
+    }
+
+    // .com.webank.wedpr.acv.proto.VoteResultStorage vote_result = 4;
+
+
+    pub fn get_vote_result(&self) -> &VoteResultStorage {
+        self.vote_result.as_ref().unwrap_or_else(|| <VoteResultStorage as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_vote_result(&mut self) {
+        self.vote_result.clear();
+    }
+
+    pub fn has_vote_result(&self) -> bool {
+        self.vote_result.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_vote_result(&mut self, v: VoteResultStorage) {
+        self.vote_result = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_vote_result(&mut self) -> &mut VoteResultStorage {
+        if self.vote_result.is_none() {
+            self.vote_result.set_default();
+        }
+        self.vote_result.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_vote_result(&mut self) -> VoteResultStorage {
+        self.vote_result.take().unwrap_or_else(|| VoteResultStorage::new())
+    }
+}
+
+impl ::protobuf::Message for PollTranscript {
+    fn is_initialized(&self) -> bool {
+        for v in &self.poll_parameters {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.vote_sum {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.aggregated_decrypted_result {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.vote_result {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.poll_parameters)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.vote_sum)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.aggregated_decrypted_result)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.vote_result)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.poll_parameters.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.vote_sum.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.aggregated_decrypted_result.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.vote_result.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.poll_parameters.as_ref() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.vote_sum.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.aggregated_decrypted_result.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.vote_result.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PollTranscript {
+        PollTranscript::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<PollParametersStorage>>(
+                "poll_parameters",
+                |m: &PollTranscript| { &m.poll_parameters },
+                |m: &mut PollTranscript| { &mut m.poll_parameters },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<VoteStorage>>(
+                "vote_sum",
+                |m: &PollTranscript| { &m.vote_sum },
+                |m: &mut PollTranscript| { &mut m.vote_sum },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<DecryptedResultPartStorage>>(
+                "aggregated_decrypted_result",
+                |m: &PollTranscript| { &m.aggregated_decrypted_result },
+                |m: &mut PollTranscript| { &mut m.aggregated_decrypted_result },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<VoteResultStorage>>(
+                "vote_result",
+                |m: &PollTranscript| { &m.vote_result },
+                |m: &mut PollTranscript| { &mut m.vote_result },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PollTranscript>(
+                "PollTranscript",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static PollTranscript {
+        static instance: ::protobuf::rt::LazyV2<PollTranscript> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PollTranscript::new)
+    }
+}
+
+impl ::protobuf::Clear for PollTranscript {
+    fn clear(&mut self) {
+        self.poll_parameters.clear();
+        self.vote_sum.clear();
+        self.aggregated_decrypted_result.clear();
+        self.vote_result.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PollTranscript {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PollTranscript {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct CounterReshareProof {
+    // message fields
+    pub counter_id: ::std::string::String,
+    pub new_share: ::protobuf::RepeatedField<CounterParametersShareRequest>,
+    pub binding_point: ::std::vec::Vec<u8>,
+    pub equality_proof: ::std::vec::Vec<u8>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a CounterReshareProof {
+    fn default() -> &'a CounterReshareProof {
+        <CounterReshareProof as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CounterReshareProof {
+    pub fn new() -> CounterReshareProof {
+        ::std::default::Default::default()
+    }
+
+    // string counter_id = 1;
+
+
+    pub fn get_counter_id(&self) -> &str {
+        &self.counter_id
+    }
+    pub fn clear_counter_id(&mut self) {
+        self.counter_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_counter_id(&mut self, v: ::std::string::String) {
+        self.counter_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_counter_id(&mut self) -> &mut ::std::string::String {
+        &mut self.counter_id
+    }
+
+    // Take field
+    pub fn take_counter_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.counter_id, ::std::string::String::new())
+    }
+
+    // repeated .com.webank.wedpr.acv.proto.CounterParametersShareRequest new_share = 2;
+
+
+    pub fn get_new_share(&self) -> &[CounterParametersShareRequest] {
+        &self.new_share
+    }
+    pub fn clear_new_share(&mut self) {
+        self.new_share.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_new_share(&mut self, v: ::protobuf::RepeatedField<CounterParametersShareRequest>) {
+        self.new_share = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_new_share(&mut self) -> &mut ::protobuf::RepeatedField<CounterParametersShareRequest> {
+        &mut self.new_share
+    }
+
+    // Take field
+    pub fn take_new_share(&mut self) -> ::protobuf::RepeatedField<CounterParametersShareRequest> {
+        ::std::mem::replace(&mut self.new_share, ::protobuf::RepeatedField::new())
+    }
+
+    // bytes binding_point = 3;
+
+
+    pub fn get_binding_point(&self) -> &[u8] {
+        &self.binding_point
+    }
+    pub fn clear_binding_point(&mut self) {
+        self.binding_point.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_binding_point(&mut self, v: ::std::vec::Vec<u8>) {
+        self.binding_point = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_binding_point(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.binding_point
+    }
+
+    // Take field
+    pub fn take_binding_point(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.binding_point, ::std::vec::Vec::new())
+    }
+
+    // bytes equality_proof = 4;
+
+
+    pub fn get_equality_proof(&self) -> &[u8] {
+        &self.equality_proof
+    }
+    pub fn clear_equality_proof(&mut self) {
+        self.equality_proof.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_equality_proof(&mut self, v: ::std::vec::Vec<u8>) {
+        self.equality_proof = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_equality_proof(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.equality_proof
+    }
+
+    // Take field
+    pub fn take_equality_proof(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.equality_proof, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for CounterReshareProof {
+    fn is_initialized(&self) -> bool {
+        for v in &self.new_share {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.counter_id)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.new_share)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.binding_point)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.equality_proof)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.counter_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.counter_id);
+        }
+        for value in &self.new_share {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if !self.binding_point.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.binding_point);
+        }
+        if !self.equality_proof.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.equality_proof);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.counter_id.is_empty() {
+            os.write_string(1, &self.counter_id)?;
+        }
+        for v in &self.new_share {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if !self.binding_point.is_empty() {
+            os.write_bytes(3, &self.binding_point)?;
+        }
+        if !self.equality_proof.is_empty() {
+            os.write_bytes(4, &self.equality_proof)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> CounterReshareProof {
+        CounterReshareProof::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "counter_id",
+                |m: &CounterReshareProof| { &m.counter_id },
+                |m: &mut CounterReshareProof| { &mut m.counter_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<CounterParametersShareRequest>>(
+                "new_share",
+                |m: &CounterReshareProof| { &m.new_share },
+                |m: &mut CounterReshareProof| { &mut m.new_share },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "binding_point",
+                |m: &CounterReshareProof| { &m.binding_point },
+                |m: &mut CounterReshareProof| { &mut m.binding_point },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                "equality_proof",
+                |m: &CounterReshareProof| { &m.equality_proof },
+                |m: &mut CounterReshareProof| { &mut m.equality_proof },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<CounterReshareProof>(
+                "CounterReshareProof",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static CounterReshareProof {
+        static instance: ::protobuf::rt::LazyV2<CounterReshareProof> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(CounterReshareProof::new)
+    }
+}
+
+impl ::protobuf::Clear for CounterReshareProof {
+    fn clear(&mut self) {
+        self.counter_id.clear();
+        self.new_share.clear();
+        self.binding_point.clear();
+        self.equality_proof.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for CounterReshareProof {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CounterReshareProof {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x16solution/acv/acv.proto\x12\x1acom.webank.wedpr.acv.proto\"-\n\rCan\
-    didateList\x12\x1c\n\tcandidate\x18\x01\x20\x03(\tR\tcandidate\"\x81\x01\
+    didateList\x12\x1c\n\tcandidate\x18\x01\x20\x03(\tR\tcandidate\"\xf8\x02\
     \n\x15PollParametersStorage\x12\x1d\n\npoll_point\x18\x01\x20\x01(\x0cR\
     \tpollPoint\x12I\n\ncandidates\x18\x02\x20\x01(\x0b2).com.webank.wedpr.a\
-    cv.proto.CandidateListR\ncandidates\";\n\rCounterSecret\x12*\n\x11poll_s\
-    ecret_share\x18\x01\x20\x01(\x0cR\x0fpollSecretShare\"0\n\x0bVoterSecret\
-    \x12!\n\x0cvoter_secret\x18\x01\x20\x01(\x0cR\x0bvoterSecret\"\xc5\x01\n\
-    \x13RegistrationRequest\x12X\n\x0cweight_point\x18\x01\x20\x01(\x0b25.co\
-    m.webank.wedpr.acv.proto.RegistrationBlindingPointR\x0bweightPoint\x12T\
-    \n\nzero_point\x18\x02\x20\x01(\x0b25.com.webank.wedpr.acv.proto.Registr\
-    ationBlindingPointR\tzeroPoint\"\x7f\n\x19RegistrationBlindingPoint\x12.\
-    \n\x13blinding_poll_point\x18\x01\x20\x01(\x0cR\x11blindingPollPoint\x12\
-    2\n\x15blinding_basepoint_g2\x18\x02\x20\x01(\x0cR\x13blindingBasepointG\
-    2\"\xd8\x01\n\x14RegistrationResponse\x12!\n\x0cvoter_weight\x18\x01\x20\
-    \x01(\rR\x0bvoterWeight\x12:\n\x06ballot\x18\x02\x20\x01(\x0b2\".com.web\
-    ank.wedpr.acv.proto.BallotR\x06ballot\x12\x1c\n\tsignature\x18\x03\x20\
-    \x01(\x0cR\tsignature\x12C\n\x0bzero_ballot\x18\x04\x20\x01(\x0b2\".com.\
-    webank.wedpr.acv.proto.BallotR\nzeroBallot\"L\n\x06Ballot\x12\x20\n\x0bc\
-    iphertext1\x18\x01\x20\x01(\x0cR\x0bciphertext1\x12\x20\n\x0bciphertext2\
-    \x18\x02\x20\x01(\x0cR\x0bciphertext2\"h\n\x1dCounterParametersShareRequ\
-    est\x12\x1d\n\ncounter_id\x18\x01\x20\x01(\tR\tcounterId\x12(\n\x10poll_\
-    point_share\x18\x02\x20\x01(\x0cR\x0epollPointShare\"\x8f\x01\n\x18Count\
-    erParametersStorage\x12s\n\x18counter_parameters_share\x18\x01\x20\x03(\
-    \x0b29.com.webank.wedpr.acv.proto.CounterParametersShareRequestR\x16coun\
-    terParametersShare\"@\n\nVoteChoice\x12\x1c\n\tcandidate\x18\x01\x20\x01\
-    (\tR\tcandidate\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05value\"M\n\x12\
-    UnlistedVoteChoice\x12!\n\x0ccandidate_id\x18\x01\x20\x01(\rR\x0bcandida\
-    teId\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05value\"\xa6\x01\n\x0bVote\
-    Choices\x12>\n\x06choice\x18\x01\x20\x03(\x0b2&.com.webank.wedpr.acv.pro\
-    to.VoteChoiceR\x06choice\x12W\n\x0funlisted_choice\x18\x02\x20\x03(\x0b2\
-    ..com.webank.wedpr.acv.proto.UnlistedVoteChoiceR\x0eunlistedChoice\"k\n\
-    \x0fCandidateBallot\x12\x1c\n\tcandidate\x18\x01\x20\x01(\tR\tcandidate\
-    \x12:\n\x06ballot\x18\x02\x20\x01(\x0b2\".com.webank.wedpr.acv.proto.Bal\
-    lotR\x06ballot\"d\n\x0bBallotProof\x12!\n\x0cformat_proof\x18\x01\x20\
-    \x01(\x0cR\x0bformatProof\x122\n\x15either_equality_proof\x18\x02\x20\
-    \x01(\x0cR\x13eitherEqualityProof\"j\n\x17StringToBallotProofPair\x12\
-    \x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12=\n\x05value\x18\x02\x20\x01\
-    (\x0b2'.com.webank.wedpr.acv.proto.BallotProofR\x05value\"\xde\x02\n\x0b\
-    VoteRequest\x12;\n\x04vote\x18\x01\x20\x01(\x0b2'.com.webank.wedpr.acv.p\
-    roto.VoteStorageR\x04vote\x12V\n\x0cballot_proof\x18\x02\x20\x03(\x0b23.\
-    com.webank.wedpr.acv.proto.StringToBallotProofPairR\x0bballotProof\x12\
-    \x1f\n\x0brange_proof\x18\x03\x20\x01(\x0cR\nrangeProof\x12*\n\x11sum_ba\
-    lance_proof\x18\x04\x20\x01(\x0cR\x0fsumBalanceProof\x12m\n\x15unlisted_\
-    ballot_proof\x18\x05\x20\x03(\x0b29.com.webank.wedpr.acv.proto.CipherPoi\
-    ntsToBallotProofPairR\x13unlistedBallotProof\"\xb6\x03\n\x0bVoteStorage\
-    \x12\x1c\n\tsignature\x18\x01\x20\x01(\x0cR\tsignature\x12E\n\x0cblank_b\
-    allot\x18\x02\x20\x01(\x0b2\".com.webank.wedpr.acv.proto.BallotR\x0bblan\
-    kBallot\x12C\n\x0brest_ballot\x18\x03\x20\x01(\x0b2\".com.webank.wedpr.a\
-    cv.proto.BallotR\nrestBallot\x12N\n\x0cvoted_ballot\x18\x04\x20\x03(\x0b\
-    2+.com.webank.wedpr.acv.proto.CandidateBallotR\x0bvotedBallot\x12h\n\x15\
-    voted_ballot_unlisted\x18\x05\x20\x03(\x0b24.com.webank.wedpr.acv.proto.\
-    CipherPointsToBallotPairR\x13votedBallotUnlisted\x12C\n\x0bzero_ballot\
-    \x18\x06\x20\x01(\x0b2\".com.webank.wedpr.acv.proto.BallotR\nzeroBallot\
-    \"\x92\x01\n\x18CipherPointsToBallotPair\x12:\n\x03key\x18\x01\x20\x01(\
-    \x0b2(.com.webank.wedpr.acv.proto.CipherPointsR\x03key\x12:\n\x06ballot\
-    \x18\x02\x20\x01(\x0b2\".com.webank.wedpr.acv.proto.BallotR\x06ballot\"\
-    \x9a\x01\n\x1dCipherPointsToBallotProofPair\x12:\n\x03key\x18\x01\x20\
-    \x01(\x0b2(.com.webank.wedpr.acv.proto.CipherPointsR\x03key\x12=\n\x05va\
-    lue\x18\x02\x20\x01(\x0b2'.com.webank.wedpr.acv.proto.BallotProofR\x05va\
-    lue\"R\n\x0cCipherPoints\x12\x20\n\x0bciphertext1\x18\x01\x20\x01(\x0cR\
-    \x0bciphertext1\x12\x20\n\x0bciphertext2\x18\x02\x20\x01(\x0cR\x0bcipher\
-    text2\"u\n\x0cCountingPart\x12\x1d\n\ncounter_id\x18\x01\x20\x01(\tR\tco\
-    unterId\x12\x1f\n\x0bblinding_c2\x18\x02\x20\x01(\x0cR\nblindingC2\x12%\
-    \n\x0eequality_proof\x18\x03\x20\x01(\x0cR\requalityProof\"l\n\x18String\
-    ToCountingPartPair\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12>\n\
-    \x05value\x18\x02\x20\x01(\x0b2(.com.webank.wedpr.acv.proto.CountingPart\
-    R\x05value\"\xb5\x02\n\x1aDecryptedResultPartStorage\x12G\n\nblank_part\
-    \x18\x01\x20\x01(\x0b2(.com.webank.wedpr.acv.proto.CountingPartR\tblankP\
-    art\x12[\n\x0ecandidate_part\x18\x02\x20\x03(\x0b24.com.webank.wedpr.acv\
-    .proto.StringToCountingPartPairR\rcandidatePart\x12q\n\x17unlisted_candi\
-    date_part\x18\x03\x20\x03(\x0b29.com.webank.wedpr.acv.proto.UnlistedBall\
-    otDecryptedResultR\x15unlistedCandidatePart\"\xf7\x02\n\x1dUnlistedBallo\
-    tDecryptedResult\x12\x1c\n\tcandidate\x18\x01\x20\x01(\x03R\tcandidate\
-    \x12S\n\x10candidate_cipher\x18\x02\x20\x01(\x0b2(.com.webank.wedpr.acv.\
-    proto.CipherPointsR\x0fcandidateCipher\x12j\n\x1cdecrypted_unlisted_cand\
-    idate\x18\x04\x20\x01(\x0b2(.com.webank.wedpr.acv.proto.CountingPartR\
-    \x1adecryptedUnlistedCandidate\x12w\n#decrypted_unlisted_candidate_ballo\
-    t\x18\x05\x20\x03(\x0b2(.com.webank.wedpr.acv.proto.CountingPartR\x20dec\
-    ryptedUnlistedCandidateBallot\"\xb3\x01\n\x11VoteResultStorage\x12E\n\
-    \x06result\x18\x01\x20\x03(\x0b2-.com.webank.wedpr.acv.proto.StringToInt\
-    64PairR\x06result\x12W\n\x0funlisted_result\x18\x02\x20\x03(\x0b2..com.w\
-    ebank.wedpr.acv.proto.UnlistedVoteChoiceR\x0eunlistedResult\";\n\x11Stri\
-    ngToInt64Pair\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05v\
-    alue\x18\x02\x20\x01(\x03R\x05valueB\x1e\n\x1acom.webank.wedpr.acv.proto\
-    P\x01b\x06proto3\
+    cv.proto.CandidateListR\ncandidates\x12R\n\rcandidate_cap\x18\x03\x20\
+    \x03(\x0b2-.com.webank.wedpr.acv.proto.StringToInt64PairR\x0ccandidateCa\
+    p\x12\x1d\n\npoll_epoch\x18\x04\x20\x01(\x04R\tpollEpoch\x12\x17\n\x07po\
+    ll_id\x18\x05\x20\x01(\x0cR\x06pollId\x12/\n\x13required_selections\x18\
+    \x06\x20\x01(\rR\x12requiredSelections\x128\n\x18authorized_counter_shar\
+    e\x18\x07\x20\x03(\x0cR\x16authorizedCounterShare\"\\\n\rCounterSecret\
+    \x12*\n\x11poll_secret_share\x18\x01\x20\x01(\x0cR\x0fpollSecretShare\
+    \x12\x1f\n\x0bshare_index\x18\x02\x20\x01(\x04R\nshareIndex\"0\n\x0bVote\
+    rSecret\x12!\n\x0cvoter_secret\x18\x01\x20\x01(\x0cR\x0bvoterSecret\"\
+    \xc5\x01\n\x13RegistrationRequest\x12X\n\x0cweight_point\x18\x01\x20\x01\
+    (\x0b25.com.webank.wedpr.acv.proto.RegistrationBlindingPointR\x0bweightP\
+    oint\x12T\n\nzero_point\x18\x02\x20\x01(\x0b25.com.webank.wedpr.acv.prot\
+    o.RegistrationBlindingPointR\tzeroPoint\"\x7f\n\x19RegistrationBlindingP\
+    oint\x12.\n\x13blinding_poll_point\x18\x01\x20\x01(\x0cR\x11blindingPoll\
+    Point\x122\n\x15blinding_basepoint_g2\x18\x02\x20\x01(\x0cR\x13blindingB\
+    asepointG2\"\xd8\x01\n\x14RegistrationResponse\x12!\n\x0cvoter_weight\
+    \x18\x01\x20\x01(\rR\x0bvoterWeight\x12:\n\x06ballot\x18\x02\x20\x01(\
+    \x0b2\".com.webank.wedpr.acv.proto.BallotR\x06ballot\x12\x1c\n\tsignatur\
+    e\x18\x03\x20\x01(\x0cR\tsignature\x12C\n\x0bzero_ballot\x18\x04\x20\x01\
+    (\x0b2\".com.webank.wedpr.acv.proto.BallotR\nzeroBallot\"L\n\x06Ballot\
+    \x12\x20\n\x0bciphertext1\x18\x01\x20\x01(\x0cR\x0bciphertext1\x12\x20\n\
+    \x0bciphertext2\x18\x02\x20\x01(\x0cR\x0bciphertext2\"\x89\x01\n\x1dCoun\
+    terParametersShareRequest\x12\x1d\n\ncounter_id\x18\x01\x20\x01(\tR\tcou\
+    nterId\x12(\n\x10poll_point_share\x18\x02\x20\x01(\x0cR\x0epollPointShar\
+    e\x12\x1f\n\x0bshare_index\x18\x03\x20\x01(\x04R\nshareIndex\"\x8f\x01\n\
+    \x18CounterParametersStorage\x12s\n\x18counter_parameters_share\x18\x01\
+    \x20\x03(\x0b29.com.webank.wedpr.acv.proto.CounterParametersShareRequest\
+    R\x16counterParametersShare\"@\n\nVoteChoice\x12\x1c\n\tcandidate\x18\
+    \x01\x20\x01(\tR\tcandidate\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05va\
+    lue\"M\n\x12UnlistedVoteChoice\x12!\n\x0ccandidate_id\x18\x01\x20\x01(\r\
+    R\x0bcandidateId\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05value\"\xa6\
+    \x01\n\x0bVoteChoices\x12>\n\x06choice\x18\x01\x20\x03(\x0b2&.com.webank\
+    .wedpr.acv.proto.VoteChoiceR\x06choice\x12W\n\x0funlisted_choice\x18\x02\
+    \x20\x03(\x0b2..com.webank.wedpr.acv.proto.UnlistedVoteChoiceR\x0eunlist\
+    edChoice\"k\n\x0fCandidateBallot\x12\x1c\n\tcandidate\x18\x01\x20\x01(\t\
+    R\tcandidate\x12:\n\x06ballot\x18\x02\x20\x01(\x0b2\".com.webank.wedpr.a\
+    cv.proto.BallotR\x06ballot\"d\n\x0bBallotProof\x12!\n\x0cformat_proof\
+    \x18\x01\x20\x01(\x0cR\x0bformatProof\x122\n\x15either_equality_proof\
+    \x18\x02\x20\x01(\x0cR\x13eitherEqualityProof\"j\n\x17StringToBallotProo\
+    fPair\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12=\n\x05value\x18\
+    \x02\x20\x01(\x0b2'.com.webank.wedpr.acv.proto.BallotProofR\x05value\"\
+    \xc7\x04\n\x0bVoteRequest\x12;\n\x04vote\x18\x01\x20\x01(\x0b2'.com.weba\
+    nk.wedpr.acv.proto.VoteStorageR\x04vote\x12V\n\x0cballot_proof\x18\x02\
+    \x20\x03(\x0b23.com.webank.wedpr.acv.proto.StringToBallotProofPairR\x0bb\
+    allotProof\x12\x1f\n\x0brange_proof\x18\x03\x20\x01(\x0cR\nrangeProof\
+    \x12*\n\x11sum_balance_proof\x18\x04\x20\x01(\x0cR\x0fsumBalanceProof\
+    \x12m\n\x15unlisted_ballot_proof\x18\x05\x20\x03(\x0b29.com.webank.wedpr\
+    .acv.proto.CipherPointsToBallotProofPairR\x13unlistedBallotProof\x12!\n\
+    \x0cvoter_weight\x18\x06\x20\x01(\rR\x0bvoterWeight\x12!\n\x0cweight_pro\
+    of\x18\x07\x20\x01(\x0cR\x0bweightProof\x12&\n\x0fcap_range_proof\x18\
+    \x08\x20\x01(\x0cR\rcapRangeProof\x12/\n\x13authorization_token\x18\t\
+    \x20\x01(\x0cR\x12authorizationToken\x12\x17\n\x07poll_id\x18\n\x20\x01(\
+    \x0cR\x06pollId\x12/\n\x13authorization_nonce\x18\x0b\x20\x01(\x0cR\x12a\
+    uthorizationNonce\"\xb6\x03\n\x0bVoteStorage\x12\x1c\n\tsignature\x18\
+    \x01\x20\x01(\x0cR\tsignature\x12E\n\x0cblank_ballot\x18\x02\x20\x01(\
+    \x0b2\".com.webank.wedpr.acv.proto.BallotR\x0bblankBallot\x12C\n\x0brest\
+    _ballot\x18\x03\x20\x01(\x0b2\".com.webank.wedpr.acv.proto.BallotR\nrest\
+    Ballot\x12N\n\x0cvoted_ballot\x18\x04\x20\x03(\x0b2+.com.webank.wedpr.ac\
+    v.proto.CandidateBallotR\x0bvotedBallot\x12h\n\x15voted_ballot_unlisted\
+    \x18\x05\x20\x03(\x0b24.com.webank.wedpr.acv.proto.CipherPointsToBallotP\
+    airR\x13votedBallotUnlisted\x12C\n\x0bzero_ballot\x18\x06\x20\x01(\x0b2\
+    \".com.webank.wedpr.acv.proto.BallotR\nzeroBallot\"\x92\x01\n\x18CipherP\
+    ointsToBallotPair\x12:\n\x03key\x18\x01\x20\x01(\x0b2(.com.webank.wedpr.\
+    acv.proto.CipherPointsR\x03key\x12:\n\x06ballot\x18\x02\x20\x01(\x0b2\".\
+    com.webank.wedpr.acv.proto.BallotR\x06ballot\"\x9a\x01\n\x1dCipherPoints\
+    ToBallotProofPair\x12:\n\x03key\x18\x01\x20\x01(\x0b2(.com.webank.wedpr.\
+    acv.proto.CipherPointsR\x03key\x12=\n\x05value\x18\x02\x20\x01(\x0b2'.co\
+    m.webank.wedpr.acv.proto.BallotProofR\x05value\"R\n\x0cCipherPoints\x12\
+    \x20\n\x0bciphertext1\x18\x01\x20\x01(\x0cR\x0bciphertext1\x12\x20\n\x0b\
+    ciphertext2\x18\x02\x20\x01(\x0cR\x0bciphertext2\"u\n\x0cCountingPart\
+    \x12\x1d\n\ncounter_id\x18\x01\x20\x01(\tR\tcounterId\x12\x1f\n\x0bblind\
+    ing_c2\x18\x02\x20\x01(\x0cR\nblindingC2\x12%\n\x0eequality_proof\x18\
+    \x03\x20\x01(\x0cR\requalityProof\"l\n\x18StringToCountingPartPair\x12\
+    \x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12>\n\x05value\x18\x02\x20\x01\
+    (\x0b2(.com.webank.wedpr.acv.proto.CountingPartR\x05value\"\xb5\x02\n\
+    \x1aDecryptedResultPartStorage\x12G\n\nblank_part\x18\x01\x20\x01(\x0b2(\
+    .com.webank.wedpr.acv.proto.CountingPartR\tblankPart\x12[\n\x0ecandidate\
+    _part\x18\x02\x20\x03(\x0b24.com.webank.wedpr.acv.proto.StringToCounting\
+    PartPairR\rcandidatePart\x12q\n\x17unlisted_candidate_part\x18\x03\x20\
+    \x03(\x0b29.com.webank.wedpr.acv.proto.UnlistedBallotDecryptedResultR\
+    \x15unlistedCandidatePart\"\xf7\x02\n\x1dUnlistedBallotDecryptedResult\
+    \x12\x1c\n\tcandidate\x18\x01\x20\x01(\x03R\tcandidate\x12S\n\x10candida\
+    te_cipher\x18\x02\x20\x01(\x0b2(.com.webank.wedpr.acv.proto.CipherPoints\
+    R\x0fcandidateCipher\x12j\n\x1cdecrypted_unlisted_candidate\x18\x04\x20\
+    \x01(\x0b2(.com.webank.wedpr.acv.proto.CountingPartR\x1adecryptedUnliste\
+    dCandidate\x12w\n#decrypted_unlisted_candidate_ballot\x18\x05\x20\x03(\
+    \x0b2(.com.webank.wedpr.acv.proto.CountingPartR\x20decryptedUnlistedCand\
+    idateBallot\"\xb3\x01\n\x11VoteResultStorage\x12E\n\x06result\x18\x01\
+    \x20\x03(\x0b2-.com.webank.wedpr.acv.proto.StringToInt64PairR\x06result\
+    \x12W\n\x0funlisted_result\x18\x02\x20\x03(\x0b2..com.webank.wedpr.acv.p\
+    roto.UnlistedVoteChoiceR\x0eunlistedResult\";\n\x11StringToInt64Pair\x12\
+    \x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\
+    \x01(\x03R\x05value\"\xf8\x02\n\x0ePollTranscript\x12Z\n\x0fpoll_paramet\
+    ers\x18\x01\x20\x01(\x0b21.com.webank.wedpr.acv.proto.PollParametersStor\
+    ageR\x0epollParameters\x12B\n\x08vote_sum\x18\x02\x20\x01(\x0b2'.com.web\
+    ank.wedpr.acv.proto.VoteStorageR\x07voteSum\x12v\n\x1baggregated_decrypt\
+    ed_result\x18\x03\x20\x01(\x0b26.com.webank.wedpr.acv.proto.DecryptedRes\
+    ultPartStorageR\x19aggregatedDecryptedResult\x12N\n\x0bvote_result\x18\
+    \x04\x20\x01(\x0b2-.com.webank.wedpr.acv.proto.VoteResultStorageR\nvoteR\
+    esult\"\xd8\x01\n\x13CounterReshareProof\x12\x1d\n\ncounter_id\x18\x01\
+    \x20\x01(\tR\tcounterId\x12V\n\tnew_share\x18\x02\x20\x03(\x0b29.com.web\
+    ank.wedpr.acv.proto.CounterParametersShareRequestR\x08newShare\x12#\n\rb\
+    inding_point\x18\x03\x20\x01(\x0cR\x0cbindingPoint\x12%\n\x0eequality_pr\
+    oof\x18\x04\x20\x01(\x0cR\requalityProofB\x1e\n\x1acom.webank.wedpr.acv.\
+    protoP\x01b\x06proto3\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
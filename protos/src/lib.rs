@@ -4,8 +4,11 @@
 #[macro_use]
 extern crate lazy_static;
 use crate::{
-    config::{HASH, SIGNATURE},
-    generated::{acv::Ballot, zkp::PBBalanceProof},
+    config::{HASH, SIGNATURE, VOTE_MESSAGE_DOMAIN},
+    generated::{
+        acv::{Ballot, PollParametersStorage},
+        zkp::PBBalanceProof,
+    },
 };
 use wedpr_l_utils::{
     error::WedprError,
@@ -66,16 +69,73 @@ pub fn pb_to_arithmetric_proof(
     })
 }
 
+// Hash and signature algorithms of a ballot signature, and the poll the
+// signed message is bound to. An empty poll id and epoch 0 bind the message
+// to no poll id or epoch.
+pub struct BallotSignatureParams<'a, H: Hash, S: Signature> {
+    pub hash: &'a H,
+    pub signature: &'a S,
+    pub poll_id: &'a [u8],
+    pub poll_epoch: u64,
+}
+
+impl<'a, H: Hash, S: Signature> BallotSignatureParams<'a, H, S> {
+    pub fn new(hash: &'a H, signature: &'a S) -> Self {
+        BallotSignatureParams {
+            hash,
+            signature,
+            poll_id: &[],
+            poll_epoch: 0,
+        }
+    }
+
+    pub fn poll_id(mut self, poll_id: &'a [u8]) -> Self {
+        self.poll_id = poll_id;
+        self
+    }
+
+    pub fn poll_epoch(mut self, poll_epoch: u64) -> Self {
+        self.poll_epoch = poll_epoch;
+        self
+    }
+
+    // Weight and zero ballots of unbounded polls, and legacy blank ballots,
+    // are signed without a poll binding, so params carrying one are an
+    // error rather than silently ignored.
+    fn check_unbound(&self) -> Result<(), WedprError> {
+        if !self.poll_id.is_empty() || self.poll_epoch != 0 {
+            return Err(WedprError::ArgumentError);
+        }
+        Ok(())
+    }
+}
+
 // generate signature for the ballot
 pub fn generate_ballot_signature(
     secret_key: &[u8],
     ballot: &Ballot,
 ) -> Result<Vec<u8>, WedprError> {
-    let mut hash_vec = Vec::new();
-    hash_vec.append(&mut ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut ballot.get_ciphertext2().to_vec());
-    let message_hash = HASH.hash(&hash_vec);
-    SIGNATURE.sign(secret_key, &message_hash)
+    generate_ballot_signature_with_params(
+        &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+        secret_key,
+        ballot,
+    )
+}
+
+// generate signature for the ballot with the given algorithms, bound to the
+// poll id and epoch of the params
+pub fn generate_ballot_signature_with_params<H: Hash, S: Signature>(
+    params: &BallotSignatureParams<H, S>,
+    secret_key: &[u8],
+    ballot: &Ballot,
+) -> Result<Vec<u8>, WedprError> {
+    let message_hash = vote_message_hash_with(
+        params.hash,
+        params.poll_id,
+        params.poll_epoch,
+        ballot,
+    );
+    params.signature.sign(secret_key, &message_hash)
 }
 
 pub fn generate_ballots_signature(
@@ -83,13 +143,27 @@ pub fn generate_ballots_signature(
     weight_ballot: &Ballot,
     zero_ballot: &Ballot,
 ) -> Result<Vec<u8>, WedprError> {
-    let mut hash_vec = Vec::new();
-    hash_vec.append(&mut weight_ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut weight_ballot.get_ciphertext2().to_vec());
-    hash_vec.append(&mut zero_ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut zero_ballot.get_ciphertext2().to_vec());
-    let message_hash = HASH.hash(&hash_vec);
-    SIGNATURE.sign(secret_key, &message_hash)
+    generate_ballots_signature_with_params(
+        &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+        secret_key,
+        weight_ballot,
+        zero_ballot,
+    )
+}
+
+// generate signature for the weight and zero ballots with the given
+// algorithms; the params must not carry a poll id or epoch
+pub fn generate_ballots_signature_with_params<H: Hash, S: Signature>(
+    params: &BallotSignatureParams<H, S>,
+    secret_key: &[u8],
+    weight_ballot: &Ballot,
+    zero_ballot: &Ballot,
+) -> Result<Vec<u8>, WedprError> {
+    params.check_unbound()?;
+    let message_hash = params
+        .hash
+        .hash(&ballots_message(weight_ballot, zero_ballot));
+    params.signature.sign(secret_key, &message_hash)
 }
 
 pub fn verify_ballot_signature(
@@ -97,11 +171,48 @@ pub fn verify_ballot_signature(
     ballot: &Ballot,
     signature: &Vec<u8>,
 ) -> Result<bool, WedprError> {
-    let mut hash_vec = Vec::new();
-    hash_vec.append(&mut ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut ballot.get_ciphertext2().to_vec());
-    let message_hash: Vec<u8> = HASH.hash(&hash_vec);
-    Ok(SIGNATURE.verify(
+    verify_ballot_signature_with_params(
+        &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+        public_key,
+        ballot,
+        signature,
+    )
+}
+
+// verify signature for the ballot with the given algorithms, bound to the
+// poll id and epoch of the params
+pub fn verify_ballot_signature_with_params<H: Hash, S: Signature>(
+    params: &BallotSignatureParams<H, S>,
+    public_key: &[u8],
+    ballot: &Ballot,
+    signature: &Vec<u8>,
+) -> Result<bool, WedprError> {
+    let message_hash: Vec<u8> = vote_message_hash_with(
+        params.hash,
+        params.poll_id,
+        params.poll_epoch,
+        ballot,
+    );
+    Ok(params.signature.verify(
+        &public_key,
+        &message_hash.as_ref(),
+        &signature.as_slice(),
+    ))
+}
+
+// verify signature for the ballot over the bare ciphertexts, as signed before
+// the domain tag. Only for blank ballots certified by those releases: the
+// bare message is not domain-separated from other uses of the key. The
+// params must not carry a poll id or epoch.
+pub fn verify_legacy_ballot_signature<H: Hash, S: Signature>(
+    params: &BallotSignatureParams<H, S>,
+    public_key: &[u8],
+    ballot: &Ballot,
+    signature: &Vec<u8>,
+) -> Result<bool, WedprError> {
+    params.check_unbound()?;
+    let message_hash: Vec<u8> = params.hash.hash(&ballot_message(ballot, 0));
+    Ok(params.signature.verify(
         &public_key,
         &message_hash.as_ref(),
         &signature.as_slice(),
@@ -114,19 +225,86 @@ pub fn verify_ballots_signature(
     zero_ballot: &Ballot,
     signature: &Vec<u8>,
 ) -> Result<bool, WedprError> {
-    let mut hash_vec = Vec::new();
-    hash_vec.append(&mut weight_ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut weight_ballot.get_ciphertext2().to_vec());
-    hash_vec.append(&mut zero_ballot.get_ciphertext1().to_vec());
-    hash_vec.append(&mut zero_ballot.get_ciphertext2().to_vec());
-    let message_hash: Vec<u8> = HASH.hash(&hash_vec);
-    Ok(SIGNATURE.verify(
+    verify_ballots_signature_with_params(
+        &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+        public_key,
+        weight_ballot,
+        zero_ballot,
+        signature,
+    )
+}
+
+// verify signature for the weight and zero ballots with the given
+// algorithms; the params must not carry a poll id or epoch
+pub fn verify_ballots_signature_with_params<H: Hash, S: Signature>(
+    params: &BallotSignatureParams<H, S>,
+    public_key: &[u8],
+    weight_ballot: &Ballot,
+    zero_ballot: &Ballot,
+    signature: &Vec<u8>,
+) -> Result<bool, WedprError> {
+    params.check_unbound()?;
+    let message_hash: Vec<u8> = params
+        .hash
+        .hash(&ballots_message(weight_ballot, zero_ballot));
+    Ok(params.signature.verify(
         &public_key,
         &message_hash.as_ref(),
         &signature.as_slice(),
     ))
 }
 
+// hash of the message signed into the blank ballot of a poll
+pub fn vote_message_hash(
+    poll_parameters: &PollParametersStorage,
+    blank_ballot: &Ballot,
+) -> Vec<u8> {
+    vote_message_hash_with(
+        &*HASH,
+        poll_parameters.get_poll_id(),
+        poll_parameters.get_poll_epoch(),
+        blank_ballot,
+    )
+}
+
+// hash of the message signed into a blank ballot with the given hash
+// algorithm. The ballot message is prefixed with the domain tag and the
+// length-prefixed poll id, which is empty for polls without an id, so that
+// signatures made for other purposes or other polls are rejected.
+fn vote_message_hash_with<H: Hash>(
+    hash: &H,
+    poll_id: &[u8],
+    poll_epoch: u64,
+    blank_ballot: &Ballot,
+) -> Vec<u8> {
+    let mut hash_vec = Vec::new();
+    hash_vec.extend_from_slice(VOTE_MESSAGE_DOMAIN.as_bytes());
+    hash_vec.extend_from_slice(&(poll_id.len() as u64).to_be_bytes());
+    hash_vec.extend_from_slice(poll_id);
+    hash_vec.append(&mut ballot_message(blank_ballot, poll_epoch));
+    hash.hash(&hash_vec)
+}
+
+// Concatenates the ciphertexts of a ballot, followed by the big-endian poll
+// epoch when it is set.
+fn ballot_message(ballot: &Ballot, poll_epoch: u64) -> Vec<u8> {
+    let mut hash_vec = Vec::new();
+    hash_vec.append(&mut ballot.get_ciphertext1().to_vec());
+    hash_vec.append(&mut ballot.get_ciphertext2().to_vec());
+    if poll_epoch != 0 {
+        hash_vec.extend_from_slice(&poll_epoch.to_be_bytes());
+    }
+    hash_vec
+}
+
+// Concatenates the ciphertexts of the weight and zero ballots of an unbounded
+// poll.
+fn ballots_message(weight_ballot: &Ballot, zero_ballot: &Ballot) -> Vec<u8> {
+    let mut hash_vec = ballot_message(weight_ballot, 0);
+    hash_vec.append(&mut ballot_message(zero_ballot, 0));
+    hash_vec
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +319,60 @@ mod tests {
         let proof_parser = bytes_to_proto::<PBBalanceProof>(&bytes).unwrap();
         assert_eq!(proof_parser, proof);
     }
+
+    #[test]
+    fn test_vote_message_domain() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let mut ballot = Ballot::new();
+        ballot.set_ciphertext1(vec![1u8; 32]);
+        ballot.set_ciphertext2(vec![2u8; 32]);
+
+        // A signature over the bare ciphertexts, as made before the domain
+        // tag or for another purpose, is not a ballot signature.
+        let bare_signature = SIGNATURE
+            .sign(&secret_key, &HASH.hash(&ballot_message(&ballot, 0)))
+            .unwrap();
+        assert!(!verify_ballot_signature(
+            &public_key,
+            &ballot,
+            &bare_signature
+        )
+        .unwrap());
+        assert!(verify_legacy_ballot_signature(
+            &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+            &public_key,
+            &ballot,
+            &bare_signature
+        )
+        .unwrap());
+
+        // Polls without an id sign the tagged message too.
+        let signature =
+            generate_ballot_signature(&secret_key, &ballot).unwrap();
+        assert!(
+            verify_ballot_signature(&public_key, &ballot, &signature).unwrap()
+        );
+        assert!(!verify_legacy_ballot_signature(
+            &BallotSignatureParams::new(&*HASH, &*SIGNATURE),
+            &public_key,
+            &ballot,
+            &signature
+        )
+        .unwrap());
+        assert!(!verify_ballot_signature_with_params(
+            &BallotSignatureParams::new(&*HASH, &*SIGNATURE).poll_id(b"poll-1"),
+            &public_key,
+            &ballot,
+            &signature
+        )
+        .unwrap());
+        // Unbounded ballots are not bound to a poll.
+        assert!(verify_legacy_ballot_signature(
+            &BallotSignatureParams::new(&*HASH, &*SIGNATURE).poll_epoch(1),
+            &public_key,
+            &ballot,
+            &bare_signature
+        )
+        .is_err());
+    }
 }
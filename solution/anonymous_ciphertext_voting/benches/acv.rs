@@ -0,0 +1,259 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Performance tests for ACV components.
+//!
+//! Run `cargo bench` and `cargo bench --features parallel` to compare the
+//! sequential and the parallel verifier.
+
+extern crate criterion;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+extern crate wedpr_s_anonymous_ciphertext_voting;
+use std::convert::TryFrom;
+use wedpr_l_crypto_zkp_utils::{
+    get_random_scalar, point_to_bytes, BASEPOINT_G1, BASEPOINT_G2,
+};
+use wedpr_l_utils::traits::Signature;
+use wedpr_s_anonymous_ciphertext_voting::{
+    context::VerificationContext, coordinator, counter,
+    parameters::ParsedPollParameters, suite::default_suite, verifier, voter,
+};
+use wedpr_s_protos::{
+    config::SIGNATURE,
+    generated::acv::{
+        Ballot, CandidateBallot, CandidateList, CounterParametersStorage,
+        PollParametersStorage, VoteRequest, VoteStorage,
+    },
+};
+
+fn make_poll_parameters(candidate_count: usize) -> PollParametersStorage {
+    let mut counter_parameters = CounterParametersStorage::new();
+    for counter_id in &["1001", "1002", "1003"] {
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share(counter_id, &counter_secret)
+                .unwrap(),
+        );
+    }
+    let mut candidate_list = CandidateList::new();
+    for index in 0..candidate_count {
+        candidate_list
+            .mut_candidate()
+            .push(format!("candidate_{}", index));
+    }
+    coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+        .unwrap()
+}
+
+fn make_vote_request(
+    poll_parameters: &PollParametersStorage,
+    secret_key: &[u8],
+) -> VoteRequest {
+    let candidate_count =
+        poll_parameters.get_candidates().get_candidate().len();
+    let voter_secret = voter::make_voter_secret();
+    let registration_request =
+        voter::make_registration_request(&voter_secret, poll_parameters)
+            .unwrap();
+    let registration_response = coordinator::certify_voter(
+        secret_key,
+        &registration_request,
+        candidate_count as u32,
+    )
+    .unwrap();
+    let vote_choices = voter::make_vote_choices(
+        &vec![1; candidate_count],
+        poll_parameters.get_candidates(),
+    );
+    voter::vote(
+        &voter_secret,
+        &vote_choices,
+        &registration_response,
+        poll_parameters,
+    )
+    .unwrap()
+}
+
+fn create_verify_vote_request_helper(c: &mut Criterion) {
+    let candidate_count = 64;
+    let label = format!(
+        "create_verify_vote_request_helper, candidate count = {}",
+        candidate_count
+    );
+
+    let (public_key, secret_key) = SIGNATURE.generate_keypair();
+    let poll_parameters = make_poll_parameters(candidate_count);
+    let vote_request = make_vote_request(&poll_parameters, &secret_key);
+
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            assert!(verifier::verify_vote_request(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .unwrap());
+        });
+    });
+}
+
+fn create_verify_vote_request_with_points_helper(c: &mut Criterion) {
+    let candidate_count = 1000;
+    let (public_key, secret_key) = SIGNATURE.generate_keypair();
+    let poll_parameters = make_poll_parameters(candidate_count);
+    let parsed_parameters =
+        ParsedPollParameters::try_from(&poll_parameters).unwrap();
+    let vote_request = make_vote_request(&poll_parameters, &secret_key);
+    let points = verifier::VoteRequestPoints::try_from(&vote_request).unwrap();
+
+    let label = format!(
+        "create_verify_vote_request_parsed_helper, candidate count = {}",
+        candidate_count
+    );
+    let bytes_parameters = parsed_parameters.clone();
+    let bytes_request = vote_request.clone();
+    let bytes_public_key = public_key.clone();
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            assert!(verifier::verify_vote_request_with_options(
+                &bytes_parameters,
+                &bytes_request,
+                &bytes_public_key,
+                verifier::VoteVerifyOptions::new(&default_suite())
+            )
+            .is_ok());
+        });
+    });
+
+    let label = format!(
+        "create_verify_vote_request_with_points_helper, candidate count = {}",
+        candidate_count
+    );
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            assert!(verifier::verify_vote_request_with_options(
+                &parsed_parameters,
+                &vote_request,
+                &public_key,
+                verifier::VoteVerifyOptions::new(&default_suite())
+                    .points(&points)
+            )
+            .is_ok());
+        });
+    });
+}
+
+fn create_verification_context_helper(c: &mut Criterion) {
+    let candidate_count = 64;
+    let request_count = 10;
+    let (public_key, secret_key) = SIGNATURE.generate_keypair();
+    let poll_parameters = make_poll_parameters(candidate_count);
+    let vote_requests: Vec<VoteRequest> = (0..request_count)
+        .map(|_| make_vote_request(&poll_parameters, &secret_key))
+        .collect();
+
+    let label = format!(
+        "create_verify_vote_request_per_call_helper, candidate count = {}, \
+         request count = {}",
+        candidate_count, request_count
+    );
+    let per_call_parameters = poll_parameters.clone();
+    let per_call_requests = vote_requests.clone();
+    let per_call_public_key = public_key.clone();
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            for vote_request in &per_call_requests {
+                assert!(verifier::verify_vote_request(
+                    &per_call_parameters,
+                    vote_request,
+                    &per_call_public_key
+                )
+                .unwrap());
+            }
+        });
+    });
+
+    let label = format!(
+        "create_verification_context_helper, candidate count = {}, request \
+         count = {}",
+        candidate_count, request_count
+    );
+    let verification_context =
+        VerificationContext::new(&poll_parameters).unwrap();
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            for vote_request in &vote_requests {
+                assert!(verification_context
+                    .verify(vote_request, &public_key)
+                    .unwrap());
+            }
+        });
+    });
+}
+
+fn make_random_ballot() -> Ballot {
+    let mut ballot = Ballot::new();
+    ballot.set_ciphertext1(point_to_bytes(
+        &(*BASEPOINT_G1 * get_random_scalar()),
+    ));
+    ballot.set_ciphertext2(point_to_bytes(
+        &(*BASEPOINT_G2 * get_random_scalar()),
+    ));
+    ballot
+}
+
+// Aggregation only reads the ciphertexts, so random ballots are enough and
+// avoid proving thousands of vote requests during setup.
+fn make_random_vote_part(
+    poll_parameters: &PollParametersStorage,
+) -> VoteStorage {
+    let mut vote_part = VoteStorage::new();
+    vote_part.set_blank_ballot(make_random_ballot());
+    for candidate in poll_parameters.get_candidates().get_candidate() {
+        let mut ballot_pair = CandidateBallot::new();
+        ballot_pair.set_candidate(candidate.to_string());
+        ballot_pair.set_ballot(make_random_ballot());
+        vote_part.mut_voted_ballot().push(ballot_pair);
+    }
+    vote_part
+}
+
+fn create_aggregate_vote_sum_response_helper(c: &mut Criterion) {
+    let candidate_count = 50;
+    let vote_part_count = 10000;
+    let label = format!(
+        "create_aggregate_vote_sum_response_helper, candidate count = {}, \
+         vote part count = {}",
+        candidate_count, vote_part_count
+    );
+
+    let poll_parameters = make_poll_parameters(candidate_count);
+    let vote_parts: Vec<VoteStorage> = (0..vote_part_count)
+        .map(|_| make_random_vote_part(&poll_parameters))
+        .collect();
+
+    c.bench_function(&label, move |b| {
+        b.iter(|| {
+            let mut vote_sum = VoteStorage::new();
+            for vote_part in &vote_parts {
+                assert!(coordinator::aggregate_vote_sum_response(
+                    &poll_parameters,
+                    vote_part,
+                    &mut vote_sum
+                )
+                .unwrap());
+            }
+        });
+    });
+}
+
+criterion_group! {
+    name = acv_benches;
+    config = Criterion::default().sample_size(10);
+    targets =
+    create_verify_vote_request_helper,
+    create_verify_vote_request_with_points_helper,
+    create_verification_context_helper,
+    create_aggregate_vote_sum_response_helper
+}
+criterion_main!(acv_benches);
@@ -0,0 +1,209 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Runs a whole ACV poll described in JSON and prints the tally.
+//!
+//! Run `cargo run --example acv_cli -- examples/acv_cli.json`, or pipe the
+//! JSON to stdin. Each voter is certified with its weight and votes its
+//! choices, one per candidate. A voter marked `tampered` sends a vote request
+//! with a broken range proof, whose verification failure is printed and whose
+//! ballots are left out of the tally.
+
+use serde::Deserialize;
+use std::{
+    convert::TryFrom,
+    env, fs,
+    io::{self, Read},
+    process,
+};
+use wedpr_l_crypto_zkp_utils::bytes_to_point;
+use wedpr_l_utils::{error::WedprError, traits::Signature};
+use wedpr_s_anonymous_ciphertext_voting::{
+    codec, coordinator, counter, parameters::ParsedPollParameters, result,
+    suite::default_suite, verifier, voter,
+};
+use wedpr_s_protos::{
+    config::SIGNATURE,
+    generated::acv::{
+        CandidateList, CounterParametersStorage, DecryptedResultPartStorage,
+        VoteStorage,
+    },
+};
+
+#[derive(Deserialize)]
+struct PollInput {
+    candidates: Vec<String>,
+    counters: Vec<String>,
+    max_vote_limit: i64,
+    voters: Vec<VoterInput>,
+}
+
+#[derive(Deserialize)]
+struct VoterInput {
+    weight: u32,
+    choices: Vec<u32>,
+    #[serde(default)]
+    tampered: bool,
+}
+
+fn main() {
+    let result = read_input().and_then(|input| run_poll(&input));
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+fn read_input() -> Result<PollInput, String> {
+    let json = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path, e))?,
+        None => {
+            let mut json = String::new();
+            io::stdin()
+                .read_to_string(&mut json)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            json
+        },
+    };
+    serde_json::from_str(&json).map_err(|e| format!("invalid poll: {}", e))
+}
+
+fn run_poll(input: &PollInput) -> Result<(), String> {
+    // Counters share the poll key and the coordinator opens the poll.
+    let mut counter_secret_list = vec![];
+    let mut counter_parameters = CounterParametersStorage::new();
+    for counter_id in &input.counters {
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share(counter_id, &counter_secret)
+                .map_err(describe)?,
+        );
+        counter_secret_list.push(counter_secret);
+    }
+    let mut candidate_list = CandidateList::new();
+    for candidate in &input.candidates {
+        candidate_list.mut_candidate().push(candidate.to_string());
+    }
+    let (public_key, secret_key) = SIGNATURE.generate_keypair();
+    let poll_parameters =
+        coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+            .map_err(describe)?;
+    let parsed_parameters =
+        ParsedPollParameters::try_from(&poll_parameters).map_err(describe)?;
+
+    // Voters register and vote, and the coordinator verifies and aggregates
+    // the ballots received as hex strings.
+    let mut encrypted_vote_sum = VoteStorage::new();
+    for (index, voter_input) in input.voters.iter().enumerate() {
+        if voter_input.choices.len() != input.candidates.len() {
+            return Err(format!(
+                "voter {} must vote for each candidate",
+                index
+            ));
+        }
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .map_err(describe)?;
+        let registration_response = coordinator::certify_voter(
+            &secret_key,
+            &registration_request,
+            voter_input.weight,
+        )
+        .map_err(describe)?;
+        let mut vote_request = voter::vote(
+            &voter_secret,
+            &voter::make_vote_choices(&voter_input.choices, &candidate_list),
+            &registration_response,
+            &poll_parameters,
+        )
+        .map_err(|e| format!("voter {} cannot vote: {:?}", index, e))?;
+        if voter_input.tampered {
+            vote_request.mut_range_proof()[0] ^= 1;
+        }
+        let vote_request_hex =
+            codec::vote_request_to_hex(&vote_request).map_err(describe)?;
+
+        let received_request = codec::vote_request_from_hex(&vote_request_hex)
+            .map_err(describe)?;
+        if let Err(failure) = verifier::verify_vote_request_with_options(
+            &parsed_parameters,
+            &received_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&default_suite()),
+        ) {
+            println!("voter {}: ballot rejected, {}", index, failure);
+            continue;
+        }
+        println!("voter {}: ballot accepted", index);
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            received_request.get_vote(),
+            &mut encrypted_vote_sum,
+        )
+        .map_err(describe)?;
+    }
+
+    // Counters decrypt their parts of the sum, which are verified and
+    // aggregated by the coordinator.
+    let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+    for (index, counter_id) in input.counters.iter().enumerate() {
+        let partially_decrypted_result = counter::count(
+            counter_id,
+            &counter_secret_list[index],
+            &encrypted_vote_sum,
+        )
+        .map_err(describe)?;
+        let counter_share = bytes_to_point(
+            counter_parameters.get_counter_parameters_share()[index]
+                .get_poll_point_share(),
+        )
+        .map_err(describe)?;
+        if let Err(failure) = verifier::verify_count_request_detailed(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &counter_share,
+            &partially_decrypted_result,
+        ) {
+            return Err(format!(
+                "counter {} rejected, {}",
+                counter_id, failure
+            ));
+        }
+        coordinator::aggregate_decrypted_part_sum(
+            &poll_parameters,
+            &partially_decrypted_result,
+            &mut aggregated_decrypted_result,
+        )
+        .map_err(describe)?;
+    }
+
+    // The coordinator recovers the tallies, which anyone can verify.
+    let vote_result = coordinator::finalize_vote_result(
+        &poll_parameters,
+        &encrypted_vote_sum,
+        &aggregated_decrypted_result,
+        input.max_vote_limit,
+    )
+    .map_err(describe)?;
+    if !verifier::verify_vote_result(
+        &poll_parameters,
+        &encrypted_vote_sum,
+        &aggregated_decrypted_result,
+        &vote_result,
+    )
+    .map_err(describe)?
+    {
+        return Err("vote result does not match the ballots".to_string());
+    }
+    let tally = result::decode_vote_result(&vote_result).map_err(describe)?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&tally).map_err(|e| e.to_string())?
+    );
+    Ok(())
+}
+
+fn describe(error: WedprError) -> String {
+    format!("{:?}", error)
+}
@@ -0,0 +1,62 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Feeds arbitrary bytes as a vote part into the vote sum aggregation, which
+//! must reject malformed input with an error instead of panicking.
+//!
+//! Run `cargo fuzz run aggregate_vote_sum_response` from the ACV crate
+//! directory.
+
+#![no_main]
+#[macro_use]
+extern crate lazy_static;
+use libfuzzer_sys::fuzz_target;
+use wedpr_s_anonymous_ciphertext_voting::{coordinator, counter};
+use wedpr_s_protos::{
+    bytes_to_proto,
+    generated::acv::{
+        CandidateList, CounterParametersStorage, PollParametersStorage,
+        VoteStorage,
+    },
+};
+
+lazy_static! {
+    static ref POLL_PARAMETERS: PollParametersStorage = {
+        let mut counter_parameters = CounterParametersStorage::new();
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share("1001", &counter_secret).unwrap(),
+        );
+        let mut candidate_list = CandidateList::new();
+        for candidate in ["Kitten", "Doge", "Bunny"] {
+            candidate_list.mut_candidate().push(candidate.to_string());
+        }
+        coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+            .unwrap()
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    let vote_part = match bytes_to_proto::<VoteStorage>(data) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    // Aggregate into an empty sum, then into the arbitrary part itself as a
+    // running sum.
+    let mut vote_sum = VoteStorage::new();
+    let _ = coordinator::aggregate_vote_sum_response(
+        &POLL_PARAMETERS,
+        &vote_part,
+        &mut vote_sum,
+    );
+    let mut vote_sum = vote_part.clone();
+    let _ = coordinator::aggregate_vote_sum_response(
+        &POLL_PARAMETERS,
+        &vote_part,
+        &mut vote_sum,
+    );
+    let _ = coordinator::aggregate_vote_sum_response_unlisted(
+        &POLL_PARAMETERS,
+        &vote_part,
+        &mut vote_sum,
+    );
+});
@@ -0,0 +1,66 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Feeds arbitrary bytes as a vote request into the verifier, which must
+//! reject malformed input with an error instead of panicking.
+//!
+//! Run `cargo fuzz run verify_vote_request` from the ACV crate directory.
+
+#![no_main]
+#[macro_use]
+extern crate lazy_static;
+use libfuzzer_sys::fuzz_target;
+use wedpr_l_utils::traits::Signature;
+use wedpr_s_anonymous_ciphertext_voting::{coordinator, counter, verifier};
+use wedpr_s_protos::{
+    bytes_to_proto,
+    config::SIGNATURE,
+    generate_ballot_signature,
+    generated::acv::{
+        CandidateList, CounterParametersStorage, PollParametersStorage,
+        VoteRequest,
+    },
+};
+
+lazy_static! {
+    static ref KEYPAIR: (Vec<u8>, Vec<u8>) = SIGNATURE.generate_keypair();
+    static ref POLL_PARAMETERS: PollParametersStorage = {
+        let mut counter_parameters = CounterParametersStorage::new();
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share("1001", &counter_secret).unwrap(),
+        );
+        let mut candidate_list = CandidateList::new();
+        for candidate in ["Kitten", "Doge", "Bunny"] {
+            candidate_list.mut_candidate().push(candidate.to_string());
+        }
+        coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+            .unwrap()
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut vote_request = match bytes_to_proto::<VoteRequest>(data) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let (public_key, secret_key) = &*KEYPAIR;
+    let _ = verifier::verify_vote_range_only(&POLL_PARAMETERS, &vote_request);
+    let _ = verifier::verify_vote_request(
+        &POLL_PARAMETERS,
+        &vote_request,
+        public_key,
+    );
+    // Sign the blank ballot as the coordinator would, so that the proof
+    // parsing after the signature check is reached too.
+    if let Ok(signature) = generate_ballot_signature(
+        secret_key,
+        vote_request.get_vote().get_blank_ballot(),
+    ) {
+        vote_request.mut_vote().set_signature(signature);
+        let _ = verifier::verify_vote_request(
+            &POLL_PARAMETERS,
+            &vote_request,
+            public_key,
+        );
+    }
+});
@@ -0,0 +1,486 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of anonymous yes/no voting, where each voter casts a ballot of 0
+//! or 1 for every candidate.
+//!
+//! Unlike bounded polls, ballots do not split a voter weight, so there is no
+//! rest ballot and no balance proof. Instead the range proof covers both
+//! v * G1 and (1 - v) * G1 of each ballot, which are both in range only when
+//! v is 0 or 1. Registration, aggregation, counting and result verification
+//! are those of bounded polls, certifying each voter with weight 1.
+//!
+//! Polls requiring exactly k selections also prove, in the balance proof
+//! field of the request, that the voted ballots sum to k: the sum and the
+//! identity point add up to k * G1 by the sum relationship proof of bounded
+//! polls, with the blank ballot replaced by the constant commitment to k.
+
+use crate::{
+    candidate::CandidateId,
+    common::{
+        ballot_points, verify_candidate_format_proofs, verify_candidate_set,
+        verify_input_limits, verify_poll_id, verify_range_proof_batch_size,
+        verify_vote_signature, vote_verify_result,
+    },
+    parameters::ParsedPollParameters,
+    secret::zeroize_scalars,
+    suite::default_suite,
+    trace::trace_stage,
+    utils::{
+        align_commitment_list_if_needed, align_scalar_list_if_needed,
+        align_u64_list_if_needed,
+    },
+    verifier::VoteVerifyFailure,
+};
+use curve25519_dalek::{
+    ristretto::RistrettoPoint,
+    scalar::Scalar,
+    traits::{Identity, MultiscalarMul},
+};
+use std::{collections::HashMap, convert::TryFrom};
+use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
+    prove_format_proof, prove_sum_relationship, verify_sum_relationship,
+};
+use wedpr_l_crypto_zkp_range_proof::{
+    prove_value_range_in_batch, verify_value_range_in_batch,
+};
+use wedpr_l_crypto_zkp_utils::{
+    bytes_to_point, get_random_scalar, point_to_bytes, Deserialize, Serialize,
+    BASEPOINT_G1, BASEPOINT_G2,
+};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    Ballot, BallotProof, CandidateBallot, PollParametersStorage,
+    RegistrationResponse, StringToBallotProofPair, VoteChoices, VoteRequest,
+};
+
+/// Votes 0 or 1 for each candidate and generates the associated ZKP proofs.
+pub fn vote(
+    vote_choices: &VoteChoices,
+    registration_response: &RegistrationResponse,
+    poll_parameters: &PollParametersStorage,
+) -> Result<VoteRequest, WedprError> {
+    let mut vote_request = VoteRequest::new();
+    vote_request.set_poll_id(poll_parameters.get_poll_id().to_vec());
+    let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
+    let mut choice_list: Vec<u64> = Vec::new();
+    let mut blinding_list: Vec<Scalar> = Vec::new();
+    for choice in vote_choices.get_choice() {
+        let value = choice.get_value() as u64;
+        if value > 1 {
+            return Err(WedprError::ArgumentError);
+        }
+
+        // Make a ciphertext ballot.
+        let blinding = get_random_scalar();
+        let ciphertext1 = RistrettoPoint::multiscalar_mul(
+            &[Scalar::from(value), blinding],
+            &[*BASEPOINT_G1, poll_point],
+        );
+        let ciphertext2 = *BASEPOINT_G2 * blinding;
+        let mut vote_ballot = Ballot::new();
+        vote_ballot.set_ciphertext1(point_to_bytes(&ciphertext1));
+        vote_ballot.set_ciphertext2(point_to_bytes(&ciphertext2));
+
+        // Prove ballot format.
+        let format_proof = prove_format_proof(
+            value,
+            &blinding,
+            &*BASEPOINT_G1,
+            &*BASEPOINT_G2,
+            &poll_point,
+        );
+        let mut ballot_proof = BallotProof::new();
+        ballot_proof.set_format_proof(format_proof.serialize());
+
+        // Write back.
+        let mut proof_pair = StringToBallotProofPair::new();
+        proof_pair.set_key(choice.get_candidate().to_string());
+        proof_pair.set_value(ballot_proof);
+        vote_request.mut_ballot_proof().push(proof_pair);
+        let mut ballot_pair = CandidateBallot::new();
+        ballot_pair.set_candidate(choice.get_candidate().to_string());
+        ballot_pair.set_ballot(vote_ballot);
+        vote_request.mut_vote().mut_voted_ballot().push(ballot_pair);
+
+        choice_list.push(value);
+        blinding_list.push(blinding);
+    }
+
+    let required_selections = poll_parameters.get_required_selections();
+    if required_selections != 0 {
+        let selection_count: u64 = choice_list.iter().sum();
+        if selection_count != required_selections as u64 {
+            wedpr_println!(
+                "{} selections instead of {}",
+                selection_count,
+                required_selections
+            );
+            return Err(WedprError::ArgumentError);
+        }
+        let blinding_sum: Scalar = blinding_list.iter().sum();
+        let selection_proof = prove_sum_relationship(
+            selection_count,
+            0,
+            &blinding_sum,
+            &Scalar::zero(),
+            &Scalar::zero(),
+            &BASEPOINT_G1,
+            &poll_point,
+        );
+        vote_request.set_sum_balance_proof(selection_proof.serialize());
+    }
+
+    // Prove the range of each ballot and of its complement, i.e.
+    // G1 - ciphertext1 committing to 1 - v with the negated blinding.
+    let complement_list: Vec<u64> =
+        choice_list.iter().map(|value| 1 - value).collect();
+    choice_list.extend(complement_list);
+    let complement_blinding_list: Vec<Scalar> =
+        blinding_list.iter().map(|blinding| -blinding).collect();
+    blinding_list.extend(complement_blinding_list);
+    align_u64_list_if_needed(&mut choice_list);
+    align_scalar_list_if_needed(&mut blinding_list);
+    let (range_proof, _) =
+        prove_value_range_in_batch(&choice_list, &blinding_list, &poll_point)?;
+    // Blindings are no longer needed once all proofs are made.
+    zeroize_scalars(&mut blinding_list);
+
+    // Write back.
+    vote_request.set_range_proof(range_proof);
+    let vote = vote_request.mut_vote();
+    vote.set_signature(registration_response.get_signature().to_vec());
+    vote.set_blank_ballot(registration_response.get_ballot().clone());
+    Ok(vote_request)
+}
+
+/// Verifies whether yes/no ciphertext ballots from a certified voter are
+/// valid.
+pub fn verify_vote_request(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<bool, WedprError> {
+    vote_verify_result(verify_vote_request_detailed(
+        poll_parameters,
+        vote_request,
+        public_key,
+    ))
+}
+
+/// Verifies whether yes/no ciphertext ballots from a certified voter are
+/// valid, reporting which check failed.
+pub fn verify_vote_request_detailed(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<(), VoteVerifyFailure> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    verify_input_limits(&parsed_parameters.limits, vote_request)?;
+    verify_candidate_set(&parsed_parameters.candidates, vote_request)?;
+    let vote = vote_request.get_vote();
+    ballot_points(vote.get_blank_ballot())?;
+    let mut ballot_index = HashMap::new();
+    let mut commitments = Vec::new();
+    for pair in vote.get_voted_ballot() {
+        let points = ballot_points(pair.get_ballot())?;
+        ballot_index.insert(CandidateId::new(pair.get_candidate()), points);
+        commitments.push(points.0);
+    }
+    verify_poll_id(&parsed_parameters, vote_request)?;
+    verify_vote_signature(
+        &default_suite(),
+        &parsed_parameters,
+        vote_request,
+        public_key,
+    )?;
+
+    let voted_ballot_sum: RistrettoPoint = commitments.iter().sum();
+    let complements: Vec<RistrettoPoint> = commitments
+        .iter()
+        .map(|commitment| *BASEPOINT_G1 - commitment)
+        .collect();
+    commitments.extend(complements);
+    align_commitment_list_if_needed(&mut commitments);
+    verify_range_proof_batch_size(
+        commitments.len(),
+        vote_request.get_range_proof(),
+    )?;
+    if !trace_stage("range_proof", || {
+        verify_value_range_in_batch(
+            &commitments,
+            vote_request.get_range_proof(),
+            &parsed_parameters.poll_point,
+        )
+    }) {
+        wedpr_println!("verify_range failed for yes/no ballots!");
+        return Err(VoteVerifyFailure::RangeProof);
+    }
+    if parsed_parameters.required_selections != 0 {
+        verify_selection_count(
+            &parsed_parameters,
+            vote_request,
+            &voted_ballot_sum,
+        )?;
+    }
+    verify_candidate_format_proofs(
+        &ballot_index,
+        vote_request.get_ballot_proof(),
+        &parsed_parameters.poll_point,
+    )
+}
+
+// Checks that the binary ballots sum to the required selections, so that
+// exactly that many candidates are selected.
+fn verify_selection_count(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    voted_ballot_sum: &RistrettoPoint,
+) -> Result<(), VoteVerifyFailure> {
+    let selection_proof =
+        Deserialize::deserialize(vote_request.get_sum_balance_proof())?;
+    let required_sum = *BASEPOINT_G1
+        * Scalar::from(parsed_parameters.required_selections as u64);
+    if !verify_sum_relationship(
+        voted_ballot_sum,
+        &RistrettoPoint::identity(),
+        &required_sum,
+        &selection_proof,
+        &BASEPOINT_G1,
+        &parsed_parameters.poll_point,
+    )? {
+        wedpr_println!("verify_selection_count failed!");
+        return Err(VoteVerifyFailure::SelectionCount);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::POLL_RESULT_KEY_TOTAL_BALLOTS,
+        coordinator, counter, parameters,
+        test_fixtures::{make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::{
+        config::SIGNATURE,
+        generated::acv::{DecryptedResultPartStorage, VoteStorage},
+    };
+
+    #[test]
+    fn test_anonymous_yes_no_voting() {
+        let counter_id_list = ["1001", "1002"];
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&counter_id_list);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        // Each voter picks one of the candidates.
+        let voter_choice_list: Vec<Vec<u32>> =
+            vec![vec![1, 0, 0], vec![0, 1, 0], vec![1, 0, 0], vec![0, 0, 1]];
+        let mut vote_request_list = vec![];
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for voter_choice in &voter_choice_list {
+            let voter_secret = voter::make_voter_secret();
+            let registration_request = voter::make_registration_request(
+                &voter_secret,
+                &poll_parameters,
+            )
+            .unwrap();
+            let registration_response = coordinator::certify_voter(
+                &secret_key,
+                &registration_request,
+                1,
+            )
+            .unwrap();
+            let vote_request = vote(
+                &voter::make_vote_choices(voter_choice, &candidate_list),
+                &registration_response,
+                &poll_parameters,
+            )
+            .unwrap();
+            assert!(verify_vote_request(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .unwrap());
+            assert!(coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                &vote_request.get_vote(),
+                &mut encrypted_vote_sum
+            )
+            .unwrap());
+            vote_request_list.push((vote_request, registration_response));
+        }
+
+        // Counting and results are shared with bounded polls.
+        let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+        for index in 0..counter_secret_list.len() {
+            let partially_decrypted_result = counter::count(
+                &counter_id_list[index],
+                &counter_secret_list[index],
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            let counter_share = bytes_to_point(
+                counter_parameters.get_counter_parameters_share()[index]
+                    .get_poll_point_share(),
+            )
+            .unwrap();
+            assert!(verifier::verify_count_request(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &counter_share,
+                &partially_decrypted_result
+            )
+            .unwrap());
+            assert!(coordinator::aggregate_decrypted_part_sum(
+                &poll_parameters,
+                &partially_decrypted_result,
+                &mut aggregated_decrypted_result
+            )
+            .unwrap());
+        }
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            10,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+        let tallies: Vec<(String, i64)> = vote_result
+            .get_result()
+            .iter()
+            .map(|pair| (pair.get_key().to_string(), pair.get_value()))
+            .collect();
+        for (key, expected) in [
+            (POLL_RESULT_KEY_TOTAL_BALLOTS, 4),
+            ("Kitten", 2),
+            ("Doge", 1),
+            ("Bunny", 1),
+        ] {
+            assert!(tallies.contains(&(key.to_string(), expected)));
+        }
+
+        // Ballots other than 0 or 1 cannot be voted or proved.
+        let (vote_request, registration_response) = &vote_request_list[0];
+        assert!(matches!(
+            vote(
+                &voter::make_vote_choices(&vec![2, 0, 0], &candidate_list),
+                registration_response,
+                &poll_parameters,
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+        let mut tampered_request = vote_request.clone();
+        let tampered_ballot =
+            tampered_request.mut_vote().mut_voted_ballot()[0].mut_ballot();
+        let tampered_c1 = bytes_to_point(tampered_ballot.get_ciphertext1())
+            .unwrap()
+            + *BASEPOINT_G1;
+        tampered_ballot.set_ciphertext1(point_to_bytes(&tampered_c1));
+        assert!(matches!(
+            verify_vote_request_detailed(
+                &poll_parameters,
+                &tampered_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::RangeProof)
+        ));
+    }
+
+    #[test]
+    fn test_required_selections() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let open_poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let make_poll = |required_selections| {
+            parameters::PollParametersBuilder::new()
+                .poll_point(open_poll_parameters.get_poll_point())
+                .candidates(["Kitten", "Doge", "Bunny"])
+                .required_selections(required_selections)
+                .build()
+        };
+        let poll_parameters = make_poll(2).unwrap();
+        assert!(matches!(make_poll(4), Err(WedprError::ArgumentError)));
+
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 1)
+                .unwrap();
+        let vote_for =
+            |choices: Vec<u32>, poll_parameters: &PollParametersStorage| {
+                vote(
+                    &voter::make_vote_choices(&choices, &candidate_list),
+                    &registration_response,
+                    poll_parameters,
+                )
+            };
+        let vote_request = vote_for(vec![1, 0, 1], &poll_parameters).unwrap();
+        assert!(verify_vote_request_detailed(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .is_ok());
+
+        // Voters cannot select another number of candidates.
+        for choices in [vec![1, 1, 1], vec![0, 1, 0]] {
+            assert!(matches!(
+                vote_for(choices, &poll_parameters),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+
+        // A ballot selecting k + 1 candidates, proved for another poll, is
+        // rejected, as is a ballot without a selection proof.
+        let all_request =
+            vote_for(vec![1, 1, 1], &make_poll(3).unwrap()).unwrap();
+        assert!(verify_vote_request_detailed(
+            &make_poll(3).unwrap(),
+            &all_request,
+            &public_key
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_vote_request_detailed(
+                &poll_parameters,
+                &all_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::SelectionCount)
+        ));
+        let open_request =
+            vote_for(vec![1, 1, 1], &open_poll_parameters).unwrap();
+        assert!(open_request.get_sum_balance_proof().is_empty());
+        assert!(verify_vote_request_detailed(
+            &poll_parameters,
+            &open_request,
+            &public_key
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of homomorphic arithmetic on ciphertext ballots.
+//!
+//! Ballots are ElGamal-style ciphertexts, so adding or subtracting both
+//! ciphertexts of two ballots adds or subtracts the values they encrypt.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, traits::Identity};
+use std::{
+    convert::TryFrom,
+    ops::{Add, Neg, Sub},
+};
+use wedpr_l_crypto_zkp_utils::{bytes_to_point, point_to_bytes};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::Ballot;
+
+/// Ballot with both ciphertexts decompressed, for summing ballots without
+/// converting points to and from bytes at each step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HomomorphicBallot {
+    pub ciphertext1: RistrettoPoint,
+    pub ciphertext2: RistrettoPoint,
+}
+
+impl HomomorphicBallot {
+    /// Returns the ballot encrypting 0 with randomness 0, which is the
+    /// identity of ballot addition.
+    pub fn zero() -> Self {
+        HomomorphicBallot {
+            ciphertext1: RistrettoPoint::identity(),
+            ciphertext2: RistrettoPoint::identity(),
+        }
+    }
+}
+
+impl TryFrom<&Ballot> for HomomorphicBallot {
+    type Error = WedprError;
+
+    fn try_from(ballot: &Ballot) -> Result<Self, Self::Error> {
+        Ok(HomomorphicBallot {
+            ciphertext1: bytes_to_point(ballot.get_ciphertext1())?,
+            ciphertext2: bytes_to_point(ballot.get_ciphertext2())?,
+        })
+    }
+}
+
+impl From<HomomorphicBallot> for Ballot {
+    fn from(ballot: HomomorphicBallot) -> Self {
+        let mut encoded_ballot = Ballot::new();
+        encoded_ballot.set_ciphertext1(point_to_bytes(&ballot.ciphertext1));
+        encoded_ballot.set_ciphertext2(point_to_bytes(&ballot.ciphertext2));
+        encoded_ballot
+    }
+}
+
+impl Add for HomomorphicBallot {
+    type Output = HomomorphicBallot;
+
+    fn add(self, other: HomomorphicBallot) -> HomomorphicBallot {
+        HomomorphicBallot {
+            ciphertext1: self.ciphertext1 + other.ciphertext1,
+            ciphertext2: self.ciphertext2 + other.ciphertext2,
+        }
+    }
+}
+
+impl Sub for HomomorphicBallot {
+    type Output = HomomorphicBallot;
+
+    fn sub(self, other: HomomorphicBallot) -> HomomorphicBallot {
+        HomomorphicBallot {
+            ciphertext1: self.ciphertext1 - other.ciphertext1,
+            ciphertext2: self.ciphertext2 - other.ciphertext2,
+        }
+    }
+}
+
+impl Neg for HomomorphicBallot {
+    type Output = HomomorphicBallot;
+
+    fn neg(self) -> HomomorphicBallot {
+        HomomorphicBallot {
+            ciphertext1: -self.ciphertext1,
+            ciphertext2: -self.ciphertext2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coordinator, test_fixtures::make_candidate_list};
+    use wedpr_l_crypto_zkp_utils::{get_random_scalar, BASEPOINT_G1};
+    use wedpr_s_protos::generated::acv::{
+        CandidateBallot, PollParametersStorage, VoteStorage,
+    };
+
+    #[test]
+    fn test_homomorphic_ballot() {
+        use crate::config::ZERO_BALLOT;
+
+        let random_ballot = || {
+            let mut ballot = Ballot::new();
+            ballot.set_ciphertext1(point_to_bytes(
+                &(*BASEPOINT_G1 * get_random_scalar()),
+            ));
+            ballot.set_ciphertext2(point_to_bytes(
+                &(*BASEPOINT_G1 * get_random_scalar()),
+            ));
+            ballot
+        };
+        let left_ballot = random_ballot();
+        let right_ballot = random_ballot();
+        let left = HomomorphicBallot::try_from(&left_ballot).unwrap();
+        let right = HomomorphicBallot::try_from(&right_ballot).unwrap();
+
+        // Arithmetic matches adding the decompressed points by hand.
+        let mut manual_sum = Ballot::new();
+        manual_sum.set_ciphertext1(point_to_bytes(
+            &(bytes_to_point(left_ballot.get_ciphertext1()).unwrap()
+                + bytes_to_point(right_ballot.get_ciphertext1()).unwrap()),
+        ));
+        manual_sum.set_ciphertext2(point_to_bytes(
+            &(bytes_to_point(left_ballot.get_ciphertext2()).unwrap()
+                + bytes_to_point(right_ballot.get_ciphertext2()).unwrap()),
+        ));
+        assert_eq!(Ballot::from(left + right), manual_sum);
+        assert_eq!(left + right - right, left);
+        assert_eq!(left - left, HomomorphicBallot::zero());
+        assert_eq!(-left + left, HomomorphicBallot::zero());
+        assert_eq!(left + HomomorphicBallot::zero(), left);
+        assert_eq!(Ballot::from(HomomorphicBallot::zero()), *ZERO_BALLOT);
+        assert_eq!(Ballot::from(left), left_ballot);
+
+        let mut invalid_ballot = left_ballot.clone();
+        invalid_ballot.set_ciphertext2(vec![1; 31]);
+        assert!(HomomorphicBallot::try_from(&invalid_ballot).is_err());
+
+        // Aggregation sums ballots like the wrapper.
+        let candidate_list = make_candidate_list(&["Kitten"]);
+        let mut poll_parameters = PollParametersStorage::new();
+        poll_parameters.set_candidates(candidate_list);
+        let mut vote_part = VoteStorage::new();
+        vote_part.set_blank_ballot(left_ballot.clone());
+        let mut ballot_pair = CandidateBallot::new();
+        ballot_pair.set_candidate("Kitten".to_string());
+        ballot_pair.set_ballot(right_ballot.clone());
+        vote_part.mut_voted_ballot().push(ballot_pair);
+        let mut vote_sum = VoteStorage::new();
+        for _ in 0..2 {
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                &vote_part,
+                &mut vote_sum,
+            )
+            .unwrap();
+        }
+        assert_eq!(vote_sum.get_blank_ballot(), &Ballot::from(left + left));
+        assert_eq!(
+            vote_sum.get_voted_ballot()[0].get_ballot(),
+            &Ballot::from(right + right)
+        );
+    }
+}
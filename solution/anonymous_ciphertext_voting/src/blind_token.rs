@@ -0,0 +1,332 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of blind-signed authorization tokens, letting a poll authority
+//! authorize voters without being able to link tokens back to them.
+//!
+//! Tokens are blind Schnorr signatures. For an authority secret key x with
+//! public key Y = x * G1, the authority commits to a nonce R = k * G1. The
+//! voter blinds it to R' = R + alpha * G1 + beta * Y and sends the challenge
+//! c = H(R', Y, m) + beta of the message m. The authority answers
+//! s = k + c * x, which the voter unblinds to s' = s + alpha. The token
+//! (R', s') verifies as s' * G1 = R' + H(R', Y, m) * Y. As alpha and beta are
+//! uniformly random, a token matches every issuance seen by the authority
+//! equally well.
+//!
+//! A nonce must answer a single challenge, and an authority should not keep
+//! many issuances open at once, as concurrent blind Schnorr sessions allow
+//! forging extra tokens.
+//!
+//! A vote request token is issued for a fresh nonce chosen by the voter with
+//! [`make_token_nonce`], not for anything the coordinator has seen, such as
+//! the certified blank ballot. The authority is trusted to issue tokens only
+//! to eligible voters, but learns nothing linking a token to its issuance as
+//! long as the voter discards the blinding. The token is not bound to the
+//! ballots, so it is a bearer credential: verifiers must accept each nonce
+//! once, and voters should submit requests over a channel that does not
+//! reveal them before they are accepted.
+
+use crate::config::BLIND_TOKEN_CHALLENGE_DOMAIN;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_crypto_zkp_utils::{
+    bytes_to_point, bytes_to_scalar, get_random_scalar, point_to_bytes,
+    scalar_to_bytes, BASEPOINT_G1,
+};
+use wedpr_l_utils::{error::WedprError, traits::Hash};
+use wedpr_s_protos::config::HASH;
+
+const POINT_LENGTH: usize = 32;
+const SCALAR_LENGTH: usize = 32;
+const TOKEN_NONCE_LENGTH: usize = 32;
+
+/// Voter secrets blinding one token issuance.
+#[derive(Clone)]
+pub struct TokenBlinding {
+    alpha: Scalar,
+    beta: Scalar,
+    public_key: RistrettoPoint,
+    blinded_commitment: RistrettoPoint,
+}
+
+/// Makes an authority key pair, returned as (public key, secret key).
+pub fn make_token_keypair() -> (Vec<u8>, Vec<u8>) {
+    let secret_key = get_random_scalar();
+    let public_key = secret_key * *BASEPOINT_G1;
+    (point_to_bytes(&public_key), scalar_to_bytes(&secret_key))
+}
+
+/// Makes the authority nonce of one issuance, returned as (commitment,
+/// nonce). The commitment is sent to the voter and the nonce is kept for
+/// [`sign_blinded`].
+pub fn make_issuance_commitment() -> (Vec<u8>, Vec<u8>) {
+    let nonce = get_random_scalar();
+    let commitment = nonce * *BASEPOINT_G1;
+    (point_to_bytes(&commitment), scalar_to_bytes(&nonce))
+}
+
+/// Makes fresh voter blinding for an issuance commitment of the authority.
+pub fn make_token_blinding(
+    public_key: &[u8],
+    commitment: &[u8],
+) -> Result<TokenBlinding, WedprError> {
+    let public_key = bytes_to_point(public_key)?;
+    let commitment = bytes_to_point(commitment)?;
+    let alpha = get_random_scalar();
+    let beta = get_random_scalar();
+    Ok(TokenBlinding {
+        alpha,
+        beta,
+        public_key,
+        blinded_commitment: commitment
+            + alpha * *BASEPOINT_G1
+            + beta * public_key,
+    })
+}
+
+/// Blinds the challenge of a message, to be signed by the authority.
+pub fn blind(message: &[u8], blinding: &TokenBlinding) -> Vec<u8> {
+    let challenge = token_challenge(
+        &blinding.blinded_commitment,
+        &blinding.public_key,
+        message,
+    );
+    scalar_to_bytes(&(challenge + blinding.beta))
+}
+
+/// Signs a blinded challenge with the nonce of its issuance. The nonce must
+/// not be used again.
+pub fn sign_blinded(
+    secret_key: &[u8],
+    nonce: &[u8],
+    blinded: &[u8],
+) -> Result<Vec<u8>, WedprError> {
+    let secret_key = bytes_to_scalar(secret_key)?;
+    let nonce = bytes_to_scalar(nonce)?;
+    let blinded_challenge = bytes_to_scalar(blinded)?;
+    Ok(scalar_to_bytes(&(nonce + blinded_challenge * secret_key)))
+}
+
+/// Unblinds the authority response into a token for the blinded message.
+/// Checking the token with [`verify_token`] detects a dishonest response.
+pub fn unblind(
+    signed: &[u8],
+    blinding: &TokenBlinding,
+) -> Result<Vec<u8>, WedprError> {
+    let response = bytes_to_scalar(signed)?;
+    let mut token = point_to_bytes(&blinding.blinded_commitment);
+    token.append(&mut scalar_to_bytes(&(response + blinding.alpha)));
+    Ok(token)
+}
+
+/// Verifies whether the token was issued for the message by the authority
+/// of the public key.
+pub fn verify_token(
+    public_key: &[u8],
+    message: &[u8],
+    token: &[u8],
+) -> Result<bool, WedprError> {
+    if token.len() != POINT_LENGTH + SCALAR_LENGTH {
+        return Err(WedprError::FormatError);
+    }
+    let public_key = bytes_to_point(public_key)?;
+    let commitment = bytes_to_point(&token[..POINT_LENGTH])?;
+    let response = bytes_to_scalar(&token[POINT_LENGTH..])?;
+    let challenge = token_challenge(&commitment, &public_key, message);
+    Ok(response * *BASEPOINT_G1 == commitment + challenge * public_key)
+}
+
+/// Makes the fresh nonce a vote request token is issued for, to be blinded
+/// with [`blind`] and set as the authorization nonce of the request.
+pub fn make_token_nonce() -> Vec<u8> {
+    scalar_to_bytes(&get_random_scalar())
+}
+
+/// Checks whether a nonce has the length of the nonces made by
+/// [`make_token_nonce`].
+pub fn is_valid_token_nonce(nonce: &[u8]) -> bool {
+    nonce.len() == TOKEN_NONCE_LENGTH
+}
+
+pub(crate) fn token_challenge(
+    commitment: &RistrettoPoint,
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> Scalar {
+    let mut hash_vec = Vec::new();
+    hash_vec.extend_from_slice(BLIND_TOKEN_CHALLENGE_DOMAIN.as_bytes());
+    hash_vec.append(&mut point_to_bytes(commitment));
+    hash_vec.append(&mut point_to_bytes(public_key));
+    hash_vec.extend_from_slice(message);
+    let mut challenge_bytes = [0u8; 32];
+    challenge_bytes.copy_from_slice(&HASH.hash(&hash_vec)[..32]);
+    Scalar::from_bytes_mod_order(challenge_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier,
+    };
+    use std::collections::HashSet;
+    use wedpr_l_crypto_zkp_utils::{bytes_to_point, BASEPOINT_G1};
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_blind_token() {
+        let (authority_public_key, authority_secret_key) = make_token_keypair();
+        let public_key_point = bytes_to_point(&authority_public_key).unwrap();
+
+        // Issue tokens for two messages, keeping what the authority sees.
+        let messages = [b"voter one".to_vec(), b"voter two".to_vec()];
+        let mut issuances = Vec::new();
+        let mut tokens = Vec::new();
+        for message in &messages {
+            let (commitment, nonce) = make_issuance_commitment();
+            let blinding =
+                make_token_blinding(&authority_public_key, &commitment)
+                    .unwrap();
+            let blinded = blind(message, &blinding);
+            let signed =
+                sign_blinded(&authority_secret_key, &nonce, &blinded).unwrap();
+            let token = unblind(&signed, &blinding).unwrap();
+            assert!(
+                verify_token(&authority_public_key, message, &token).unwrap()
+            );
+            issuances.push((commitment, blinded, signed));
+            tokens.push(token);
+        }
+
+        // Tokens do not verify for other messages or authorities.
+        assert!(
+            !verify_token(&authority_public_key, &messages[1], &tokens[0])
+                .unwrap()
+        );
+        let (other_public_key, _) = make_token_keypair();
+        assert!(
+            !verify_token(&other_public_key, &messages[0], &tokens[0]).unwrap()
+        );
+        assert!(matches!(
+            verify_token(&authority_public_key, &messages[0], &tokens[0][..32]),
+            Err(WedprError::FormatError)
+        ));
+
+        // Unlinkability: every token is explained by every issuance with some
+        // blinding, so the authority cannot tell which issuance it came from.
+        for (commitment, blinded, signed) in &issuances {
+            let commitment = bytes_to_point(commitment).unwrap();
+            let blinded = bytes_to_scalar(blinded).unwrap();
+            let signed = bytes_to_scalar(signed).unwrap();
+            for (message, token) in messages.iter().zip(&tokens) {
+                let token_commitment = bytes_to_point(&token[..32]).unwrap();
+                let token_response = bytes_to_scalar(&token[32..]).unwrap();
+                let challenge = token_challenge(
+                    &token_commitment,
+                    &public_key_point,
+                    message,
+                );
+                let alpha = token_response - signed;
+                let beta = blinded - challenge;
+                assert_eq!(
+                    commitment
+                        + alpha * *BASEPOINT_G1
+                        + beta * public_key_point,
+                    token_commitment
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_token_nonce() {
+        let nonce = make_token_nonce();
+        assert!(is_valid_token_nonce(&nonce));
+        assert_ne!(nonce, make_token_nonce());
+        assert!(!is_valid_token_nonce(&nonce[1..]));
+        assert!(!is_valid_token_nonce(&[]));
+    }
+
+    #[test]
+    fn test_verify_vote_request_with_token() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (authority_public_key, authority_secret_key) = make_token_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2, 3,
+            ]);
+
+        // Requests without a token are rejected.
+        let mut spent_nonces = HashSet::new();
+        assert!(!verifier::verify_vote_request_with_token(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &authority_public_key,
+            &mut spent_nonces
+        )
+        .unwrap());
+
+        // The token is issued for a fresh nonce of the voter, which the
+        // authority never sees unblinded.
+        let token_nonce = make_token_nonce();
+        let (commitment, nonce) = make_issuance_commitment();
+        let blinding =
+            make_token_blinding(&authority_public_key, &commitment).unwrap();
+        let blinded = blind(&token_nonce, &blinding);
+        let signed =
+            sign_blinded(&authority_secret_key, &nonce, &blinded).unwrap();
+        vote_request.set_authorization_nonce(token_nonce.clone());
+        vote_request
+            .set_authorization_token(unblind(&signed, &blinding).unwrap());
+
+        // A token issued by another authority is rejected.
+        let (other_public_key, _) = make_token_keypair();
+        assert!(!verifier::verify_vote_request_with_token(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &other_public_key,
+            &mut spent_nonces
+        )
+        .unwrap());
+        // So is a token moved to another nonce.
+        let mut moved_request = vote_request.clone();
+        moved_request.set_authorization_nonce(make_token_nonce());
+        assert!(!verifier::verify_vote_request_with_token(
+            &poll_parameters,
+            &moved_request,
+            &public_key,
+            &authority_public_key,
+            &mut spent_nonces
+        )
+        .unwrap());
+
+        assert!(verifier::verify_vote_request_with_token(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &authority_public_key,
+            &mut spent_nonces
+        )
+        .unwrap());
+        assert!(spent_nonces.contains(&token_nonce));
+        // Each token authorizes a single request.
+        assert!(!verifier::verify_vote_request_with_token(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &authority_public_key,
+            &mut spent_nonces
+        )
+        .unwrap());
+    }
+}
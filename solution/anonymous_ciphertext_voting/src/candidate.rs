@@ -0,0 +1,108 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of candidate identifiers.
+
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalized candidate identifier. Protobuf candidate strings are trimmed
+/// and converted to Unicode NFC at the boundary, so that names differing only
+/// in surrounding whitespace or encoding identify the same candidate.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CandidateId(String);
+
+impl CandidateId {
+    pub fn new(candidate: &str) -> Self {
+        CandidateId(candidate.trim().nfc().collect())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for CandidateId {
+    fn from(candidate: &str) -> Self {
+        CandidateId::new(candidate)
+    }
+}
+
+impl fmt::Display for CandidateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::{config::SIGNATURE, generated::acv::VoteStorage};
+
+    #[test]
+    fn test_candidate_id_normalization() {
+        use crate::candidate::CandidateId;
+
+        assert_eq!(CandidateId::new(" Kitten\t"), CandidateId::new("Kitten"));
+        // Decomposed and precomposed forms of the same name are equal.
+        assert_eq!(
+            CandidateId::new("Rene\u{301}"),
+            CandidateId::new("Ren\u{e9}")
+        );
+        assert_ne!(CandidateId::new("Kitten"), CandidateId::new("kitten"));
+
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        // Candidates equal after normalization cannot share a poll.
+        let ambiguous_list = make_candidate_list(&["Kitten", "Kitten "]);
+        assert!(coordinator::make_poll_parameters(
+            &ambiguous_list,
+            &counter_parameters
+        )
+        .is_err());
+
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 20, &[
+                1, 2, 3,
+            ]);
+
+        // A request naming a candidate with trailing whitespace is treated
+        // as naming the same candidate, in verification and aggregation.
+        let mut padded_request = vote_request.clone();
+        padded_request.mut_vote().mut_voted_ballot()[0]
+            .set_candidate("Kitten ".to_string());
+        padded_request.mut_ballot_proof()[0].set_key("Kitten ".to_string());
+        assert!(verifier::verify_vote_request(
+            &poll_parameters,
+            &padded_request,
+            &public_key
+        )
+        .unwrap());
+        let mut vote_sum = VoteStorage::new();
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut vote_sum,
+        )
+        .unwrap();
+        let mut padded_vote_sum = VoteStorage::new();
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            padded_request.get_vote(),
+            &mut padded_vote_sum,
+        )
+        .unwrap();
+        assert_eq!(padded_vote_sum, vote_sum);
+    }
+}
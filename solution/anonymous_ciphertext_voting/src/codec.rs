@@ -0,0 +1,230 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of string encodings for ACV messages, for transports such as JSON
+//! that cannot carry raw protobuf bytes.
+
+use crate::config::{BASE64, HEX};
+use protobuf::Message;
+use wedpr_l_utils::{error::WedprError, traits::Coder};
+use wedpr_s_protos::{
+    bytes_to_proto,
+    generated::acv::{
+        DecryptedResultPartStorage, PollTranscript, VoteRequest, VoteStorage,
+    },
+    proto_to_bytes,
+};
+
+/// Encodes a message with the given coder, such as [`HEX`] or [`BASE64`].
+pub fn proto_to_string<T: Message, C: Coder>(
+    coder: &C,
+    proto: &T,
+) -> Result<String, WedprError> {
+    Ok(coder.encode(&proto_to_bytes(proto)?))
+}
+
+/// Decodes a message encoded by [`proto_to_string`] with the same coder.
+/// Invalid input is reported as an error rather than a panic.
+pub fn string_to_proto<T: Message, C: Coder>(
+    coder: &C,
+    input: &str,
+) -> Result<T, WedprError> {
+    bytes_to_proto(&coder.decode(input)?)
+}
+
+pub fn vote_request_to_hex(
+    vote_request: &VoteRequest,
+) -> Result<String, WedprError> {
+    proto_to_string(&*HEX, vote_request)
+}
+
+pub fn vote_request_from_hex(input: &str) -> Result<VoteRequest, WedprError> {
+    string_to_proto(&*HEX, input)
+}
+
+pub fn vote_request_to_base64(
+    vote_request: &VoteRequest,
+) -> Result<String, WedprError> {
+    proto_to_string(&*BASE64, vote_request)
+}
+
+pub fn vote_request_from_base64(
+    input: &str,
+) -> Result<VoteRequest, WedprError> {
+    string_to_proto(&*BASE64, input)
+}
+
+pub fn vote_storage_to_hex(
+    vote_storage: &VoteStorage,
+) -> Result<String, WedprError> {
+    proto_to_string(&*HEX, vote_storage)
+}
+
+pub fn vote_storage_from_hex(input: &str) -> Result<VoteStorage, WedprError> {
+    string_to_proto(&*HEX, input)
+}
+
+pub fn vote_storage_to_base64(
+    vote_storage: &VoteStorage,
+) -> Result<String, WedprError> {
+    proto_to_string(&*BASE64, vote_storage)
+}
+
+pub fn vote_storage_from_base64(
+    input: &str,
+) -> Result<VoteStorage, WedprError> {
+    string_to_proto(&*BASE64, input)
+}
+
+pub fn decrypted_result_part_storage_to_hex(
+    decrypted_result: &DecryptedResultPartStorage,
+) -> Result<String, WedprError> {
+    proto_to_string(&*HEX, decrypted_result)
+}
+
+pub fn decrypted_result_part_storage_from_hex(
+    input: &str,
+) -> Result<DecryptedResultPartStorage, WedprError> {
+    string_to_proto(&*HEX, input)
+}
+
+pub fn decrypted_result_part_storage_to_base64(
+    decrypted_result: &DecryptedResultPartStorage,
+) -> Result<String, WedprError> {
+    proto_to_string(&*BASE64, decrypted_result)
+}
+
+pub fn decrypted_result_part_storage_from_base64(
+    input: &str,
+) -> Result<DecryptedResultPartStorage, WedprError> {
+    string_to_proto(&*BASE64, input)
+}
+
+pub fn poll_transcript_to_hex(
+    transcript: &PollTranscript,
+) -> Result<String, WedprError> {
+    proto_to_string(&*HEX, transcript)
+}
+
+pub fn poll_transcript_from_hex(
+    input: &str,
+) -> Result<PollTranscript, WedprError> {
+    string_to_proto(&*HEX, input)
+}
+
+pub fn poll_transcript_to_base64(
+    transcript: &PollTranscript,
+) -> Result<String, WedprError> {
+    proto_to_string(&*BASE64, transcript)
+}
+
+pub fn poll_transcript_from_base64(
+    input: &str,
+) -> Result<PollTranscript, WedprError> {
+    string_to_proto(&*BASE64, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        counter,
+        test_fixtures::{run_poll, CompletedPoll},
+    };
+    use wedpr_s_protos::generated::acv::CounterSecret;
+
+    #[test]
+    fn test_string_round_trip() {
+        let CompletedPoll {
+            vote_request_list,
+            encrypted_vote_sum,
+            aggregated_decrypted_result,
+            ..
+        } = run_poll();
+        let vote_request = &vote_request_list[0];
+        assert_eq!(
+            &vote_request_from_hex(&vote_request_to_hex(vote_request).unwrap())
+                .unwrap(),
+            vote_request
+        );
+        assert_eq!(
+            &vote_request_from_base64(
+                &vote_request_to_base64(vote_request).unwrap()
+            )
+            .unwrap(),
+            vote_request
+        );
+        assert_eq!(
+            vote_storage_from_hex(
+                &vote_storage_to_hex(&encrypted_vote_sum).unwrap()
+            )
+            .unwrap(),
+            encrypted_vote_sum
+        );
+        assert_eq!(
+            vote_storage_from_base64(
+                &vote_storage_to_base64(&encrypted_vote_sum).unwrap()
+            )
+            .unwrap(),
+            encrypted_vote_sum
+        );
+        assert_eq!(
+            decrypted_result_part_storage_from_hex(
+                &decrypted_result_part_storage_to_hex(
+                    &aggregated_decrypted_result
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            aggregated_decrypted_result
+        );
+        assert_eq!(
+            decrypted_result_part_storage_from_base64(
+                &decrypted_result_part_storage_to_base64(
+                    &aggregated_decrypted_result
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            aggregated_decrypted_result
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(vote_request_from_hex("not hex").is_err());
+        assert!(vote_request_from_base64("not base64!").is_err());
+        // Valid encodings of bytes that are not a protobuf message.
+        assert!(vote_storage_from_hex("ffffffff").is_err());
+    }
+
+    #[test]
+    fn test_coder_round_trip() {
+        use rand::{rngs::OsRng, Rng, RngCore};
+
+        fn check_round_trip<C: Coder>(coder: &C) {
+            for _ in 0..100 {
+                let mut input = vec![0u8; OsRng.gen_range(0, 256)];
+                OsRng.fill_bytes(&mut input);
+                assert_eq!(coder.decode(&coder.encode(&input)).unwrap(), input);
+            }
+        }
+        check_round_trip(&*HEX);
+        check_round_trip(&*BASE64);
+
+        // Invalid input is an error rather than a panic.
+        assert!(HEX.decode("not hex").is_err());
+        assert!(BASE64.decode("not base64!").is_err());
+
+        // Message encodings can be parameterized over the coder.
+        let counter_secret = counter::make_counter_secret();
+        let encoded_secret =
+            proto_to_string(&*BASE64, &counter_secret).unwrap();
+        assert_eq!(
+            string_to_proto::<CounterSecret, _>(&*BASE64, &encoded_secret)
+                .unwrap(),
+            counter_secret
+        );
+        assert!(string_to_proto::<CounterSecret, _>(&*HEX, &encoded_secret)
+            .is_err());
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for polls whose candidate labels stay confidential until the
+//! result is published.
+//!
+//! The coordinator replaces each label by a hiding commitment, the hex hash
+//! of a random nonce and the normalized label, and makes the poll parameters
+//! from the committed identifiers. Ballots and proofs then refer to the
+//! committed identifiers only, so voting, counting and verification are
+//! unchanged. The openings are published with the result to map the tallies
+//! back to the labels.
+
+use crate::{
+    candidate::CandidateId,
+    config::{CANDIDATE_COMMITMENT_DOMAIN, HEX, POLL_RESULT_KEY_TOTAL_BALLOTS},
+    utils::is_candidate_set,
+};
+use rand::{rngs::OsRng, RngCore};
+use std::collections::HashMap;
+use wedpr_l_utils::{
+    error::WedprError,
+    traits::{Coder, Hash},
+};
+use wedpr_s_protos::{
+    config::HASH,
+    generated::acv::{CandidateList, PollParametersStorage, VoteResultStorage},
+};
+
+/// Size of the random nonce hiding a candidate label.
+const CANDIDATE_NONCE_SIZE: usize = 32;
+
+/// Opening of a committed candidate identifier, kept by the coordinator until
+/// the result is published.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CandidateOpening {
+    pub label: String,
+    pub nonce: Vec<u8>,
+}
+
+impl CandidateOpening {
+    /// Returns the committed identifier opened by this opening.
+    pub fn committed_id(&self) -> String {
+        commit_candidate(&self.label, &self.nonce)
+    }
+}
+
+/// Commits to a candidate label with the given nonce.
+pub fn commit_candidate(label: &str, nonce: &[u8]) -> String {
+    let mut hash_vec = Vec::new();
+    hash_vec.extend_from_slice(CANDIDATE_COMMITMENT_DOMAIN.as_bytes());
+    hash_vec.extend_from_slice(&(nonce.len() as u64).to_be_bytes());
+    hash_vec.extend_from_slice(nonce);
+    hash_vec.extend_from_slice(CandidateId::new(label).as_str().as_bytes());
+    HEX.encode(&HASH.hash(&hash_vec))
+}
+
+/// Commits to every label of a candidate list with fresh nonces. Returns the
+/// committed identifiers, to make the poll parameters from, and the openings
+/// in the same order.
+pub fn commit_candidate_list(
+    candidate_list: &CandidateList,
+) -> (CandidateList, Vec<CandidateOpening>) {
+    let mut committed_list = CandidateList::new();
+    let mut openings = Vec::with_capacity(candidate_list.get_candidate().len());
+    for label in candidate_list.get_candidate() {
+        let mut nonce = vec![0u8; CANDIDATE_NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce);
+        let opening = CandidateOpening {
+            label: label.to_string(),
+            nonce,
+        };
+        committed_list.mut_candidate().push(opening.committed_id());
+        openings.push(opening);
+    }
+    (committed_list, openings)
+}
+
+/// Replaces the committed identifiers in a vote result by their labels.
+/// Every poll candidate must be opened exactly once, and the result must not
+/// contain keys other than the total ballots and the poll candidates.
+pub fn reveal_vote_result(
+    poll_parameters: &PollParametersStorage,
+    vote_result: &VoteResultStorage,
+    openings: &[CandidateOpening],
+) -> Result<VoteResultStorage, WedprError> {
+    let committed_ids: Vec<String> = openings
+        .iter()
+        .map(|opening| opening.committed_id())
+        .collect();
+    let candidates = poll_parameters.get_candidates().get_candidate();
+    if !is_candidate_set(
+        candidates,
+        committed_ids
+            .iter()
+            .map(|committed_id| committed_id.as_str()),
+    ) {
+        wedpr_println!("candidate openings do not match the poll candidates");
+        return Err(WedprError::VerificationError);
+    }
+    let label_index: HashMap<CandidateId, &str> = committed_ids
+        .iter()
+        .zip(openings)
+        .map(|(committed_id, opening)| {
+            (CandidateId::new(committed_id), opening.label.as_str())
+        })
+        .collect();
+
+    let mut revealed_result = vote_result.clone();
+    for pair in revealed_result.mut_result().iter_mut() {
+        if pair.get_key() == POLL_RESULT_KEY_TOTAL_BALLOTS {
+            continue;
+        }
+        let label = match label_index.get(&CandidateId::new(pair.get_key())) {
+            Some(label) => label.to_string(),
+            None => {
+                wedpr_println!(
+                    "unexpected vote result key: {}",
+                    pair.get_key()
+                );
+                return Err(WedprError::ArgumentError);
+            },
+        };
+        pair.set_key(label);
+    }
+    Ok(revealed_result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, counter, result,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::{config::SIGNATURE, generated::acv::VoteStorage};
+
+    #[test]
+    fn test_committed_candidates() {
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let (committed_list, openings) = commit_candidate_list(&candidate_list);
+        // The parameters only carry committed identifiers.
+        let poll_parameters = coordinator::make_poll_parameters(
+            &committed_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        for label in candidate_list.get_candidate() {
+            assert!(!poll_parameters
+                .get_candidates()
+                .get_candidate()
+                .contains(label));
+        }
+
+        // Ballots verify against the committed identifiers.
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &committed_list, 10, &[
+                1, 2, 3,
+            ]);
+        assert!(verifier::verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .unwrap());
+
+        let mut encrypted_vote_sum = VoteStorage::new();
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut encrypted_vote_sum,
+        )
+        .unwrap();
+        let partially_decrypted_result = counter::count(
+            "1001",
+            &counter_secret_list[0],
+            &encrypted_vote_sum,
+        )
+        .unwrap();
+        let aggregated_decrypted_result =
+            coordinator::aggregate_decrypted_parts(&poll_parameters, &[
+                partially_decrypted_result,
+            ])
+            .unwrap();
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            10,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result
+        )
+        .unwrap());
+
+        // The openings map the tallies back to the labels.
+        let revealed_result =
+            reveal_vote_result(&poll_parameters, &vote_result, &openings)
+                .unwrap();
+        let decoded = result::decode_vote_result(&revealed_result).unwrap();
+        assert_eq!(decoded.total, 10);
+        assert_eq!(decoded.per_candidate["Kitten"], 1);
+        assert_eq!(decoded.per_candidate["Doge"], 2);
+        assert_eq!(decoded.per_candidate["Bunny"], 3);
+
+        // A wrong or missing opening is rejected.
+        let mut wrong_openings = openings.clone();
+        wrong_openings[0].label = "Tiger".to_string();
+        assert!(matches!(
+            reveal_vote_result(&poll_parameters, &vote_result, &wrong_openings),
+            Err(WedprError::VerificationError)
+        ));
+        assert!(matches!(
+            reveal_vote_result(&poll_parameters, &vote_result, &openings[1..]),
+            Err(WedprError::VerificationError)
+        ));
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Verification steps shared by bounded polls and anonymous yes/no polls, so
+//! that both solutions check signatures, candidates and ballot formats the
+//! same way.
+
+use crate::{
+    candidate::CandidateId,
+    config::RISTRETTO_POINT_SIZE_IN_BYTES,
+    generators::GeneratorSet,
+    parameters::{ParsedPollParameters, VerifyLimits},
+    suite::VoteCryptoSuite,
+    trace::{trace_candidate_stage, trace_stage},
+    utils::{
+        find_duplicate_candidate, index_candidates, is_indexed_candidate_set,
+        range_proof_batch_size,
+    },
+    verifier::VoteVerifyFailure,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand::{seq::SliceRandom, RngCore};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use wedpr_l_crypto_zkp_discrete_logarithm_proof::verify_format_proof;
+use wedpr_l_crypto_zkp_utils::{bytes_to_point, Deserialize};
+use wedpr_l_utils::{
+    error::WedprError,
+    traits::{Hash, Signature},
+};
+use wedpr_s_protos::{
+    generated::acv::{Ballot, StringToBallotProofPair, VoteRequest},
+    verify_ballot_signature_with_params,
+};
+
+/// Maps the detailed outcome of a vote request check to the boolean result
+/// of the verifier entrypoints. Requests whose candidates or proofs do not
+/// fit the poll at all are errors rather than invalid votes.
+pub(crate) fn vote_verify_result(
+    result: Result<(), VoteVerifyFailure>,
+) -> Result<bool, WedprError> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(VoteVerifyFailure::Malformed(error)) => Err(error),
+        Err(VoteVerifyFailure::CandidateMismatch)
+        | Err(VoteVerifyFailure::PollMismatch)
+        | Err(VoteVerifyFailure::InputTooLarge) => {
+            Err(WedprError::ArgumentError)
+        },
+        Err(VoteVerifyFailure::DuplicateCandidate(_))
+        | Err(VoteVerifyFailure::RangeProofBatchSize(_, _)) => {
+            Err(WedprError::FormatError)
+        },
+        Err(_) => Err(WedprError::VerificationError),
+    }
+}
+
+/// Checks that a vote request carrying a poll id was cast in the verified
+/// poll. Requests without a poll id are bound to the poll by the signature on
+/// the blank ballot alone.
+pub(crate) fn verify_poll_id(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+) -> Result<(), VoteVerifyFailure> {
+    let poll_id = vote_request.get_poll_id();
+    if !poll_id.is_empty() && poll_id != parsed_parameters.poll_id.as_slice() {
+        wedpr_println!("vote request was cast in another poll!");
+        return Err(VoteVerifyFailure::PollMismatch);
+    }
+    Ok(())
+}
+
+/// Checks the coordinator signature on the blank ballot of a vote request.
+pub(crate) fn verify_vote_signature<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<(), VoteVerifyFailure> {
+    let vote = vote_request.get_vote();
+    let verify_result = trace_stage("signature", || {
+        verify_ballot_signature_with_params(
+            &suite
+                .signature_params()
+                .poll_id(&parsed_parameters.poll_id)
+                .poll_epoch(parsed_parameters.poll_epoch),
+            public_key,
+            vote.get_blank_ballot(),
+            &vote.get_signature().to_vec(),
+        )
+    })?;
+    if !verify_result {
+        return Err(VoteVerifyFailure::Signature);
+    }
+    Ok(())
+}
+
+// Decompresses both ciphertexts of a ballot, rejecting malformed encodings
+// like [`crate::verifier::validate_ballot`].
+pub(crate) fn ballot_points(
+    ballot: &Ballot,
+) -> Result<(RistrettoPoint, RistrettoPoint), WedprError> {
+    let mut points = [RistrettoPoint::default(); 2];
+    for (point, ciphertext) in points
+        .iter_mut()
+        .zip(&[ballot.get_ciphertext1(), ballot.get_ciphertext2()])
+    {
+        if ciphertext.len() != RISTRETTO_POINT_SIZE_IN_BYTES {
+            wedpr_println!(
+                "malformed ballot: ciphertext of {} bytes",
+                ciphertext.len()
+            );
+            return Err(WedprError::FormatError);
+        }
+        *point = match bytes_to_point(ciphertext) {
+            Ok(v) => v,
+            Err(_) => {
+                wedpr_println!("malformed ballot: invalid point encoding");
+                return Err(WedprError::FormatError);
+            },
+        };
+    }
+    Ok((points[0], points[1]))
+}
+
+// Checks that the range proof was generated for as many values as the padded
+// commitments, so that a proof for another count is reported as such instead
+// of as an invalid proof.
+pub(crate) fn verify_range_proof_batch_size(
+    expected_len: usize,
+    range_proof: &[u8],
+) -> Result<(), VoteVerifyFailure> {
+    match range_proof_batch_size(range_proof) {
+        Some(batch_size) if batch_size == expected_len => Ok(()),
+        Some(batch_size) => {
+            wedpr_println!(
+                "range proof is for {} values instead of {}!",
+                batch_size,
+                expected_len
+            );
+            Err(VoteVerifyFailure::RangeProofBatchSize(
+                expected_len,
+                batch_size,
+            ))
+        },
+        None => {
+            wedpr_println!("range proof of {} bytes", range_proof.len());
+            Err(WedprError::FormatError.into())
+        },
+    }
+}
+
+// Checks the sizes of a vote request against the verify limits, before
+// anything is decompressed or proved.
+pub(crate) fn verify_input_limits(
+    limits: &VerifyLimits,
+    vote_request: &VoteRequest,
+) -> Result<(), VoteVerifyFailure> {
+    let ballot_proofs = vote_request.get_ballot_proof();
+    let voted_count = vote_request.get_vote().get_voted_ballot().len();
+    if voted_count > limits.max_candidates
+        || ballot_proofs.len() > limits.max_candidates
+    {
+        wedpr_println!(
+            "{} ballots and {} ballot proofs exceed the limit of {}!",
+            voted_count,
+            ballot_proofs.len(),
+            limits.max_candidates
+        );
+        return Err(VoteVerifyFailure::InputTooLarge);
+    }
+    let proof_bytes = vote_request.get_range_proof().len()
+        + vote_request.get_sum_balance_proof().len()
+        + vote_request.get_weight_proof().len()
+        + ballot_proofs
+            .iter()
+            .map(|pair| {
+                let ballot_proof = pair.get_value();
+                ballot_proof.get_format_proof().len()
+                    + ballot_proof.get_either_equality_proof().len()
+            })
+            .sum::<usize>();
+    if proof_bytes > limits.max_proof_bytes {
+        wedpr_println!(
+            "{} proof bytes exceed the limit of {}!",
+            proof_bytes,
+            limits.max_proof_bytes
+        );
+        return Err(VoteVerifyFailure::InputTooLarge);
+    }
+    Ok(())
+}
+
+pub(crate) fn verify_candidate_set(
+    candidates: &[String],
+    vote_request: &VoteRequest,
+) -> Result<(), VoteVerifyFailure> {
+    verify_indexed_candidate_set(&index_candidates(candidates), vote_request)
+}
+
+pub(crate) fn verify_indexed_candidate_set(
+    candidate_set: &HashSet<CandidateId>,
+    vote_request: &VoteRequest,
+) -> Result<(), VoteVerifyFailure> {
+    // Each candidate must appear once, so that the ballot passing its format
+    // proof is the ballot summed.
+    let duplicate_candidate = find_duplicate_candidate(
+        vote_request
+            .get_vote()
+            .get_voted_ballot()
+            .iter()
+            .map(|pair| pair.get_candidate()),
+    )
+    .or_else(|| {
+        find_duplicate_candidate(
+            vote_request
+                .get_ballot_proof()
+                .iter()
+                .map(|pair| pair.get_key()),
+        )
+    });
+    if let Some(candidate) = duplicate_candidate {
+        wedpr_println!("duplicate candidate {} in vote request!", candidate);
+        return Err(VoteVerifyFailure::DuplicateCandidate(
+            candidate.to_string(),
+        ));
+    }
+    let voted_candidates = vote_request
+        .get_vote()
+        .get_voted_ballot()
+        .iter()
+        .map(|pair| pair.get_candidate());
+    let proof_candidates = vote_request
+        .get_ballot_proof()
+        .iter()
+        .map(|pair| pair.get_key());
+    if !is_indexed_candidate_set(candidate_set, voted_candidates)
+        || !is_indexed_candidate_set(candidate_set, proof_candidates)
+    {
+        wedpr_println!("verify_candidate_set failed!");
+        return Err(VoteVerifyFailure::CandidateMismatch);
+    }
+    Ok(())
+}
+
+pub(crate) fn verify_candidate_format_proof(
+    ballot_index: &HashMap<CandidateId, (RistrettoPoint, RistrettoPoint)>,
+    candidate_ballot_proof: &StringToBallotProofPair,
+    poll_point: &RistrettoPoint,
+) -> Result<(), VoteVerifyFailure> {
+    let candidate = candidate_ballot_proof.get_key();
+    let ballot_proof = candidate_ballot_proof.get_value();
+    trace_candidate_stage("format_proof", candidate, || {
+        let (ciphertext1, ciphertext2) =
+            match ballot_index.get(&CandidateId::new(candidate)) {
+                Some(points) => *points,
+                None => return Err(WedprError::ArgumentError.into()),
+            };
+        if !verify_ballot_format(
+            &ciphertext1,
+            &ciphertext2,
+            ballot_proof.get_format_proof(),
+            &GeneratorSet::default(),
+            poll_point,
+        )? {
+            return Err(VoteVerifyFailure::FormatProof(candidate.to_string()));
+        }
+        Ok(())
+    })
+}
+
+// Checks that ciphertext1 = v * g1 + r * poll_point and ciphertext2 = r * g2
+// share the same randomness r.
+pub(crate) fn verify_ballot_format(
+    ciphertext1: &RistrettoPoint,
+    ciphertext2: &RistrettoPoint,
+    format_proof: &[u8],
+    generators: &GeneratorSet,
+    poll_point: &RistrettoPoint,
+) -> Result<bool, WedprError> {
+    let format_proof = Deserialize::deserialize(format_proof)?;
+    verify_format_proof(
+        ciphertext1,
+        ciphertext2,
+        &format_proof,
+        &generators.g1,
+        &generators.g2,
+        poll_point,
+    )
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn verify_candidate_format_proofs(
+    ballot_index: &HashMap<CandidateId, (RistrettoPoint, RistrettoPoint)>,
+    ballot_proofs: &[StringToBallotProofPair],
+    poll_point: &RistrettoPoint,
+) -> Result<(), VoteVerifyFailure> {
+    for candidate_ballot_proof in ballot_proofs {
+        verify_candidate_format_proof(
+            ballot_index,
+            candidate_ballot_proof,
+            poll_point,
+        )?;
+    }
+    Ok(())
+}
+
+// Verifies the format proofs of all candidates one by one in a random order,
+// so that where a failing verification stops does not tell which candidate
+// comes first in the request.
+pub(crate) fn verify_candidate_format_proofs_shuffled(
+    ballot_index: &HashMap<CandidateId, (RistrettoPoint, RistrettoPoint)>,
+    ballot_proofs: &[StringToBallotProofPair],
+    poll_point: &RistrettoPoint,
+    rng: &mut dyn RngCore,
+) -> Result<(), VoteVerifyFailure> {
+    let mut shuffled_proofs: Vec<&StringToBallotProofPair> =
+        ballot_proofs.iter().collect();
+    shuffled_proofs.shuffle(rng);
+    for candidate_ballot_proof in shuffled_proofs {
+        verify_candidate_format_proof(
+            ballot_index,
+            candidate_ballot_proof,
+            poll_point,
+        )?;
+    }
+    Ok(())
+}
+
+/// Verifies the format proofs of all candidates concurrently, stopping at the
+/// first failure. Only the independent per-candidate checks run in parallel;
+/// point accumulation stays on the calling thread.
+#[cfg(feature = "parallel")]
+pub(crate) fn verify_candidate_format_proofs(
+    ballot_index: &HashMap<CandidateId, (RistrettoPoint, RistrettoPoint)>,
+    ballot_proofs: &[StringToBallotProofPair],
+    poll_point: &RistrettoPoint,
+) -> Result<(), VoteVerifyFailure> {
+    ballot_proofs
+        .par_iter()
+        .try_for_each(|candidate_ballot_proof| {
+            verify_candidate_format_proof(
+                ballot_index,
+                candidate_ballot_proof,
+                poll_point,
+            )
+        })
+}
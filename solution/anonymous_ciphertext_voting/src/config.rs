@@ -1,3 +1,83 @@
 // Copyright 2020 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+use crate::{
+    hash::{WedprRipemd160, WedprSha256},
+    signature::WedprEd25519,
+};
+use curve25519_dalek::{ristretto::RistrettoPoint, traits::Identity};
+use wedpr_l_common_coder_base64::WedprBase64;
+use wedpr_l_common_coder_hex::WedprHex;
+use wedpr_l_crypto_ecies_secp256k1::WedprSecp256k1Ecies;
+use wedpr_l_crypto_hash_sm3::WedprSm3;
+use wedpr_l_crypto_signature_sm2::WedprSm2p256v1;
+use wedpr_l_crypto_zkp_utils::point_to_bytes;
+use wedpr_s_protos::generated::acv::Ballot;
+
 /// Poll result key for total ballots.
 pub const POLL_RESULT_KEY_TOTAL_BALLOTS: &str = "Wedpr_voting_total_ballots";
+/// Domain separator prefixed to the input of vote nullifiers.
+pub const VOTE_NULLIFIER_DOMAIN: &str = "Wedpr_voting_nullifier";
+/// Domain separator prefixed to the input of candidate commitments.
+pub const CANDIDATE_COMMITMENT_DOMAIN: &str =
+    "Wedpr_voting_candidate_commitment";
+/// Domain separator for hashing VRF inputs to points.
+pub const VRF_HASH_TO_POINT_DOMAIN: &str = "Wedpr_voting_vrf_hash_to_point";
+/// Domain separator for deriving VRF outputs.
+pub const VRF_OUTPUT_DOMAIN: &str = "Wedpr_voting_vrf_output";
+/// Domain separator for hashing counter reshares to points.
+pub const COUNTER_RESHARE_DOMAIN: &str = "Wedpr_voting_counter_reshare";
+/// Domain separator for blind token challenges.
+pub const BLIND_TOKEN_CHALLENGE_DOMAIN: &str =
+    "Wedpr_voting_blind_token_challenge";
+/// Domain separator for deriving generator sets.
+pub const GENERATOR_SET_DOMAIN: &str = "Wedpr_voting_generator_set";
+/// Size of a compressed Ristretto point in a ballot ciphertext.
+pub const RISTRETTO_POINT_SIZE_IN_BYTES: usize = 32;
+/// Version byte leading compact vote sum snapshots.
+pub const VOTE_SUM_SNAPSHOT_VERSION: u8 = 1;
+/// Default cap on the ballots or ballot proofs of a vote request.
+pub const DEFAULT_MAX_VOTE_CANDIDATES: usize = 4096;
+/// Default cap on the total proof bytes of a vote request.
+pub const DEFAULT_MAX_VOTE_PROOF_BYTES: usize = 4 << 20;
+/// Cap on the baby steps of a tally table, a few MB of entries. Bounds above
+/// its square, 2^32, still recover, with more giant steps per tally.
+pub const MAX_TALLY_BABY_STEPS: u64 = 1 << 16;
+// Layout of the proofs made by wedpr_l_crypto_zkp_range_proof, which does
+// not export it: each value is proved over 32 bits, and an aggregated proof
+// holds 9 group elements or scalars plus two points per inner product round.
+// The utils tests pin it against real proofs.
+pub(crate) const RANGE_PROOF_BITS: usize = 32;
+pub(crate) const RANGE_PROOF_FIXED_ELEMENTS: usize = 9;
+
+lazy_static! {
+    /// Group identity. Range proof commitments are padded with it, which
+    /// commits to the value 0 with blinding 0 and so cannot affect the
+    /// soundness of the proof.
+    pub static ref IDENTITY_POINT: RistrettoPoint = RistrettoPoint::identity();
+    /// Ballot whose ciphertexts are both the group identity, i.e. 0
+    /// encrypted with randomness 0, used to start vote sums.
+    pub static ref ZERO_BALLOT: Ballot = {
+        let mut ballot = Ballot::new();
+        ballot.set_ciphertext1(point_to_bytes(&IDENTITY_POINT));
+        ballot.set_ciphertext2(point_to_bytes(&IDENTITY_POINT));
+        ballot
+    };
+    /// Shared ed25519 signature reference, for ballots signed with keys from
+    /// existing identity systems.
+    pub static ref SIGNATURE_ED25519: WedprEd25519 = WedprEd25519::default();
+    /// Shared SM2 signature reference, for deployments requiring SM
+    /// algorithms regardless of the crate features.
+    pub static ref SIGNATURE_SM2: WedprSm2p256v1 = WedprSm2p256v1::default();
+    /// Shared SM3 hash reference, paired with SIGNATURE_SM2.
+    pub static ref HASH_SM3: WedprSm3 = WedprSm3::default();
+    /// Shared SHA-256 hash reference, for signing devices expecting
+    /// SHA-256 message hashes.
+    pub static ref HASH_SHA256: WedprSha256 = WedprSha256::default();
+    /// Shared RIPEMD-160 hash reference, for address derivation.
+    pub static ref HASH_RIPEMD160: WedprRipemd160 = WedprRipemd160::default();
+    /// Shared secp256k1 ECIES reference, for distributing counter secrets.
+    pub static ref ECIES: WedprSecp256k1Ecies = WedprSecp256k1Ecies::default();
+    /// Shared hex coder reference for string encodings.
+    pub static ref HEX: WedprHex = WedprHex::default();
+    /// Shared base64 coder reference for string encodings.
+    pub static ref BASE64: WedprBase64 = WedprBase64::default();
+}
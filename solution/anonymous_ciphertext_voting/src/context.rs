@@ -0,0 +1,155 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of verification contexts, preparing the poll once for verifying a
+//! stream of vote requests.
+//!
+//! A context holds the parsed poll parameters and the index of the normalized
+//! candidates. It is immutable once made, so it is `Send + Sync` and can be
+//! shared by reference or through an `Arc` across a thread pool. The ZKP
+//! verifiers take plain points, so the context keeps no basepoint tables.
+
+use crate::{
+    candidate::CandidateId,
+    common::{
+        verify_indexed_candidate_set, verify_input_limits, vote_verify_result,
+    },
+    parameters::ParsedPollParameters,
+    suite::default_suite,
+    utils::index_candidates,
+    verifier::{
+        verify_vote_request_points, VoteVerifyFailure, VoteVerifyOptions,
+    },
+};
+use std::{collections::HashSet, convert::TryFrom};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{PollParametersStorage, VoteRequest};
+
+/// Poll state prepared once for verifying many vote requests of the poll.
+#[derive(Clone, Debug)]
+pub struct VerificationContext {
+    parsed_parameters: ParsedPollParameters,
+    candidate_set: HashSet<CandidateId>,
+}
+
+impl VerificationContext {
+    /// Makes a context, rejecting invalid poll parameters.
+    pub fn new(
+        poll_parameters: &PollParametersStorage,
+    ) -> Result<Self, WedprError> {
+        Ok(VerificationContext::from(ParsedPollParameters::try_from(
+            poll_parameters,
+        )?))
+    }
+
+    /// Returns the parsed poll parameters of the context.
+    pub fn parsed_parameters(&self) -> &ParsedPollParameters {
+        &self.parsed_parameters
+    }
+
+    /// Verifies whether ciphertext ballots from a certified voter are valid,
+    /// like [`crate::verifier::verify_vote_request`].
+    pub fn verify(
+        &self,
+        vote_request: &VoteRequest,
+        public_key: &[u8],
+    ) -> Result<bool, WedprError> {
+        vote_verify_result(self.verify_detailed(vote_request, public_key))
+    }
+
+    /// Verifies whether ciphertext ballots from a certified voter are valid,
+    /// reporting which check failed.
+    pub fn verify_detailed(
+        &self,
+        vote_request: &VoteRequest,
+        public_key: &[u8],
+    ) -> Result<(), VoteVerifyFailure> {
+        verify_input_limits(&self.parsed_parameters.limits, vote_request)?;
+        verify_indexed_candidate_set(&self.candidate_set, vote_request)?;
+        verify_vote_request_points(
+            &self.parsed_parameters,
+            vote_request,
+            public_key,
+            VoteVerifyOptions::new(&default_suite()),
+        )
+    }
+}
+
+impl From<ParsedPollParameters> for VerificationContext {
+    fn from(parsed_parameters: ParsedPollParameters) -> Self {
+        let candidate_set = index_candidates(&parsed_parameters.candidates);
+        VerificationContext {
+            parsed_parameters,
+            candidate_set,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_verification_context() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<VerificationContext>();
+
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001", "1002"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_request_list = vec![];
+        for (voter_weight, choices) in [
+            (10, vec![1, 2, 3]),
+            (20, vec![0, 15, 5]),
+            (6, vec![6, 0, 0]),
+        ] {
+            vote_request_list.push(cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                voter_weight,
+                &choices,
+            ));
+        }
+        let mut tampered_request = vote_request_list[0].clone();
+        tampered_request.mut_range_proof()[0] ^= 1;
+
+        // One context is shared by all the verifying threads.
+        let verification_context = std::sync::Arc::new(
+            VerificationContext::new(&poll_parameters).unwrap(),
+        );
+        let handles: Vec<_> = vote_request_list
+            .into_iter()
+            .map(|vote_request| {
+                let verification_context = verification_context.clone();
+                let public_key = public_key.clone();
+                std::thread::spawn(move || {
+                    verification_context.verify(&vote_request, &public_key)
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().unwrap());
+        }
+        assert!(verification_context
+            .verify(&tampered_request, &public_key)
+            .is_err());
+        assert!(verification_context
+            .verify_detailed(&tampered_request, &public_key)
+            .is_err());
+
+        let mut invalid_parameters = poll_parameters.clone();
+        invalid_parameters.set_poll_point(vec![1; 32]);
+        assert!(VerificationContext::new(&invalid_parameters).is_err());
+    }
+}
@@ -6,23 +6,39 @@ use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
 use wedpr_l_crypto_zkp_utils::{bytes_to_point, point_to_bytes, BASEPOINT_G1};
 use wedpr_l_utils::error::WedprError;
 
+use wedpr_l_utils::traits::{Hash, Signature};
 use wedpr_s_protos::{
-    generate_ballot_signature, generate_ballots_signature,
+    generate_ballot_signature_with_params,
+    generate_ballots_signature_with_params,
     generated::acv::{
         Ballot, CandidateBallot, CandidateList, CounterParametersStorage,
-        CountingPart, DecryptedResultPartStorage, PollParametersStorage,
-        RegistrationRequest, RegistrationResponse, StringToCountingPartPair,
-        StringToInt64Pair, UnlistedBallotDecryptedResult, UnlistedVoteChoice,
+        CounterReshareProof, CountingPart, DecryptedResultPartStorage,
+        PollParametersStorage, RegistrationRequest, RegistrationResponse,
+        StringToCountingPartPair, StringToInt64Pair,
+        UnlistedBallotDecryptedResult, UnlistedVoteChoice, VoteRequest,
         VoteResultStorage, VoteStorage,
     },
 };
 
 use crate::{
-    config::POLL_RESULT_KEY_TOTAL_BALLOTS,
-    utils::{get_ballot_by_candidate, get_counting_part_by_candidate},
+    ballot::HomomorphicBallot,
+    config::{IDENTITY_POINT, POLL_RESULT_KEY_TOTAL_BALLOTS, ZERO_BALLOT},
+    nullifier::{derive_blank_ballot_nullifier, derive_vote_request_nullifier},
+    suite::{default_suite, VoteCryptoSuite},
+    tally::TallyTable,
+    utils::{
+        canonical_candidate_list, check_candidate_list,
+        get_ballot_by_candidate, get_counting_part_by_candidate,
+        index_ballots_by_candidate, index_counting_parts_by_candidate,
+        lookup_by_candidate,
+    },
+    verifier::verify_counter_reshare,
 };
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+};
 
 /// Makes system parameters for a new poll.
 pub fn make_poll_parameters(
@@ -34,18 +50,124 @@ pub fn make_poll_parameters(
         poll_point += bytes_to_point(share.get_poll_point_share())?;
     }
 
+    check_candidate_list(candidate_list)?;
     let mut storage = PollParametersStorage::default();
-    storage.set_candidates(candidate_list.clone());
+    storage.set_candidates(canonical_candidate_list(candidate_list));
     storage.set_poll_point(point_to_bytes(&poll_point));
     Ok(storage)
 }
 
+/// Makes poll parameters that also cap the ballots a voter may give to each
+/// candidate. Caps are listed in the order of the candidate list.
+pub fn make_poll_parameters_with_caps(
+    candidate_list: &CandidateList,
+    counter_parameters: &CounterParametersStorage,
+    candidate_caps: &[u32],
+) -> Result<PollParametersStorage, WedprError> {
+    if candidate_caps.len() != candidate_list.get_candidate().len() {
+        return Err(WedprError::ArgumentError);
+    }
+    let mut storage = make_poll_parameters(candidate_list, counter_parameters)?;
+    for (candidate, cap) in
+        candidate_list.get_candidate().iter().zip(candidate_caps)
+    {
+        let mut pair = StringToInt64Pair::new();
+        pair.set_key(candidate.to_string());
+        pair.set_value(*cap as i64);
+        storage.mut_candidate_cap().push(pair);
+    }
+    Ok(storage)
+}
+
+/// Replaces the share of a leaving counter with the new shares of a verified
+/// reshare. The poll parameters stay valid, as the poll point is unchanged.
+/// Parameters of threshold counters cannot be reshared, see
+/// [`verify_counter_reshare`].
+pub fn apply_counter_reshare(
+    counter_parameters: &CounterParametersStorage,
+    reshare_proof: &CounterReshareProof,
+) -> Result<CounterParametersStorage, WedprError> {
+    if !verify_counter_reshare(counter_parameters, reshare_proof)? {
+        return Err(WedprError::VerificationError);
+    }
+    let mut updated_parameters = CounterParametersStorage::new();
+    for share in counter_parameters.get_counter_parameters_share() {
+        if share.get_counter_id() == reshare_proof.get_counter_id() {
+            for new_share in reshare_proof.get_new_share() {
+                updated_parameters
+                    .mut_counter_parameters_share()
+                    .push(new_share.clone());
+            }
+        } else {
+            updated_parameters
+                .mut_counter_parameters_share()
+                .push(share.clone());
+        }
+    }
+    Ok(updated_parameters)
+}
+
 /// Certifies a voter's registration. It confirm its weight which indicates the
 /// maximum votes that the voter can vote for all candidates in a poll.
 pub fn certify_voter(
     secret_key: &[u8],
     registration_request: &RegistrationRequest,
     voter_weight: u32,
+) -> Result<RegistrationResponse, WedprError> {
+    certify_voter_with_suite(
+        &default_suite(),
+        secret_key,
+        registration_request,
+        voter_weight,
+    )
+}
+
+/// Certifies a voter's registration like [`certify_voter`], signing with the
+/// given crypto suite.
+pub fn certify_voter_with_suite<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    secret_key: &[u8],
+    registration_request: &RegistrationRequest,
+    voter_weight: u32,
+) -> Result<RegistrationResponse, WedprError> {
+    certify_voter_in_epoch_with_suite(
+        suite,
+        secret_key,
+        registration_request,
+        voter_weight,
+        &[],
+        0,
+    )
+}
+
+/// Certifies a voter's registration like [`certify_voter`], binding the blank
+/// ballot to the epoch of the poll so that it is rejected once the poll moves
+/// to another epoch. The signed message is prefixed with a domain tag and the
+/// poll id, so that the signature cannot be taken from another poll or
+/// another use of the coordinator key.
+pub fn certify_voter_in_epoch(
+    secret_key: &[u8],
+    registration_request: &RegistrationRequest,
+    voter_weight: u32,
+    poll_parameters: &PollParametersStorage,
+) -> Result<RegistrationResponse, WedprError> {
+    certify_voter_in_epoch_with_suite(
+        &default_suite(),
+        secret_key,
+        registration_request,
+        voter_weight,
+        poll_parameters.get_poll_id(),
+        poll_parameters.get_poll_epoch(),
+    )
+}
+
+fn certify_voter_in_epoch_with_suite<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    secret_key: &[u8],
+    registration_request: &RegistrationRequest,
+    voter_weight: u32,
+    poll_id: &[u8],
+    poll_epoch: u64,
 ) -> Result<RegistrationResponse, WedprError> {
     let blinding_poll_point = bytes_to_point(
         registration_request
@@ -64,16 +186,89 @@ pub fn certify_voter(
     );
     let mut response = RegistrationResponse::new();
     // Sign the above data.
-    response.set_signature(generate_ballot_signature(secret_key, &ballot)?);
+    response.set_signature(generate_ballot_signature_with_params(
+        &suite
+            .signature_params()
+            .poll_id(poll_id)
+            .poll_epoch(poll_epoch),
+        secret_key,
+        &ballot,
+    )?);
     response.set_ballot(ballot);
     response.set_voter_weight(voter_weight as u32);
     Ok(response)
 }
 
+/// Voter weights assigned by the coordinator, published so that verifiers of
+/// weighted votes can check the weight each vote request claims. Weights are
+/// keyed by the nullifier of the blank ballot certified for the voter.
+#[derive(Clone, Debug, Default)]
+pub struct VoterWeightAssignment {
+    weights: HashMap<Vec<u8>, u32>,
+}
+
+impl VoterWeightAssignment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the weight certified in a registration response.
+    pub fn assign(
+        &mut self,
+        poll_parameters: &PollParametersStorage,
+        registration_response: &RegistrationResponse,
+    ) {
+        self.weights.insert(
+            derive_blank_ballot_nullifier(
+                registration_response.get_ballot(),
+                poll_parameters,
+            ),
+            registration_response.get_voter_weight(),
+        );
+    }
+
+    /// Gets the weight assigned to the voter of a vote request, or None if
+    /// its blank ballot was not certified.
+    pub fn voter_weight(
+        &self,
+        poll_parameters: &PollParametersStorage,
+        vote_request: &VoteRequest,
+    ) -> Option<u32> {
+        self.weights
+            .get(&derive_vote_request_nullifier(
+                vote_request,
+                poll_parameters,
+            ))
+            .copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+}
+
 pub fn certify_unbounded_voter(
     secret_key: &[u8],
     registration_request: &RegistrationRequest,
     value: u32,
+) -> Result<RegistrationResponse, WedprError> {
+    certify_unbounded_voter_with_suite(
+        &default_suite(),
+        secret_key,
+        registration_request,
+        value,
+    )
+}
+
+pub fn certify_unbounded_voter_with_suite<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    secret_key: &[u8],
+    registration_request: &RegistrationRequest,
+    value: u32,
 ) -> Result<RegistrationResponse, WedprError> {
     // generate weight ballot
     let blinding_poll_point = bytes_to_point(
@@ -107,7 +302,8 @@ pub fn certify_unbounded_voter(
     );
 
     let mut response = RegistrationResponse::new();
-    response.set_signature(generate_ballots_signature(
+    response.set_signature(generate_ballots_signature_with_params(
+        &suite.signature_params(),
         secret_key,
         &weight_ballot,
         &zero_ballot,
@@ -118,6 +314,21 @@ pub fn certify_unbounded_voter(
     Ok(response)
 }
 
+/// Makes the vote sum of a poll before any part is aggregated, with an
+/// identity ballot as the blank ballot and as the ballot of each candidate.
+/// Aggregating a part into it gives the part itself.
+pub fn empty_vote_sum(poll_parameters: &PollParametersStorage) -> VoteStorage {
+    let mut vote_sum = VoteStorage::new();
+    vote_sum.set_blank_ballot(ZERO_BALLOT.clone());
+    for candidate in poll_parameters.get_candidates().get_candidate() {
+        let mut ballot_pair = CandidateBallot::new();
+        ballot_pair.set_candidate(candidate.to_string());
+        ballot_pair.set_ballot(ZERO_BALLOT.clone());
+        vote_sum.mut_voted_ballot().push(ballot_pair);
+    }
+    vote_sum
+}
+
 /// Aggregates all ciphertext ballots from a voter.
 pub fn aggregate_vote_sum_response(
     poll_parameters: &PollParametersStorage,
@@ -126,51 +337,25 @@ pub fn aggregate_vote_sum_response(
 ) -> Result<bool, WedprError> {
     // Initialize for the first part.
     if !vote_sum.has_blank_ballot() {
-        let blank_ballot = vote_sum.mut_blank_ballot();
-        blank_ballot
-            .set_ciphertext1(point_to_bytes(&RistrettoPoint::default()));
-        blank_ballot
-            .set_ciphertext2(point_to_bytes(&RistrettoPoint::default()));
-        for candidate in poll_parameters.get_candidates().get_candidate() {
-            let mut ballot = Ballot::new();
-            ballot.set_ciphertext1(point_to_bytes(&RistrettoPoint::default()));
-            ballot.set_ciphertext2(point_to_bytes(&RistrettoPoint::default()));
-            let mut ballot_pair = CandidateBallot::new();
-            ballot_pair.set_candidate(candidate.to_string());
-            ballot_pair.set_ballot(ballot);
-            vote_sum.mut_voted_ballot().push(ballot_pair);
-        }
+        *vote_sum = empty_vote_sum(poll_parameters);
     }
 
-    let c1_point =
-        bytes_to_point(&vote_part.get_blank_ballot().get_ciphertext1())?;
-    let blank_c1_sum =
-        bytes_to_point(&vote_sum.get_blank_ballot().get_ciphertext1())?
-            + c1_point;
-    let c2_point =
-        bytes_to_point(&vote_part.get_blank_ballot().get_ciphertext2())?;
-    let blank_c2_sum =
-        bytes_to_point(&vote_sum.get_blank_ballot().get_ciphertext2())?
-            + c2_point;
+    let blank_sum = HomomorphicBallot::try_from(vote_sum.get_blank_ballot())?
+        + HomomorphicBallot::try_from(vote_part.get_blank_ballot())?;
 
+    let sum_ballot_index = index_ballots_by_candidate(vote_sum);
+    let new_ballot_index = index_ballots_by_candidate(vote_part);
     let mut updated_vote_sum_list = Vec::new();
     for candidate in poll_parameters.get_candidates().get_candidate() {
-        let sum_ballot = get_ballot_by_candidate(&vote_sum, candidate)?;
-        let new_ballot = get_ballot_by_candidate(&vote_part, candidate)?;
-        let candidate_voted_c1_sum =
-            bytes_to_point(&sum_ballot.get_ciphertext1())?
-                + bytes_to_point(&new_ballot.get_ciphertext1())?;
-        let candidate_voted_c2_sum =
-            bytes_to_point(&sum_ballot.get_ciphertext2())?
-                + bytes_to_point(&new_ballot.get_ciphertext2())?;
+        let sum_ballot = lookup_by_candidate(&sum_ballot_index, candidate)?;
+        let new_ballot = lookup_by_candidate(&new_ballot_index, candidate)?;
+        let candidate_sum = HomomorphicBallot::try_from(sum_ballot)?
+            + HomomorphicBallot::try_from(new_ballot)?;
 
         // Write back.
-        let mut new_sum_ballot = Ballot::new();
-        new_sum_ballot.set_ciphertext1(point_to_bytes(&candidate_voted_c1_sum));
-        new_sum_ballot.set_ciphertext2(point_to_bytes(&candidate_voted_c2_sum));
         let mut new_pair = CandidateBallot::new();
         new_pair.set_candidate(candidate.to_string());
-        new_pair.set_ballot(new_sum_ballot);
+        new_pair.set_ballot(candidate_sum.into());
         updated_vote_sum_list.push(new_pair);
     }
     // update the voted_ballot for vote sum
@@ -178,15 +363,65 @@ pub fn aggregate_vote_sum_response(
     for voted_ballot in updated_vote_sum_list {
         vote_sum.mut_voted_ballot().push(voted_ballot);
     }
-    vote_sum
-        .mut_blank_ballot()
-        .set_ciphertext1(point_to_bytes(&blank_c1_sum));
-    vote_sum
-        .mut_blank_ballot()
-        .set_ciphertext2(point_to_bytes(&blank_c2_sum));
+    vote_sum.set_blank_ballot(blank_sum.into());
     Ok(true)
 }
 
+/// Merges two aggregated vote sums, e.g. from aggregation shards, summing the
+/// blank ballot and the ballot of each listed candidate. Merging is
+/// associative and commutative, so shards can be reduced in any order. An
+/// empty sum is the identity.
+pub fn merge_vote_sums(
+    poll_parameters: &PollParametersStorage,
+    left_sum: &VoteStorage,
+    right_sum: &VoteStorage,
+) -> Result<VoteStorage, WedprError> {
+    if !right_sum.has_blank_ballot() {
+        return Ok(left_sum.clone());
+    }
+    let mut merged_sum = left_sum.clone();
+    aggregate_vote_sum_response(poll_parameters, right_sum, &mut merged_sum)?;
+    Ok(merged_sum)
+}
+
+/// Subtracts a vote part previously aggregated into a vote sum, e.g. when a
+/// voter revokes a vote before recasting it. Subtraction is done in the group,
+/// so it is the exact inverse of [`aggregate_vote_sum_response`] for the blank
+/// ballot and the ballot of each listed candidate, and never underflows.
+///
+/// The sum cannot tell whether the part was aggregated before, so callers
+/// must only subtract accepted parts. The nullifier of the revoked vote stays
+/// recorded; remove it with [`crate::nullifier::NullifierSet::remove`] before
+/// accepting the recast vote of the same voter.
+pub fn subtract_vote_from_sum(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    vote_part: &VoteStorage,
+) -> Result<VoteStorage, WedprError> {
+    if !vote_sum.has_blank_ballot() {
+        return Err(WedprError::ArgumentError);
+    }
+    let mut negated_part = VoteStorage::new();
+    negated_part.set_blank_ballot(negate_ballot(vote_part.get_blank_ballot())?);
+    for pair in vote_part.get_voted_ballot() {
+        let mut negated_pair = CandidateBallot::new();
+        negated_pair.set_candidate(pair.get_candidate().to_string());
+        negated_pair.set_ballot(negate_ballot(pair.get_ballot())?);
+        negated_part.mut_voted_ballot().push(negated_pair);
+    }
+    let mut updated_sum = vote_sum.clone();
+    aggregate_vote_sum_response(
+        poll_parameters,
+        &negated_part,
+        &mut updated_sum,
+    )?;
+    Ok(updated_sum)
+}
+
+fn negate_ballot(ballot: &Ballot) -> Result<Ballot, WedprError> {
+    Ok((-HomomorphicBallot::try_from(ballot)?).into())
+}
+
 pub fn aggregate_vote_sum_response_unlisted(
     poll_parameters: &PollParametersStorage,
     vote_part: &VoteStorage,
@@ -202,6 +437,73 @@ pub fn aggregate_vote_sum_response_unlisted(
     Ok(true)
 }
 
+/// Folds vote parts into a running sum one at a time, so that a counter can
+/// aggregate parts from a stream without collecting them into memory first.
+pub struct VoteAggregator<'a> {
+    poll_parameters: &'a PollParametersStorage,
+    vote_sum: VoteStorage,
+}
+
+impl<'a> VoteAggregator<'a> {
+    pub fn new(poll_parameters: &'a PollParametersStorage) -> Self {
+        VoteAggregator {
+            poll_parameters,
+            vote_sum: VoteStorage::new(),
+        }
+    }
+
+    /// Adds a vote part to the running sum. The sum is initialized to
+    /// identity ballots on the first part.
+    pub fn add(&mut self, vote_part: &VoteStorage) -> Result<(), WedprError> {
+        aggregate_vote_sum_response(
+            self.poll_parameters,
+            vote_part,
+            &mut self.vote_sum,
+        )?;
+        Ok(())
+    }
+
+    /// Resumes aggregation from a snapshot of a running sum, such that
+    /// continuing gives the same sum as an uninterrupted run.
+    pub fn restore(
+        poll_parameters: &'a PollParametersStorage,
+        snapshot: VoteStorage,
+    ) -> Self {
+        VoteAggregator {
+            poll_parameters,
+            vote_sum: snapshot,
+        }
+    }
+
+    /// Returns the exact running sum, to be checkpointed and later passed to
+    /// [`VoteAggregator::restore`].
+    pub fn snapshot(&self) -> VoteStorage {
+        self.vote_sum.clone()
+    }
+
+    /// Returns the aggregated vote sum.
+    pub fn finish(self) -> VoteStorage {
+        self.vote_sum
+    }
+}
+
+/// Aggregates partially decrypted results from many counters, such as those
+/// passing [`crate::verifier::verify_count_round`].
+pub fn aggregate_decrypted_parts(
+    poll_parameters: &PollParametersStorage,
+    partially_decrypted_results: &[DecryptedResultPartStorage],
+) -> Result<DecryptedResultPartStorage, WedprError> {
+    let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+    for partially_decrypted_result in partially_decrypted_results {
+        aggregate_decrypted_part_sum(
+            poll_parameters,
+            partially_decrypted_result,
+            &mut aggregated_decrypted_result,
+        )?;
+    }
+    Ok(aggregated_decrypted_result)
+}
+
 /// Aggregates a partially decrypted result from a counter.
 pub fn aggregate_decrypted_part_sum(
     poll_parameters: &PollParametersStorage,
@@ -212,11 +514,10 @@ pub fn aggregate_decrypted_part_sum(
     if !aggregated_decrypted_result.has_blank_part() {
         let blank_part = aggregated_decrypted_result.mut_blank_part();
         blank_part.set_counter_id("sum".to_string());
-        blank_part.set_blinding_c2(point_to_bytes(&RistrettoPoint::default()));
+        blank_part.set_blinding_c2(point_to_bytes(&IDENTITY_POINT));
         for candidate in poll_parameters.get_candidates().get_candidate() {
             let mut counting_part = CountingPart::new();
-            counting_part
-                .set_blinding_c2(point_to_bytes(&RistrettoPoint::default()));
+            counting_part.set_blinding_c2(point_to_bytes(&IDENTITY_POINT));
             let mut new_pair = StringToCountingPartPair::new();
             new_pair.set_key(candidate.to_string());
             new_pair.set_value(counting_part);
@@ -240,16 +541,15 @@ pub fn aggregate_decrypted_part_sum(
         .mut_blank_part()
         .set_blinding_c2(point_to_bytes(&blank_c2_r_sum));
 
+    let aggregated_part_index =
+        index_counting_parts_by_candidate(aggregated_decrypted_result);
+    let new_part_index =
+        index_counting_parts_by_candidate(partially_decrypted_result);
     let mut updated_candidate_part_list = Vec::new();
     for candidate in poll_parameters.get_candidates().get_candidate() {
-        let aggregated_part = get_counting_part_by_candidate(
-            &aggregated_decrypted_result,
-            candidate,
-        )?;
-        let new_part = get_counting_part_by_candidate(
-            &partially_decrypted_result,
-            candidate,
-        )?;
+        let aggregated_part =
+            lookup_by_candidate(&aggregated_part_index, candidate)?;
+        let new_part = lookup_by_candidate(&new_part_index, candidate)?;
         let candidate_c2_r = bytes_to_point(&new_part.get_blinding_c2())?;
         let candidate_c2_r_sum =
             bytes_to_point(&aggregated_part.get_blinding_c2())?
@@ -346,12 +646,21 @@ pub fn aggregate_decrypted_part_sum_unlisted(
 }
 
 /// Computes the final vote result from aggregated partially decrypted results.
+/// Tallies above max_vote_limit are left out of the result. A poll without
+/// ballots reports a total of 0, where the linear search used before the
+/// tally table started at 1 and left the total out.
 pub fn finalize_vote_result(
     poll_parameters: &PollParametersStorage,
     vote_sum: &VoteStorage,
     aggregated_decrypted_result: &DecryptedResultPartStorage,
     max_vote_limit: i64,
 ) -> Result<VoteResultStorage, WedprError> {
+    // The table size is capped, so any non-negative limit is safe to build.
+    if max_vote_limit < 0 {
+        return Err(WedprError::ArgumentError);
+    }
+    let tally_table = TallyTable::new(max_vote_limit as u64);
+
     let mut result = VoteResultStorage::new();
     let blank_c1_sum =
         bytes_to_point(vote_sum.get_blank_ballot().get_ciphertext1())?;
@@ -363,14 +672,11 @@ pub fn finalize_vote_result(
 
     // Compute the total votes.
     let target_total = blank_c1_sum - blank_c2_r_sum;
-    for i in 1..=max_vote_limit {
-        if target_total.eq(&(*BASEPOINT_G1 * Scalar::from(i as u64))) {
-            let mut new_pair = StringToInt64Pair::new();
-            new_pair.set_key(POLL_RESULT_KEY_TOTAL_BALLOTS.to_string());
-            new_pair.set_value(i);
-            result.mut_result().push(new_pair);
-            break;
-        }
+    if let Some(total) = tally_table.recover(&target_total) {
+        let mut new_pair = StringToInt64Pair::new();
+        new_pair.set_key(POLL_RESULT_KEY_TOTAL_BALLOTS.to_string());
+        new_pair.set_value(total as i64);
+        result.mut_result().push(new_pair);
     }
 
     // Compute the votes for each candidate.
@@ -385,14 +691,11 @@ pub fn finalize_vote_result(
         let target_candidate =
             bytes_to_point(ballot.get_ciphertext1())? - candidate_c2_r_sum;
 
-        for i in 0..=max_vote_limit {
-            if target_candidate.eq(&(*BASEPOINT_G1 * Scalar::from(i as u64))) {
-                let mut new_pair = StringToInt64Pair::new();
-                new_pair.set_key(candidate.to_string());
-                new_pair.set_value(i);
-                result.mut_result().push(new_pair);
-                break;
-            }
+        if let Some(candidate_total) = tally_table.recover(&target_candidate) {
+            let mut new_pair = StringToInt64Pair::new();
+            new_pair.set_key(candidate.to_string());
+            new_pair.set_value(candidate_total as i64);
+            result.mut_result().push(new_pair);
         }
     }
     Ok(result)
@@ -515,3 +818,487 @@ pub fn finalize_vote_result_unlisted(
     }
     Ok(vote_result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        counter, result,
+        test_fixtures::{
+            cast_vote, make_candidate_list, make_counters,
+            make_seeded_vote_storage, verify_detailed,
+        },
+        threshold, utils, verifier, voter,
+    };
+    use curve25519_dalek::scalar::Scalar;
+    use wedpr_l_crypto_zkp_utils::{
+        bytes_to_point, get_random_scalar, point_to_bytes, BASEPOINT_G1,
+    };
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::{
+        config::SIGNATURE,
+        generated::acv::{
+            Ballot, CandidateBallot, CandidateList, DecryptedResultPartStorage,
+            PollParametersStorage, VoteRequest, VoteStorage,
+        },
+    };
+
+    #[test]
+    fn test_merge_vote_sums() {
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let mut poll_parameters = PollParametersStorage::new();
+        poll_parameters.set_candidates(candidate_list);
+        let make_sum = |seeds: &[u64; 6]| {
+            make_seeded_vote_storage(&poll_parameters, seeds)
+        };
+        let merge = |left: &VoteStorage, right: &VoteStorage| {
+            merge_vote_sums(&poll_parameters, left, right).unwrap()
+        };
+
+        proptest::proptest!(|(
+            a in proptest::array::uniform6(0u64..1_000_000),
+            b in proptest::array::uniform6(0u64..1_000_000),
+            c in proptest::array::uniform6(0u64..1_000_000),
+        )| {
+            let (a, b, c) = (make_sum(&a), make_sum(&b), make_sum(&c));
+            proptest::prop_assert_eq!(merge(&a, &b), merge(&b, &a));
+            proptest::prop_assert_eq!(
+                merge(&merge(&a, &b), &c),
+                merge(&a, &merge(&b, &c))
+            );
+            proptest::prop_assert_eq!(merge(&a, &VoteStorage::new()), a);
+        });
+    }
+
+    #[test]
+    fn test_empty_vote_sum() {
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let mut poll_parameters = PollParametersStorage::new();
+        poll_parameters.set_candidates(candidate_list);
+        let empty_sum = empty_vote_sum(&poll_parameters);
+        assert_eq!(empty_sum.get_blank_ballot(), &*ZERO_BALLOT);
+        assert_eq!(empty_sum.get_voted_ballot().len(), 3);
+        for (pair, candidate) in empty_sum
+            .get_voted_ballot()
+            .iter()
+            .zip(poll_parameters.get_candidates().get_candidate())
+        {
+            assert_eq!(pair.get_candidate(), candidate);
+            assert_eq!(pair.get_ballot(), &*ZERO_BALLOT);
+        }
+
+        let mut vote_part = VoteStorage::new();
+        let mut seed = 1u64;
+        let mut next_point = || {
+            seed += 1;
+            point_to_bytes(&(*BASEPOINT_G1 * Scalar::from(seed)))
+        };
+        vote_part.mut_blank_ballot().set_ciphertext1(next_point());
+        vote_part.mut_blank_ballot().set_ciphertext2(next_point());
+        for candidate in poll_parameters.get_candidates().get_candidate() {
+            let mut pair = CandidateBallot::new();
+            pair.set_candidate(candidate.to_string());
+            pair.mut_ballot().set_ciphertext1(next_point());
+            pair.mut_ballot().set_ciphertext2(next_point());
+            vote_part.mut_voted_ballot().push(pair);
+        }
+
+        // Aggregating into the empty sum matches the lazy initialization of
+        // a fresh sum, and gives the part back.
+        let mut from_empty_sum = empty_sum.clone();
+        aggregate_vote_sum_response(
+            &poll_parameters,
+            &vote_part,
+            &mut from_empty_sum,
+        )
+        .unwrap();
+        let mut from_fresh_sum = VoteStorage::new();
+        aggregate_vote_sum_response(
+            &poll_parameters,
+            &vote_part,
+            &mut from_fresh_sum,
+        )
+        .unwrap();
+        assert_eq!(from_empty_sum, from_fresh_sum);
+        assert_eq!(from_empty_sum, vote_part);
+
+        // Aggregating empty sums keeps the empty sum.
+        let mut zero_sum = VoteStorage::new();
+        for _ in 0..3 {
+            aggregate_vote_sum_response(
+                &poll_parameters,
+                &empty_sum,
+                &mut zero_sum,
+            )
+            .unwrap();
+        }
+        assert_eq!(zero_sum, empty_sum);
+    }
+
+    #[test]
+    fn test_subtract_vote_from_sum() {
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let mut poll_parameters = PollParametersStorage::new();
+        poll_parameters.set_candidates(candidate_list);
+        let make_part = |seeds: &[u64; 6]| {
+            make_seeded_vote_storage(&poll_parameters, seeds)
+        };
+
+        proptest::proptest!(|(
+            a in proptest::array::uniform6(0u64..1_000_000),
+            b in proptest::array::uniform6(0u64..1_000_000),
+        )| {
+            let (vote_sum, vote_part) = (make_part(&a), make_part(&b));
+            let mut updated_sum = vote_sum.clone();
+            aggregate_vote_sum_response(
+                &poll_parameters,
+                &vote_part,
+                &mut updated_sum,
+            )
+            .unwrap();
+            proptest::prop_assert_eq!(
+                subtract_vote_from_sum(
+                    &poll_parameters,
+                    &updated_sum,
+                    &vote_part
+                )
+                .unwrap(),
+                vote_sum
+            );
+        });
+
+        // Nothing was aggregated into an empty sum.
+        assert!(
+            matches!(
+                subtract_vote_from_sum(
+                    &poll_parameters,
+                    &VoteStorage::new(),
+                    &make_part(&[1, 2, 3, 4, 5, 6])
+                ),
+                Err(WedprError::ArgumentError)
+            )
+        );
+    }
+
+    #[test]
+    fn test_vote_aggregator() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters =
+            make_poll_parameters(&candidate_list, &counter_parameters).unwrap();
+
+        let voter_choice_list: Vec<Vec<u32>> =
+            vec![vec![1, 2, 3], vec![2, 4, 6], vec![10, 15, 25]];
+        let mut vote_part_list = vec![];
+        for voter_choice in &voter_choice_list {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                50,
+                voter_choice,
+            );
+            vote_part_list.push(vote_request.get_vote().clone());
+        }
+
+        let mut bulk_vote_sum = VoteStorage::new();
+        for vote_part in &vote_part_list {
+            assert!(aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_part,
+                &mut bulk_vote_sum
+            )
+            .unwrap());
+        }
+        // Feed the parts one at a time, as if read from a stream.
+        let mut aggregator = VoteAggregator::new(&poll_parameters);
+        for vote_part in vote_part_list.into_iter() {
+            aggregator.add(&vote_part).unwrap();
+        }
+        assert_eq!(aggregator.finish(), bulk_vote_sum);
+
+        // Checkpointing halfway through a stream of many parts and resuming
+        // from a restored snapshot gives the uninterrupted sum. Aggregation
+        // does not check proofs, so random ballots suffice here.
+        let random_ballot = || {
+            let mut ballot = Ballot::new();
+            ballot.set_ciphertext1(point_to_bytes(
+                &(get_random_scalar() * *BASEPOINT_G1),
+            ));
+            ballot.set_ciphertext2(point_to_bytes(
+                &(get_random_scalar() * *BASEPOINT_G1),
+            ));
+            ballot
+        };
+        let mut random_part_list = vec![];
+        for _ in 0..100 {
+            let mut vote_part = VoteStorage::new();
+            vote_part.set_blank_ballot(random_ballot());
+            for candidate in candidate_list.get_candidate() {
+                let mut ballot_pair = CandidateBallot::new();
+                ballot_pair.set_candidate(candidate.to_string());
+                ballot_pair.set_ballot(random_ballot());
+                vote_part.mut_voted_ballot().push(ballot_pair);
+            }
+            random_part_list.push(vote_part);
+        }
+        let mut straight_aggregator = VoteAggregator::new(&poll_parameters);
+        for vote_part in &random_part_list {
+            straight_aggregator.add(vote_part).unwrap();
+        }
+        let mut first_aggregator = VoteAggregator::new(&poll_parameters);
+        for vote_part in &random_part_list[..50] {
+            first_aggregator.add(vote_part).unwrap();
+        }
+        let checkpoint =
+            wedpr_s_protos::proto_to_bytes(&first_aggregator.snapshot())
+                .unwrap();
+        let snapshot: VoteStorage =
+            wedpr_s_protos::bytes_to_proto(&checkpoint).unwrap();
+        let mut resumed_aggregator =
+            VoteAggregator::restore(&poll_parameters, snapshot);
+        for vote_part in &random_part_list[50..] {
+            resumed_aggregator.add(vote_part).unwrap();
+        }
+        assert_eq!(resumed_aggregator.finish(), straight_aggregator.finish());
+
+        // Finishing without any part gives an empty sum.
+        let aggregator = VoteAggregator::new(&poll_parameters);
+        assert_eq!(aggregator.finish(), VoteStorage::new());
+    }
+
+    #[test]
+    fn test_aggregation_candidate_order() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list =
+            make_candidate_list(&["Kitten", "Doge", "Bunny", "Fox"]);
+        let poll_parameters =
+            make_poll_parameters(&candidate_list, &counter_parameters).unwrap();
+
+        let mut vote_part_list = vec![];
+        for choices in [vec![1, 2, 3, 4], vec![4, 3, 2, 1]] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                10,
+                &choices,
+            );
+            vote_part_list.push(vote_request.get_vote().clone());
+        }
+
+        let mut expected_vote_sum = VoteStorage::new();
+        for vote_part in &vote_part_list {
+            assert!(aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_part,
+                &mut expected_vote_sum
+            )
+            .unwrap());
+        }
+        // Every rotation and the reversal of the ballot order give exactly
+        // the same sum, in the candidate order of the poll.
+        for shift in 0..=candidate_list.get_candidate().len() {
+            let mut vote_sum = VoteStorage::new();
+            for vote_part in &vote_part_list {
+                let mut shuffled_part = vote_part.clone();
+                let voted_ballot = shuffled_part.mut_voted_ballot();
+                if shift == candidate_list.get_candidate().len() {
+                    voted_ballot.reverse();
+                } else {
+                    voted_ballot.rotate_left(shift);
+                }
+                assert!(aggregate_vote_sum_response(
+                    &poll_parameters,
+                    &shuffled_part,
+                    &mut vote_sum
+                )
+                .unwrap());
+            }
+            assert_eq!(vote_sum, expected_vote_sum);
+        }
+    }
+
+    #[test]
+    fn test_candidate_caps() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let uncapped_parameters =
+            make_poll_parameters(&candidate_list, &counter_parameters).unwrap();
+        let capped_parameters = make_poll_parameters_with_caps(
+            &candidate_list,
+            &counter_parameters,
+            &[5, 5, 5],
+        )
+        .unwrap();
+        assert!(make_poll_parameters_with_caps(
+            &candidate_list,
+            &counter_parameters,
+            &[5, 5],
+        )
+        .is_err());
+
+        let vote_with = |choices: Vec<u32>,
+                         poll_parameters: &PollParametersStorage|
+         -> Result<VoteRequest, WedprError> {
+            let voter_secret = voter::make_voter_secret();
+            let registration_request = voter::make_registration_request(
+                &voter_secret,
+                poll_parameters,
+            )?;
+            let registration_response =
+                certify_voter(&secret_key, &registration_request, 20)?;
+            let vote_choices =
+                voter::make_vote_choices(&choices, &candidate_list);
+            voter::vote(
+                &voter_secret,
+                &vote_choices,
+                &registration_response,
+                poll_parameters,
+            )
+        };
+
+        // Votes within the caps verify, including a vote equal to a cap.
+        let capped_request =
+            vote_with(vec![5, 2, 0], &capped_parameters).unwrap();
+        assert!(verifier::verify_vote_request(
+            &capped_parameters,
+            &capped_request,
+            &public_key
+        )
+        .unwrap());
+        // Without caps, behavior is unchanged.
+        let uncapped_request =
+            vote_with(vec![5, 2, 0], &uncapped_parameters).unwrap();
+        assert!(uncapped_request.get_cap_range_proof().is_empty());
+        assert!(verifier::verify_vote_request(
+            &uncapped_parameters,
+            &uncapped_request,
+            &public_key
+        )
+        .unwrap());
+
+        // A voter cannot prove a vote beyond a cap.
+        assert!(vote_with(vec![6, 2, 0], &capped_parameters).is_err());
+        // A vote beyond a cap made without the caps is rejected.
+        let exceeding_request =
+            vote_with(vec![6, 2, 0], &uncapped_parameters).unwrap();
+        assert!(matches!(
+            verify_detailed(
+                &capped_parameters,
+                &exceeding_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::CandidateCap)
+        ));
+        // So is one borrowing the cap proof of another vote.
+        let mut borrowed_request = exceeding_request.clone();
+        borrowed_request
+            .set_cap_range_proof(capped_request.get_cap_range_proof().to_vec());
+        assert!(matches!(
+            verify_detailed(&capped_parameters, &borrowed_request, &public_key),
+            Err(verifier::VoteVerifyFailure::CandidateCap)
+        ));
+    }
+
+    #[test]
+    fn test_single_candidate_poll() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&["1001", "1002"]);
+
+        // A poll without candidates is rejected however it is made.
+        let empty_candidate_list = CandidateList::new();
+        assert!(matches!(
+            make_poll_parameters(&empty_candidate_list, &counter_parameters),
+            Err(WedprError::ArgumentError)
+        ));
+        assert!(matches!(
+            threshold::make_threshold_poll_parameters(
+                &empty_candidate_list,
+                &counter_parameters,
+                2
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // A single candidate goes through the whole flow, with a range proof
+        // over the candidate ballot and the rest ballot.
+        let candidate_list = make_candidate_list(&["Kitten"]);
+        let poll_parameters =
+            make_poll_parameters(&candidate_list, &counter_parameters).unwrap();
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for (voter_weight, choice) in [(10, 10), (5, 3), (7, 0)] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                voter_weight,
+                &[choice],
+            );
+            assert_eq!(
+                utils::range_proof_batch_size(vote_request.get_range_proof()),
+                Some(2)
+            );
+            assert!(verify_detailed(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .is_ok());
+            aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum,
+            )
+            .unwrap();
+        }
+
+        let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+        for (share, counter_secret) in counter_parameters
+            .get_counter_parameters_share()
+            .iter()
+            .zip(&counter_secret_list)
+        {
+            let partially_decrypted_result = counter::count(
+                share.get_counter_id(),
+                counter_secret,
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            assert!(verifier::verify_count_request(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &bytes_to_point(share.get_poll_point_share()).unwrap(),
+                &partially_decrypted_result
+            )
+            .unwrap());
+            aggregate_decrypted_part_sum(
+                &poll_parameters,
+                &partially_decrypted_result,
+                &mut aggregated_decrypted_result,
+            )
+            .unwrap();
+        }
+        let vote_result = finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            30,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+        let tally = result::decode_vote_result(&vote_result).unwrap();
+        assert_eq!(tally.total, 22);
+        assert_eq!(tally.per_candidate["Kitten"], 13);
+    }
+}
@@ -2,40 +2,47 @@
 
 //! Library for a poll counter.
 
+use crate::{
+    config::COUNTER_RESHARE_DOMAIN, generators::hash_to_ristretto,
+    secret::SecretScalar,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
 use wedpr_l_crypto_zkp_discrete_logarithm_proof::prove_equality_relationship_proof;
 use wedpr_l_crypto_zkp_utils::{
-    bytes_to_point, bytes_to_scalar, get_random_scalar, point_to_bytes,
-    scalar_to_bytes, Serialize, BASEPOINT_G2,
+    bytes_to_point, get_random_scalar, point_to_bytes, Serialize, BASEPOINT_G2,
 };
 use wedpr_l_utils::error::WedprError;
 
 use wedpr_s_protos::generated::acv::{
-    CounterParametersShareRequest, CounterSecret, CountingPart,
-    DecryptedResultPartStorage, StringToCountingPartPair,
+    CounterParametersShareRequest, CounterReshareProof, CounterSecret,
+    CountingPart, DecryptedResultPartStorage, StringToCountingPartPair,
     UnlistedBallotDecryptedResult, VoteStorage,
 };
 
 /// Makes secrets used by a counter.
 pub fn make_counter_secret() -> CounterSecret {
-    let secret_share = get_random_scalar();
+    let secret_share = SecretScalar::new(get_random_scalar());
     CounterSecret {
-        poll_secret_share: scalar_to_bytes(&secret_share),
+        poll_secret_share: secret_share.to_bytes(),
+        share_index: 0,
         unknown_fields: Default::default(),
         cached_size: Default::default(),
     }
 }
 
-/// Makes share of system parameters used by a group of counters.
+/// Makes share of system parameters used by a group of counters. The share
+/// carries the share index of threshold counter secrets.
 pub fn make_parameters_share(
     counter_id: &str,
     counter_secret: &CounterSecret,
 ) -> Result<CounterParametersShareRequest, WedprError> {
     let secret_scalar =
-        bytes_to_scalar(counter_secret.get_poll_secret_share())?;
-    let poll_point_share = secret_scalar * *BASEPOINT_G2;
+        SecretScalar::from_bytes(counter_secret.get_poll_secret_share())?;
+    let poll_point_share = secret_scalar.mul_point(&BASEPOINT_G2);
     Ok(CounterParametersShareRequest {
         counter_id: counter_id.to_string(),
         poll_point_share: point_to_bytes(&poll_point_share),
+        share_index: counter_secret.get_share_index(),
         unknown_fields: Default::default(),
         cached_size: Default::default(),
     })
@@ -49,7 +56,7 @@ pub fn count(
     encrypted_vote_sum: &VoteStorage,
 ) -> Result<DecryptedResultPartStorage, WedprError> {
     let secret_share =
-        bytes_to_scalar(&counter_secret.get_poll_secret_share())?;
+        SecretScalar::from_bytes(counter_secret.get_poll_secret_share())?;
     let mut partially_decrypted_result = DecryptedResultPartStorage::new();
     for candidate_ballot_pair in encrypted_vote_sum.get_voted_ballot() {
         // Count by partially decrypting the aggregated ciphertext ballots.
@@ -57,12 +64,12 @@ pub fn count(
         let candidate_part_share = bytes_to_point(ballot.get_ciphertext2())?;
         let mut counting_part = CountingPart::new();
         counting_part.set_blinding_c2(point_to_bytes(
-            &(&candidate_part_share * (secret_share)),
+            &secret_share.mul_point(&candidate_part_share),
         ));
 
         // Prove the equality for each candidate.
         let equality_proof = prove_equality_relationship_proof(
-            &secret_share,
+            secret_share.as_scalar(),
             &BASEPOINT_G2,
             &candidate_part_share,
         );
@@ -81,9 +88,9 @@ pub fn count(
     let blank_ciphertext2_sum = bytes_to_point(
         encrypted_vote_sum.get_blank_ballot().get_ciphertext2(),
     )?;
-    let blinding_c2 = blank_ciphertext2_sum * (secret_share);
+    let blinding_c2 = secret_share.mul_point(&blank_ciphertext2_sum);
     let equality_proof = prove_equality_relationship_proof(
-        &secret_share,
+        secret_share.as_scalar(),
         &BASEPOINT_G2,
         &blank_ciphertext2_sum,
     );
@@ -105,21 +112,21 @@ pub fn count_unlisted(
         count(counter_id, counter_secret, encrypted_vote_sum)?;
     // count unlisted ballot
     let secret_share =
-        bytes_to_scalar(&counter_secret.get_poll_secret_share())?;
+        SecretScalar::from_bytes(counter_secret.get_poll_secret_share())?;
     for unlisted_ballot in encrypted_vote_sum.get_voted_ballot_unlisted() {
         let unlisted_candidate_cipher = unlisted_ballot.get_key();
         let unlisted_candidate_part_share =
             bytes_to_point(unlisted_candidate_cipher.get_ciphertext2())?;
         // generate equality proof
         let equality_proof = prove_equality_relationship_proof(
-            &secret_share,
+            secret_share.as_scalar(),
             &BASEPOINT_G2,
             &unlisted_candidate_part_share,
         );
         // decrypt and generate the equality proof for unlisted candidate
         let mut decrypted_unlisted_candidate = CountingPart::new();
         decrypted_unlisted_candidate.set_blinding_c2(point_to_bytes(
-            &(unlisted_candidate_part_share * secret_share),
+            &secret_share.mul_point(&unlisted_candidate_part_share),
         ));
         decrypted_unlisted_candidate
             .set_equality_proof(equality_proof.serialize());
@@ -130,13 +137,13 @@ pub fn count_unlisted(
         let unlisted_candidate_ballot_part_share =
             bytes_to_point(unlisted_candidate_ballot.get_ciphertext2())?;
         let equality_proof = prove_equality_relationship_proof(
-            &secret_share,
+            secret_share.as_scalar(),
             &BASEPOINT_G2,
             &unlisted_candidate_ballot_part_share,
         );
         let mut decrypted_ulisted_candidate_ballot = CountingPart::new();
         decrypted_ulisted_candidate_ballot.set_blinding_c2(point_to_bytes(
-            &(unlisted_candidate_ballot_part_share * secret_share),
+            &secret_share.mul_point(&unlisted_candidate_ballot_part_share),
         ));
         decrypted_ulisted_candidate_ballot
             .set_equality_proof(equality_proof.serialize());
@@ -164,3 +171,265 @@ pub fn count_unlisted(
     }
     Ok(partially_decrypted_result)
 }
+
+/// Splits the secret of a leaving counter into secrets for new counters,
+/// e.g. to rotate its key mid-poll. The new secrets sum to the old one, so the
+/// poll point and the ballots encrypted to it are unchanged. Returns the new
+/// secrets, in the order of the new counter ids, to be sent to their holders
+/// with [`crate::ecies::encrypt_counter_secret`], and a proof for the other
+/// counters to check with [`crate::verifier::verify_counter_reshare`].
+///
+/// The leaving counter knows every new secret, so it must erase them and its
+/// own secret once they are delivered. Secrets of threshold counters cannot be
+/// reshared, as their share index would not carry over to the new counters.
+pub fn reshare_counter(
+    counter_id: &str,
+    counter_secret: &CounterSecret,
+    new_counter_ids: &[String],
+) -> Result<(Vec<CounterSecret>, CounterReshareProof), WedprError> {
+    if new_counter_ids.is_empty() || counter_secret.get_share_index() != 0 {
+        return Err(WedprError::ArgumentError);
+    }
+    let secret_share =
+        SecretScalar::from_bytes(counter_secret.get_poll_secret_share())?;
+    let mut new_counter_secrets = Vec::with_capacity(new_counter_ids.len());
+    let mut reshare_proof = CounterReshareProof::new();
+    reshare_proof.set_counter_id(counter_id.to_string());
+    // Random secrets for all new counters but the last, which takes the rest.
+    let mut rest =
+        SecretScalar::from_bytes(counter_secret.get_poll_secret_share())?;
+    for (index, new_counter_id) in new_counter_ids.iter().enumerate() {
+        let mut new_counter_secret = CounterSecret::new();
+        if index + 1 < new_counter_ids.len() {
+            let new_secret = SecretScalar::new(get_random_scalar());
+            rest.sub_assign(&new_secret);
+            new_counter_secret.set_poll_secret_share(new_secret.to_bytes());
+        } else {
+            new_counter_secret.set_poll_secret_share(rest.to_bytes());
+        }
+        reshare_proof
+            .mut_new_share()
+            .push(make_parameters_share(new_counter_id, &new_counter_secret)?);
+        new_counter_secrets.push(new_counter_secret);
+    }
+
+    // Prove knowing the old secret, bound to the new shares.
+    let binding_base = reshare_binding_base(
+        counter_id,
+        &secret_share.mul_point(&BASEPOINT_G2),
+        reshare_proof.get_new_share(),
+    );
+    let equality_proof = prove_equality_relationship_proof(
+        secret_share.as_scalar(),
+        &BASEPOINT_G2,
+        &binding_base,
+    );
+    reshare_proof.set_binding_point(point_to_bytes(
+        &secret_share.mul_point(&binding_base),
+    ));
+    reshare_proof.set_equality_proof(equality_proof.serialize());
+    Ok((new_counter_secrets, reshare_proof))
+}
+
+// Hashes a reshare to the base of its binding point, so that the proof of the
+// old secret cannot be replayed for other new shares.
+pub(crate) fn reshare_binding_base(
+    counter_id: &str,
+    old_share: &RistrettoPoint,
+    new_shares: &[CounterParametersShareRequest],
+) -> RistrettoPoint {
+    let mut hash_vec = Vec::new();
+    append_length_prefixed(&mut hash_vec, counter_id.as_bytes());
+    hash_vec.append(&mut point_to_bytes(old_share));
+    for new_share in new_shares {
+        append_length_prefixed(
+            &mut hash_vec,
+            new_share.get_counter_id().as_bytes(),
+        );
+        append_length_prefixed(&mut hash_vec, new_share.get_poll_point_share());
+    }
+    hash_to_ristretto(COUNTER_RESHARE_DOMAIN.as_bytes(), &hash_vec)
+}
+
+fn append_length_prefixed(hash_vec: &mut Vec<u8>, bytes: &[u8]) {
+    hash_vec.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    hash_vec.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list},
+        verifier,
+    };
+    use std::collections::HashMap;
+    use wedpr_l_crypto_zkp_utils::{
+        bytes_to_point, get_random_scalar, point_to_bytes, BASEPOINT_G1,
+    };
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::{
+        config::SIGNATURE,
+        generated::acv::{
+            CounterParametersStorage, DecryptedResultPartStorage, VoteStorage,
+        },
+    };
+
+    #[test]
+    fn test_counter_reshare() {
+        use std::collections::HashMap;
+
+        let counter_id_list = ["1001", "1002", "1003"];
+        let mut counter_secrets = HashMap::new();
+        let mut counter_parameters = CounterParametersStorage::default();
+        for counter_id in counter_id_list {
+            let counter_secret = make_counter_secret();
+            counter_parameters.mut_counter_parameters_share().push(
+                make_parameters_share(counter_id, &counter_secret).unwrap(),
+            );
+            counter_secrets.insert(counter_id.to_string(), counter_secret);
+        }
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for choices in [vec![1, 2, 3], vec![4, 0, 1]] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                10,
+                &choices,
+            );
+            assert!(verifier::verify_vote_request(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .unwrap());
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum,
+            )
+            .unwrap();
+        }
+
+        // Counter 1003 leaves mid-poll, handing its share to two new counters.
+        let new_counter_ids = vec!["1004".to_string(), "1005".to_string()];
+        let (new_counter_secrets, reshare_proof) =
+            reshare_counter("1003", &counter_secrets["1003"], &new_counter_ids)
+                .unwrap();
+        assert!(verifier::verify_counter_reshare(
+            &counter_parameters,
+            &reshare_proof
+        )
+        .unwrap());
+        let updated_parameters = coordinator::apply_counter_reshare(
+            &counter_parameters,
+            &reshare_proof,
+        )
+        .unwrap();
+        // The poll point is unchanged.
+        assert_eq!(
+            coordinator::make_poll_parameters(
+                &candidate_list,
+                &updated_parameters
+            )
+            .unwrap(),
+            poll_parameters
+        );
+        counter_secrets.remove("1003");
+        for (new_counter_id, new_counter_secret) in
+            new_counter_ids.iter().zip(new_counter_secrets)
+        {
+            counter_secrets
+                .insert(new_counter_id.to_string(), new_counter_secret);
+        }
+
+        // The remaining and new counters decrypt the ballots cast before.
+        let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+        for share in updated_parameters.get_counter_parameters_share() {
+            let partially_decrypted_result = count(
+                share.get_counter_id(),
+                &counter_secrets[share.get_counter_id()],
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            assert!(verifier::verify_count_request(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &bytes_to_point(share.get_poll_point_share()).unwrap(),
+                &partially_decrypted_result
+            )
+            .unwrap());
+            coordinator::aggregate_decrypted_part_sum(
+                &poll_parameters,
+                &partially_decrypted_result,
+                &mut aggregated_decrypted_result,
+            )
+            .unwrap();
+        }
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            20,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+
+        // New shares not summing to the old share are rejected.
+        let mut unbalanced_proof = reshare_proof.clone();
+        unbalanced_proof.mut_new_share()[0].set_poll_point_share(
+            point_to_bytes(&(*BASEPOINT_G1 * get_random_scalar())),
+        );
+        assert!(!verifier::verify_counter_reshare(
+            &counter_parameters,
+            &unbalanced_proof
+        )
+        .unwrap());
+        assert!(matches!(
+            coordinator::apply_counter_reshare(
+                &counter_parameters,
+                &unbalanced_proof
+            ),
+            Err(WedprError::VerificationError)
+        ));
+        // So is a proof replayed for other new counters, or by another
+        // counter, and new counters clashing with remaining ones.
+        let mut renamed_proof = reshare_proof.clone();
+        renamed_proof.mut_new_share()[0].set_counter_id("1006".to_string());
+        assert!(!verifier::verify_counter_reshare(
+            &counter_parameters,
+            &renamed_proof
+        )
+        .unwrap());
+        let mut misattributed_proof = reshare_proof.clone();
+        misattributed_proof.set_counter_id("1002".to_string());
+        assert!(!verifier::verify_counter_reshare(
+            &counter_parameters,
+            &misattributed_proof
+        )
+        .unwrap());
+        let mut clashing_proof = reshare_proof.clone();
+        clashing_proof.mut_new_share()[0].set_counter_id("1001".to_string());
+        assert!(verifier::verify_counter_reshare(
+            &counter_parameters,
+            &clashing_proof
+        )
+        .is_err());
+        assert!(reshare_counter("1001", &counter_secrets["1001"], &[]).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of ECIES encryption over secp256k1, for counters to distribute
+//! their secret shares to each other.
+//!
+//! Ciphertexts are laid out as:
+//!
+//! | bytes | content                                         |
+//! |-------|-------------------------------------------------|
+//! | 65    | uncompressed ephemeral secp256k1 public key     |
+//! | 16    | AES-256-GCM nonce                               |
+//! | 16    | AES-256-GCM authentication tag                  |
+//! | rest  | AES-256-GCM encrypted plaintext                 |
+//!
+//! The AES key is derived with HKDF-SHA256 from the ephemeral public key and
+//! the shared point, and the tag rejects any modified ciphertext.
+
+use crate::config::ECIES;
+use wedpr_l_utils::{error::WedprError, traits::Ecies};
+use wedpr_s_protos::{
+    bytes_to_proto, generated::acv::CounterSecret, proto_to_bytes,
+};
+
+/// Encrypts a plaintext to a secp256k1 public key.
+pub fn ecies_encrypt(
+    public_key: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, WedprError> {
+    ECIES.encrypt(public_key, plaintext)
+}
+
+/// Decrypts a ciphertext with a secp256k1 secret key, failing if the
+/// ciphertext was modified.
+pub fn ecies_decrypt(
+    secret_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, WedprError> {
+    ECIES.decrypt(secret_key, ciphertext)
+}
+
+/// Encrypts a counter secret to the secp256k1 public key of its recipient.
+pub fn encrypt_counter_secret(
+    public_key: &[u8],
+    counter_secret: &CounterSecret,
+) -> Result<Vec<u8>, WedprError> {
+    ecies_encrypt(public_key, &proto_to_bytes(counter_secret)?)
+}
+
+/// Decrypts a counter secret encrypted by [`encrypt_counter_secret`].
+pub fn decrypt_counter_secret(
+    secret_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<CounterSecret, WedprError> {
+    bytes_to_proto(&ecies_decrypt(secret_key, ciphertext)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::counter;
+    use wedpr_l_utils::traits::Signature;
+
+    #[test]
+    fn test_ecies_counter_secret() {
+        use wedpr_l_crypto_signature_secp256k1::WedprSecp256k1Recover;
+
+        let key_pair_generator = WedprSecp256k1Recover::default();
+        let (public_key, secret_key) = key_pair_generator.generate_keypair();
+        let (_, other_secret_key) = key_pair_generator.generate_keypair();
+
+        let plaintext = "counter share".as_bytes();
+        let ciphertext = ecies_encrypt(&public_key, plaintext).unwrap();
+        // Ephemeral key, nonce and tag precede the encrypted plaintext.
+        assert_eq!(ciphertext.len(), 65 + 16 + 16 + plaintext.len());
+        assert_eq!(ecies_decrypt(&secret_key, &ciphertext).unwrap(), plaintext);
+        assert!(ecies_decrypt(&other_secret_key, &ciphertext).is_err());
+        // Flipping any byte of the encrypted plaintext or the tag is detected.
+        for index in [65 + 16, ciphertext.len() - 1] {
+            let mut tampered_ciphertext = ciphertext.clone();
+            tampered_ciphertext[index] ^= 1;
+            assert!(ecies_decrypt(&secret_key, &tampered_ciphertext).is_err());
+        }
+
+        let counter_secret = counter::make_counter_secret();
+        let encrypted_secret =
+            encrypt_counter_secret(&public_key, &counter_secret).unwrap();
+        assert_eq!(
+            decrypt_counter_secret(&secret_key, &encrypted_secret).unwrap(),
+            counter_secret
+        );
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for estimating the size and verification cost of a vote request
+//! from the poll parameters, without running the voting protocol.
+
+use crate::{
+    config::{RANGE_PROOF_BITS, RANGE_PROOF_FIXED_ELEMENTS},
+    parameters::parse_candidate_caps,
+};
+use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
+    prove_format_proof, prove_sum_relationship,
+};
+use wedpr_l_crypto_zkp_utils::{
+    get_random_scalar, Serialize, BASEPOINT_G1, BASEPOINT_G2,
+};
+use wedpr_l_utils::{error::WedprError, traits::Signature};
+use wedpr_s_protos::{
+    config::SIGNATURE, generated::acv::PollParametersStorage,
+};
+
+// Size of a compressed Ristretto point.
+const POINT_SIZE: usize = 32;
+// Scalar multiplications checked by the verifier of one format proof and of
+// the balance proof, counting each multiscalar term once.
+const FORMAT_PROOF_POINT_OPS: usize = 6;
+const BALANCE_PROOF_POINT_OPS: usize = 14;
+
+lazy_static! {
+    // Proof and signature sizes do not depend on the poll, so they are
+    // measured once on throwaway values.
+    static ref FORMAT_PROOF_SIZE: usize = {
+        let poll_point = get_random_scalar() * *BASEPOINT_G1;
+        prove_format_proof(
+            0,
+            &get_random_scalar(),
+            &*BASEPOINT_G1,
+            &*BASEPOINT_G2,
+            &poll_point,
+        )
+        .serialize()
+        .len()
+    };
+    static ref BALANCE_PROOF_SIZE: usize = {
+        let poll_point = get_random_scalar() * *BASEPOINT_G1;
+        prove_sum_relationship(
+            0,
+            0,
+            &get_random_scalar(),
+            &get_random_scalar(),
+            &get_random_scalar(),
+            &BASEPOINT_G1,
+            &poll_point,
+        )
+        .serialize()
+        .len()
+    };
+    static ref SIGNATURE_SIZE: usize = {
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        SIGNATURE
+            .sign(&secret_key, &[0u8; 32])
+            .map(|signature| signature.len())
+            .unwrap_or(0)
+    };
+}
+
+/// Estimated cost of a vote request for a poll.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct VoteRequestCostEstimate {
+    /// Size of the serialized protobuf request in bytes.
+    pub serialized_bytes: usize,
+    /// Point decompressions and scalar multiplications done by the verifier.
+    pub point_ops: usize,
+    /// Hashes computed by the verifier, counting one per proof challenge and
+    /// one for the signed ballot, but not the range proof transcripts.
+    pub hash_ops: usize,
+}
+
+/// Estimates the size and verification cost of a vote request for all
+/// candidates of a poll, as made by [`crate::voter::vote`].
+pub fn estimate_vote_request(
+    poll_parameters: &PollParametersStorage,
+) -> Result<VoteRequestCostEstimate, WedprError> {
+    let candidates = poll_parameters.get_candidates().get_candidate();
+    let cap_count = parse_candidate_caps(poll_parameters)?.len();
+
+    let ballot_size = 2 * field_size(POINT_SIZE);
+    let mut vote_size = field_size(*SIGNATURE_SIZE)
+        + field_size(ballot_size)
+        + field_size(field_size(POINT_SIZE));
+    let mut ballot_proof_size = 0;
+    for candidate in candidates {
+        vote_size +=
+            field_size(field_size(candidate.len()) + field_size(ballot_size));
+        ballot_proof_size += field_size(
+            field_size(candidate.len())
+                + field_size(field_size(*FORMAT_PROOF_SIZE)),
+        );
+    }
+    // The rest ballot is proved together with the candidate ballots.
+    let mut serialized_bytes = field_size(vote_size)
+        + ballot_proof_size
+        + field_size(range_proof_size(candidates.len() + 1))
+        + field_size(*BALANCE_PROOF_SIZE);
+    let mut point_ops = candidates.len() * (2 + FORMAT_PROOF_POINT_OPS)
+        + 4
+        + BALANCE_PROOF_POINT_OPS
+        + range_proof_point_ops(candidates.len() + 1);
+    if cap_count > 0 {
+        serialized_bytes += field_size(range_proof_size(cap_count));
+        point_ops += range_proof_point_ops(cap_count);
+    }
+    Ok(VoteRequestCostEstimate {
+        serialized_bytes,
+        point_ops,
+        hash_ops: candidates.len() + 2,
+    })
+}
+
+// Size of a length-delimited protobuf field numbered below 16.
+fn field_size(payload_size: usize) -> usize {
+    let mut varint_size = 1;
+    let mut value = payload_size >> 7;
+    while value > 0 {
+        varint_size += 1;
+        value >>= 7;
+    }
+    1 + varint_size + payload_size
+}
+
+// Bits proved by a batch range proof over `count` values padded to a power
+// of two.
+fn range_proof_bits(count: usize) -> usize {
+    RANGE_PROOF_BITS * count.max(1).next_power_of_two()
+}
+
+fn range_proof_size(count: usize) -> usize {
+    let rounds = range_proof_bits(count).trailing_zeros() as usize;
+    POINT_SIZE * (RANGE_PROOF_FIXED_ELEMENTS + 2 * rounds)
+}
+
+// The range proof is checked with one multiscalar multiplication over the
+// generators, the inner product rounds and a few fixed terms, plus one
+// decompression per proof point.
+fn range_proof_point_ops(count: usize) -> usize {
+    let bits = range_proof_bits(count);
+    let rounds = bits.trailing_zeros() as usize;
+    2 * bits + 2 * rounds + 7 + (4 + 2 * rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_counters},
+    };
+    use wedpr_s_protos::generated::acv::CandidateList;
+
+    #[test]
+    fn test_estimate_vote_request() {
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        for candidate_count in [1, 3, 10] {
+            let mut candidate_list = CandidateList::new();
+            for index in 0..candidate_count {
+                candidate_list
+                    .mut_candidate()
+                    .push(format!("candidate_{}", index));
+            }
+            let poll_parameters = coordinator::make_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+            )
+            .unwrap();
+            let estimate = estimate_vote_request(&poll_parameters).unwrap();
+
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                candidate_count,
+                &vec![1; candidate_count as usize],
+            );
+            let actual_size =
+                wedpr_s_protos::proto_to_bytes(&vote_request).unwrap().len();
+            // Allow 5% for variable-length encodings inside the proofs.
+            let margin = actual_size / 20;
+            assert!(estimate.serialized_bytes + margin >= actual_size);
+            assert!(estimate.serialized_bytes <= actual_size + margin);
+            assert!(estimate.point_ops > 0);
+            assert_eq!(estimate.hash_ops, candidate_count as usize + 2);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of basepoint pairs, for solutions that need generators independent
+//! of the ones used by ACV.
+//!
+//! The default pair is the global BASEPOINT_G1 and BASEPOINT_G2 used by ACV.
+//! A pair derived from a domain string comes from [`hash_to_ristretto`], whose
+//! points have no known discrete logarithm relative to each other or to the
+//! globals. The range proofs fix the value generator to BASEPOINT_G1, so
+//! derived pairs only apply to the discrete logarithm proofs, which take both
+//! generators.
+
+use crate::config::GENERATOR_SET_DOMAIN;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use sha2::{Digest, Sha512};
+use wedpr_l_crypto_zkp_utils::{BASEPOINT_G1, BASEPOINT_G2};
+use wedpr_s_common::commitment::pedersen_commit_with;
+
+/// Generator pair of Pedersen commitments and ElGamal-style ciphertexts,
+/// where g1 carries values and g2 carries randomness. Functions taking a
+/// generator set behave like their counterparts without the
+/// `_with_generators` suffix, which use the default pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeneratorSet {
+    pub g1: RistrettoPoint,
+    pub g2: RistrettoPoint,
+}
+
+impl GeneratorSet {
+    /// Derives a generator pair separated by the domain, e.g. the name of the
+    /// solution using it. The same domain always derives the same pair.
+    pub fn from_domain(domain: &str) -> Self {
+        GeneratorSet {
+            g1: derive_generator(b"G1", domain),
+            g2: derive_generator(b"G2", domain),
+        }
+    }
+
+    /// Makes the Pedersen commitment value * g1 + blinding * g2.
+    pub fn commit(&self, value: u64, blinding: &Scalar) -> RistrettoPoint {
+        pedersen_commit_with(value, blinding, &self.g1, &self.g2)
+    }
+}
+
+impl Default for GeneratorSet {
+    fn default() -> Self {
+        GeneratorSet {
+            g1: *BASEPOINT_G1,
+            g2: *BASEPOINT_G2,
+        }
+    }
+}
+
+/// Hashes a message to a nothing-up-my-sleeve point, separated by the domain.
+/// The point is mapped from a 64 byte SHA-512 digest, so its discrete
+/// logarithm is unknown to everyone, including whoever chose the message.
+pub fn hash_to_ristretto(domain: &[u8], msg: &[u8]) -> RistrettoPoint {
+    // The length prefix keeps the domain and the message from sliding into
+    // each other.
+    let mut hasher = Sha512::new();
+    hasher.update((domain.len() as u64).to_be_bytes());
+    hasher.update(domain);
+    hasher.update(msg);
+    let mut uniform_bytes = [0u8; 64];
+    uniform_bytes.copy_from_slice(&hasher.finalize());
+    RistrettoPoint::from_uniform_bytes(&uniform_bytes)
+}
+
+fn derive_generator(label: &[u8], domain: &str) -> RistrettoPoint {
+    // The fixed size label leads, so no domain can collide with another
+    // domain's generator of the other label.
+    let mut msg = label.to_vec();
+    msg.extend_from_slice(domain.as_bytes());
+    hash_to_ristretto(GENERATOR_SET_DOMAIN.as_bytes(), &msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifier;
+    use wedpr_l_crypto_zkp_utils::{get_random_scalar, point_to_bytes};
+    use wedpr_s_protos::generated::acv::Ballot;
+
+    #[test]
+    fn test_hash_to_ristretto() {
+        let point = hash_to_ristretto(b"Wedpr_test_domain", b"poll 1");
+        assert_eq!(point, hash_to_ristretto(b"Wedpr_test_domain", b"poll 1"));
+        assert_ne!(point, hash_to_ristretto(b"Wedpr_test_domain", b"poll 2"));
+        assert_ne!(point, hash_to_ristretto(b"Wedpr_other_domain", b"poll 1"));
+        // Moving bytes between the domain and the message changes the point.
+        assert_ne!(point, hash_to_ristretto(b"Wedpr_test_domainp", b"oll 1"));
+        assert_ne!(point, *BASEPOINT_G1);
+    }
+
+    #[test]
+    fn test_generator_set() {
+        use curve25519_dalek::traits::Identity;
+        use wedpr_l_crypto_zkp_discrete_logarithm_proof::prove_format_proof;
+        use wedpr_l_crypto_zkp_utils::Serialize;
+
+        let default_generators = GeneratorSet::default();
+        assert_eq!(default_generators.g1, *BASEPOINT_G1);
+        assert_eq!(default_generators.g2, *BASEPOINT_G2);
+
+        // Derivation is deterministic and separated by domain.
+        let generators = GeneratorSet::from_domain("Wedpr_test_solution");
+        assert_eq!(
+            generators,
+            GeneratorSet::from_domain("Wedpr_test_solution")
+        );
+        assert_ne!(
+            generators,
+            GeneratorSet::from_domain("Wedpr_other_solution")
+        );
+        assert_ne!(generators, default_generators);
+
+        // The derived generators are independent: distinct, not the identity,
+        // and neither a small multiple of the other nor of the globals.
+        for point in [generators.g1, generators.g2] {
+            assert_ne!(point, RistrettoPoint::identity());
+            for multiple in 1..=64u64 {
+                let scalar = Scalar::from(multiple);
+                for base in [*BASEPOINT_G1, *BASEPOINT_G2] {
+                    assert_ne!(point, scalar * base);
+                    assert_ne!(point, -(scalar * base));
+                }
+            }
+        }
+        for multiple in 1..=64u64 {
+            let scalar = Scalar::from(multiple);
+            assert_ne!(generators.g2, scalar * generators.g1);
+            assert_ne!(generators.g2, -(scalar * generators.g1));
+        }
+
+        // Commitments follow the generators.
+        let blinding = get_random_scalar();
+        assert_eq!(
+            default_generators.commit(7, &blinding),
+            wedpr_s_common::commitment::pedersen_commit(7, &blinding)
+        );
+        assert_ne!(
+            generators.commit(7, &blinding),
+            default_generators.commit(7, &blinding)
+        );
+
+        // Ballots under derived generators are only well-formed under them.
+        let poll_point = *BASEPOINT_G1 * get_random_scalar();
+        let format_proof = prove_format_proof(
+            1,
+            &blinding,
+            &generators.g1,
+            &generators.g2,
+            &poll_point,
+        )
+        .serialize();
+        let mut ballot = Ballot::new();
+        ballot.set_ciphertext1(point_to_bytes(
+            &(generators.g1 + blinding * poll_point),
+        ));
+        ballot.set_ciphertext2(point_to_bytes(&(blinding * generators.g2)));
+        assert!(verifier::verify_ballot_wellformed_with_generators(
+            &ballot,
+            &generators,
+            &poll_point,
+            &format_proof
+        )
+        .unwrap());
+        assert!(!verifier::verify_ballot_wellformed(
+            &ballot,
+            &poll_point,
+            &format_proof
+        )
+        .unwrap_or(false));
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of hash algorithms expected by signing devices and address
+//! derivation.
+
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+use wedpr_l_utils::traits::Hash;
+
+/// Implements SHA-256 (FIPS 180-4) as a Hash instance.
+#[derive(Default, Debug, Clone)]
+pub struct WedprSha256 {}
+
+impl Hash for WedprSha256 {
+    fn hash<T: ?Sized + AsRef<[u8]>>(&self, input: &T) -> Vec<u8> {
+        Sha256::digest(input.as_ref()).to_vec()
+    }
+}
+
+/// Implements RIPEMD-160 as a Hash instance. Digests are 20 bytes.
+#[derive(Default, Debug, Clone)]
+pub struct WedprRipemd160 {}
+
+impl Hash for WedprRipemd160 {
+    fn hash<T: ?Sized + AsRef<[u8]>>(&self, input: &T) -> Vec<u8> {
+        Ripemd160::digest(input.as_ref()).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, parameters, suite,
+        test_fixtures::{make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use std::convert::TryFrom;
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_sha256_and_ripemd160() {
+        use crate::config::{HASH_RIPEMD160, HASH_SHA256};
+        use wedpr_l_common_coder_hex::WedprHex;
+        use wedpr_l_utils::traits::{Coder, Hash};
+
+        // Known answers from FIPS 180-4 and the RIPEMD-160 reference.
+        let hex = WedprHex::default();
+        assert_eq!(
+            HASH_SHA256.hash(""),
+            hex.decode(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            HASH_SHA256.hash("abc"),
+            hex.decode(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            HASH_SHA256.hash(
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            ),
+            hex.decode(
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            HASH_RIPEMD160.hash(""),
+            hex.decode("9c1185a5c5e9fc54612808977ee8f548b2258d31")
+                .unwrap()
+        );
+        assert_eq!(
+            HASH_RIPEMD160.hash("abc"),
+            hex.decode("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc")
+                .unwrap()
+        );
+        assert_eq!(
+            HASH_RIPEMD160.hash("message digest"),
+            hex.decode("5d0689ef49d2fae572b881b123a85ffa21595f36")
+                .unwrap()
+        );
+
+        // Ballot message hashes work unchanged under both.
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let sha256_suite =
+            suite::VoteCryptoSuite::new(&*HASH_SHA256, &*SIGNATURE);
+        let ripemd160_suite =
+            suite::VoteCryptoSuite::new(&*HASH_RIPEMD160, &*SIGNATURE);
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2, 3], &candidate_list);
+        let registration_response = coordinator::certify_voter_with_suite(
+            &sha256_suite,
+            &secret_key,
+            &registration_request,
+            20,
+        )
+        .unwrap();
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&sha256_suite)
+        )
+        .is_ok());
+        // A ballot signed over one hash does not verify under another.
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&ripemd160_suite)
+        )
+        .is_err());
+
+        let registration_response = coordinator::certify_voter_with_suite(
+            &ripemd160_suite,
+            &secret_key,
+            &registration_request,
+            20,
+        )
+        .unwrap();
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&ripemd160_suite)
+        )
+        .is_ok());
+    }
+}
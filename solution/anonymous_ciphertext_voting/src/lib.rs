@@ -6,14 +6,42 @@
 extern crate lazy_static;
 
 #[macro_use]
-extern crate wedpr_l_macros;
-
+pub mod logging;
+
+pub mod anonymous_voting;
+pub mod ballot;
+pub mod blind_token;
+pub mod candidate;
+pub mod codec;
+pub mod committed_candidate;
+mod common;
 pub mod config;
+pub mod context;
 pub mod coordinator;
 pub mod counter;
+pub mod ecies;
+pub mod estimate;
+pub mod generators;
+pub mod hash;
+pub mod nullifier;
+pub mod parameters;
+pub mod public;
+pub mod rerandomize;
+pub mod result;
+pub mod secret;
+pub mod signature;
+pub mod snapshot;
+pub mod suite;
+pub mod tally;
+#[cfg(test)]
+mod test_fixtures;
+pub mod threshold;
+mod trace;
+pub mod transcript;
 mod utils;
 pub mod verifier;
 pub mod voter;
+pub mod vrf;
 
 #[cfg(test)]
 mod tests {
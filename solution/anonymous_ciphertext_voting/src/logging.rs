@@ -0,0 +1,159 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of log routing, for services embedding ACV with their own logger.
+//!
+//! The crate logs through its own `wedpr_println!`, which shadows the macro
+//! of `wedpr_l_macros` and passes each message to the installed handler.
+//! Without a handler, messages go to stdout as before.
+
+use std::sync::{Arc, RwLock};
+
+/// Handler receiving each log message of the crate.
+pub type LogHandler = Box<dyn Fn(&str) + Send + Sync>;
+
+// The slot holds the handler behind an Arc, so a message is passed to it
+// after the lock is released.
+type SharedLogHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+lazy_static! {
+    static ref LOG_HANDLER: RwLock<Option<SharedLogHandler>> =
+        RwLock::new(None);
+}
+
+/// Routes the log messages of the crate to the handler.
+pub fn set_log_handler(handler: LogHandler) {
+    install_handler(&LOG_HANDLER, Some(handler));
+}
+
+/// Routes the log messages of the crate back to stdout.
+pub fn clear_log_handler() {
+    install_handler(&LOG_HANDLER, None);
+}
+
+#[doc(hidden)]
+pub fn log_message(message: &str) {
+    dispatch(&LOG_HANDLER, message);
+}
+
+// The routing takes the handler slot as an argument, so tests can exercise it
+// without installing a handler for the whole process.
+fn install_handler(
+    slot: &RwLock<Option<SharedLogHandler>>,
+    handler: Option<LogHandler>,
+) {
+    *slot.write().unwrap_or_else(|e| e.into_inner()) =
+        handler.map(SharedLogHandler::from);
+}
+
+// The handler is cloned out of the slot before it is called, so a handler
+// that logs, or installs another handler, does not deadlock on the lock.
+fn dispatch(slot: &RwLock<Option<SharedLogHandler>>, message: &str) {
+    let handler = slot.read().unwrap_or_else(|e| e.into_inner()).clone();
+    match handler {
+        Some(handler) => handler(message),
+        None => println!("{}", message),
+    }
+}
+
+macro_rules! wedpr_println {
+    () => {
+        $crate::logging::log_message("")
+    };
+    ($($arg:tt)*) => {
+        $crate::logging::log_message(&format!(
+            "{}:{}: {}",
+            file!(),
+            line!(),
+            format_args!($($arg)*)
+        ))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier,
+    };
+    use std::sync::Mutex;
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_log_handler() {
+        let slot = RwLock::new(None);
+        let messages = Arc::new(Mutex::new(Vec::<String>::new()));
+        let captured = messages.clone();
+        install_handler(
+            &slot,
+            Some(Box::new(move |message| {
+                captured.lock().unwrap().push(message.to_string())
+            })),
+        );
+        dispatch(&slot, "verify_range failed!");
+        assert_eq!(*messages.lock().unwrap(), vec!["verify_range failed!"]);
+
+        // Once cleared, messages go to stdout instead.
+        install_handler(&slot, None);
+        dispatch(&slot, "verify_range failed!");
+        assert_eq!(messages.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_log_handler_reinstalls_itself() {
+        // A handler replacing itself while handling a message would deadlock
+        // if it ran under the read lock.
+        let slot: &'static RwLock<Option<SharedLogHandler>> =
+            Box::leak(Box::new(RwLock::new(None)));
+        let messages = Arc::new(Mutex::new(Vec::<String>::new()));
+        let captured = messages.clone();
+        install_handler(
+            slot,
+            Some(Box::new(move |message| {
+                captured.lock().unwrap().push(message.to_string());
+                install_handler(slot, None);
+            })),
+        );
+        dispatch(slot, "first");
+        dispatch(slot, "second");
+        assert_eq!(*messages.lock().unwrap(), vec!["first"]);
+    }
+
+    #[test]
+    fn test_failed_verification_reaches_handler() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let candidate_list = make_candidate_list(&["Kitten"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[3]);
+        vote_request.mut_range_proof().truncate(32 * 9 + 1);
+
+        let messages = Arc::new(Mutex::new(Vec::<String>::new()));
+        let captured = messages.clone();
+        set_log_handler(Box::new(move |message| {
+            captured.lock().unwrap().push(message.to_string())
+        }));
+        let result = verifier::verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+        );
+        clear_log_handler();
+
+        assert!(matches!(result, Err(WedprError::FormatError)));
+        // Tests running alongside may log through the handler too, so look
+        // for the message of this failure among the captured ones.
+        assert!(messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message.ends_with("range proof of 289 bytes")));
+    }
+}
@@ -14,8 +14,8 @@ use wedpr_s_protos::generated::acv::{
 extern crate wedpr_s_anonymous_ciphertext_voting;
 use colored::Colorize;
 use wedpr_s_anonymous_ciphertext_voting::{
-    config::POLL_RESULT_KEY_TOTAL_BALLOTS, coordinator, counter, verifier,
-    voter,
+    config::POLL_RESULT_KEY_TOTAL_BALLOTS, coordinator, counter, result,
+    verifier, voter,
 };
 
 fn main() {
@@ -413,10 +413,11 @@ fn flow_cn() {
         candidate3_votes,
         plaintext_result3
     );
+    let tallies = result::decode_vote_result(&vote_result).unwrap();
     assert!(
-        vote_result.get_result()[1].get_value() == plaintext_result1
-            && vote_result.get_result()[2].get_value() == plaintext_result2
-            && vote_result.get_result()[3].get_value() == plaintext_result3
+        tallies.per_candidate["张三"] as i64 == plaintext_result1
+            && tallies.per_candidate["李四"] as i64 == plaintext_result2
+            && tallies.per_candidate["王五"] as i64 == plaintext_result3
     );
     print_alert("\n注：密文选票的计票结果与传统明文计票结果一致。");
     pause_cn();
@@ -829,10 +830,11 @@ fn flow_en() {
         candidate3_votes,
         plaintext_result3
     );
+    let tallies = result::decode_vote_result(&vote_result).unwrap();
     assert!(
-        vote_result.get_result()[1].get_value() == plaintext_result1
-            && vote_result.get_result()[2].get_value() == plaintext_result2
-            && vote_result.get_result()[3].get_value() == plaintext_result3
+        tallies.per_candidate["Kitten"] as i64 == plaintext_result1
+            && tallies.per_candidate["Doge"] as i64 == plaintext_result2
+            && tallies.per_candidate["Bunny"] as i64 == plaintext_result3
     );
     print_alert(
         "\nNotice: ciphertext ballot counting yield the same result as \
@@ -0,0 +1,283 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of vote nullifiers for detecting double votes.
+//!
+//! A nullifier is a deterministic hash of a voter key and the poll point of
+//! [`PollParametersStorage`], so the same voter always yields the same
+//! nullifier within a poll and different nullifiers across polls. It only
+//! detects duplicates: anyone who knows the voter key can recompute the
+//! nullifier, so it does not provide any unlinkability guarantee.
+
+use crate::{
+    config::VOTE_NULLIFIER_DOMAIN,
+    suite::{default_suite, VoteCryptoSuite},
+};
+use std::collections::HashSet;
+use wedpr_l_utils::traits::{Hash, Signature};
+use wedpr_s_protos::generated::acv::{
+    Ballot, PollParametersStorage, VoteRequest,
+};
+
+/// Derives the nullifier of a voter key within a poll.
+pub fn derive_vote_nullifier(
+    public_key: &[u8],
+    poll_parameters: &PollParametersStorage,
+) -> Vec<u8> {
+    derive_vote_nullifier_with_suite(
+        &default_suite(),
+        public_key,
+        poll_parameters,
+    )
+}
+
+/// Derives the nullifier of a voter key within a poll, hashing with the given
+/// crypto suite.
+pub fn derive_vote_nullifier_with_suite<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    public_key: &[u8],
+    poll_parameters: &PollParametersStorage,
+) -> Vec<u8> {
+    let poll_point = poll_parameters.get_poll_point();
+    let mut hash_vec = Vec::new();
+    hash_vec.extend_from_slice(VOTE_NULLIFIER_DOMAIN.as_bytes());
+    hash_vec.extend_from_slice(&(poll_point.len() as u64).to_be_bytes());
+    hash_vec.extend_from_slice(poll_point);
+    hash_vec.extend_from_slice(public_key);
+    suite.hash.hash(&hash_vec)
+}
+
+/// Derives the nullifier of a vote request. The coordinator certifies each
+/// voter with one signed blank ballot, so its ciphertexts serve as the voter
+/// key.
+pub fn derive_vote_request_nullifier(
+    vote_request: &VoteRequest,
+    poll_parameters: &PollParametersStorage,
+) -> Vec<u8> {
+    derive_blank_ballot_nullifier(
+        vote_request.get_vote().get_blank_ballot(),
+        poll_parameters,
+    )
+}
+
+/// Derives the nullifier of the voter certified with the blank ballot, as
+/// [`derive_vote_request_nullifier`] does for the votes of that voter.
+pub fn derive_blank_ballot_nullifier(
+    blank_ballot: &Ballot,
+    poll_parameters: &PollParametersStorage,
+) -> Vec<u8> {
+    let mut voter_key = Vec::new();
+    voter_key.extend_from_slice(blank_ballot.get_ciphertext1());
+    voter_key.extend_from_slice(blank_ballot.get_ciphertext2());
+    derive_vote_nullifier(&voter_key, poll_parameters)
+}
+
+/// Set of nullifiers already accepted in a poll.
+#[derive(Clone, Debug, Default)]
+pub struct NullifierSet {
+    nullifiers: HashSet<Vec<u8>>,
+}
+
+impl NullifierSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a nullifier, returning false if it was already recorded.
+    pub fn insert(&mut self, nullifier: Vec<u8>) -> bool {
+        self.nullifiers.insert(nullifier)
+    }
+
+    /// Forgets a nullifier, e.g. once the vote of its voter is revoked with
+    /// [`crate::coordinator::subtract_vote_from_sum`], returning false if it
+    /// was not recorded.
+    pub fn remove(&mut self, nullifier: &[u8]) -> bool {
+        self.nullifiers.remove(nullifier)
+    }
+
+    pub fn contains(&self, nullifier: &[u8]) -> bool {
+        self.nullifiers.contains(nullifier)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nullifiers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nullifiers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use wedpr_s_protos::{config::SIGNATURE, generated::acv::VoteStorage};
+
+    #[test]
+    fn test_vote_nullifier() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        let (voter_key1, _) = SIGNATURE.generate_keypair();
+        let (voter_key2, _) = SIGNATURE.generate_keypair();
+        assert_eq!(
+            derive_vote_nullifier(&voter_key1, &poll_parameters),
+            derive_vote_nullifier(&voter_key1, &poll_parameters)
+        );
+        assert_ne!(
+            derive_vote_nullifier(&voter_key1, &poll_parameters),
+            derive_vote_nullifier(&voter_key2, &poll_parameters)
+        );
+
+        // A voter submits two different requests with the same blank ballot.
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 20)
+                .unwrap();
+        let mut vote_request_list = vec![];
+        for voter_choice in [vec![1, 2, 3], vec![5, 5, 5]] {
+            let vote_choices =
+                voter::make_vote_choices(&voter_choice, &candidate_list);
+            vote_request_list.push(
+                voter::vote(
+                    &voter_secret,
+                    &vote_choices,
+                    &registration_response,
+                    &poll_parameters,
+                )
+                .unwrap(),
+            );
+        }
+        assert_eq!(
+            derive_vote_request_nullifier(
+                &vote_request_list[0],
+                &poll_parameters
+            ),
+            derive_vote_request_nullifier(
+                &vote_request_list[1],
+                &poll_parameters
+            )
+        );
+
+        let mut nullifier_set = NullifierSet::new();
+        let public_keys = vec![&public_key[..], &public_key[..]];
+        assert_eq!(
+            verifier::verify_vote_request_batch_with_nullifiers(
+                &poll_parameters,
+                &vote_request_list,
+                &public_keys,
+                &mut nullifier_set
+            )
+            .unwrap(),
+            vec![true, false]
+        );
+        assert_eq!(nullifier_set.len(), 1);
+    }
+
+    #[test]
+    fn test_revoke_and_recast() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let public_keys = vec![&public_key[..]];
+        let mut nullifier_set = NullifierSet::new();
+        let accept = |vote_request: &VoteRequest,
+                      nullifier_set: &mut NullifierSet| {
+            verifier::verify_vote_request_batch_with_nullifiers(
+                &poll_parameters,
+                &[vote_request.clone()],
+                &public_keys,
+                nullifier_set,
+            )
+            .unwrap()[0]
+        };
+
+        // Another voter's vote stays in the sum throughout.
+        let other_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                4, 6,
+            ]);
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 10)
+                .unwrap();
+        let vote = |voter_choice: Vec<u32>| {
+            voter::vote(
+                &voter_secret,
+                &voter::make_vote_choices(&voter_choice, &candidate_list),
+                &registration_response,
+                &poll_parameters,
+            )
+            .unwrap()
+        };
+        let (first_request, recast_request) =
+            (vote(vec![1, 2]), vote(vec![7, 3]));
+
+        let mut vote_sum = VoteStorage::new();
+        for vote_request in [&other_request, &first_request] {
+            assert!(accept(vote_request, &mut nullifier_set));
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut vote_sum,
+            )
+            .unwrap();
+        }
+        // The recast vote is a double vote while the first one counts.
+        assert!(!accept(&recast_request, &mut nullifier_set));
+
+        // Revoking the first vote takes it out of the sum and frees its
+        // nullifier, so the recast vote is accepted.
+        vote_sum = coordinator::subtract_vote_from_sum(
+            &poll_parameters,
+            &vote_sum,
+            first_request.get_vote(),
+        )
+        .unwrap();
+        assert!(nullifier_set.remove(&derive_vote_request_nullifier(
+            &first_request,
+            &poll_parameters
+        )));
+        assert!(accept(&recast_request, &mut nullifier_set));
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            recast_request.get_vote(),
+            &mut vote_sum,
+        )
+        .unwrap();
+        assert!(!accept(&first_request, &mut nullifier_set));
+        assert_eq!(nullifier_set.len(), 2);
+
+        let mut expected_sum = VoteStorage::new();
+        for vote_request in [&other_request, &recast_request] {
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut expected_sum,
+            )
+            .unwrap();
+        }
+        assert_eq!(vote_sum, expected_sum);
+    }
+}
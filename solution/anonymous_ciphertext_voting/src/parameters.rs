@@ -0,0 +1,564 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of parsed poll parameters.
+
+use crate::{
+    candidate::CandidateId,
+    config::{DEFAULT_MAX_VOTE_CANDIDATES, DEFAULT_MAX_VOTE_PROOF_BYTES},
+    utils::{
+        canonical_candidate_list, check_candidate_list,
+        is_canonical_candidate_order,
+    },
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use std::{collections::HashMap, convert::TryFrom};
+use wedpr_l_crypto_zkp_utils::bytes_to_point;
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    CandidateList, CounterParametersStorage, PollParametersStorage,
+};
+
+/// Poll parameters with the poll point decompressed once, for verifying many
+/// requests of the same poll.
+#[derive(Clone, Debug)]
+pub struct ParsedPollParameters {
+    pub poll_point: RistrettoPoint,
+    pub candidates: Vec<String>,
+    /// Caps on the ballots a voter may give to each capped candidate, empty
+    /// for polls without caps.
+    pub candidate_caps: HashMap<CandidateId, u64>,
+    /// Epoch signed into blank ballots, 0 for polls without epochs.
+    pub poll_epoch: u64,
+    /// Id signed with a domain tag into blank ballots, empty for polls
+    /// without an id.
+    pub poll_id: Vec<u8>,
+    /// Number of candidates each voter of a yes/no poll must select, 0 for
+    /// polls without a required number.
+    pub required_selections: u32,
+    /// Size limits on vote requests, checked before any proof.
+    pub limits: VerifyLimits,
+}
+
+/// Size limits on vote requests, so that a request with huge ballot lists or
+/// proofs is rejected before the verifier spends any work on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifyLimits {
+    /// Maximum number of voted ballots, and of ballot proofs.
+    pub max_candidates: usize,
+    /// Maximum number of bytes of all the proofs together.
+    pub max_proof_bytes: usize,
+}
+
+impl Default for VerifyLimits {
+    fn default() -> Self {
+        VerifyLimits {
+            max_candidates: DEFAULT_MAX_VOTE_CANDIDATES,
+            max_proof_bytes: DEFAULT_MAX_VOTE_PROOF_BYTES,
+        }
+    }
+}
+
+impl TryFrom<&PollParametersStorage> for ParsedPollParameters {
+    type Error = WedprError;
+
+    fn try_from(
+        poll_parameters: &PollParametersStorage,
+    ) -> Result<Self, Self::Error> {
+        let candidates = poll_parameters.get_candidates().get_candidate();
+        if !is_canonical_candidate_order(candidates) {
+            wedpr_println!("candidates are not in canonical order");
+            return Err(WedprError::ArgumentError);
+        }
+        let required_selections = poll_parameters.get_required_selections();
+        check_required_selections(required_selections, candidates.len())?;
+        Ok(ParsedPollParameters {
+            poll_point: bytes_to_point(poll_parameters.get_poll_point())?,
+            candidates: candidates.to_vec(),
+            candidate_caps: parse_candidate_caps(poll_parameters)?,
+            poll_epoch: poll_parameters.get_poll_epoch(),
+            poll_id: poll_parameters.get_poll_id().to_vec(),
+            required_selections,
+            limits: VerifyLimits::default(),
+        })
+    }
+}
+
+/// Parses the candidate caps of a poll, rejecting caps that are negative,
+/// beyond the 32-bit range of ballots, repeated or for unknown candidates.
+pub fn parse_candidate_caps(
+    poll_parameters: &PollParametersStorage,
+) -> Result<HashMap<CandidateId, u64>, WedprError> {
+    let candidates: Vec<CandidateId> = poll_parameters
+        .get_candidates()
+        .get_candidate()
+        .iter()
+        .map(|candidate| CandidateId::new(candidate))
+        .collect();
+    let mut candidate_caps = HashMap::new();
+    for pair in poll_parameters.get_candidate_cap() {
+        let cap = pair.get_value();
+        if cap < 0
+            || cap > u32::MAX as i64
+            || !candidates
+                .iter()
+                .any(|candidate| candidate == pair.get_key())
+            || candidate_caps
+                .insert(pair.get_key().to_string(), cap as u64)
+                .is_some()
+        {
+            wedpr_println!("invalid cap for candidate {}", pair.get_key());
+            return Err(WedprError::ArgumentError);
+        }
+    }
+    Ok(candidate_caps)
+}
+
+/// Builder of [`PollParametersStorage`] that validates the parameters when
+/// built, rather than leaving mistakes to surface as verification failures.
+/// Candidates are stored in canonical order.
+#[derive(Clone, Debug, Default)]
+pub struct PollParametersBuilder {
+    poll_point: Option<Vec<u8>>,
+    candidates: Vec<String>,
+    poll_epoch: u64,
+    poll_id: Vec<u8>,
+    required_selections: u32,
+    authorized_counter_shares: Vec<Vec<u8>>,
+}
+
+impl PollParametersBuilder {
+    pub fn new() -> Self {
+        PollParametersBuilder::default()
+    }
+
+    /// Sets the encoded poll point.
+    pub fn poll_point(mut self, poll_point: &[u8]) -> Self {
+        self.poll_point = Some(poll_point.to_vec());
+        self
+    }
+
+    /// Appends candidates, in order.
+    pub fn candidates<I, S>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.candidates
+            .extend(candidates.into_iter().map(|candidate| candidate.into()));
+        self
+    }
+
+    /// Sets the poll epoch signed into blank ballots.
+    pub fn poll_epoch(mut self, poll_epoch: u64) -> Self {
+        self.poll_epoch = poll_epoch;
+        self
+    }
+
+    /// Sets the poll id signed with a domain tag into blank ballots.
+    pub fn poll_id(mut self, poll_id: &[u8]) -> Self {
+        self.poll_id = poll_id.to_vec();
+        self
+    }
+
+    /// Sets the number of candidates each voter of a yes/no poll must select.
+    pub fn required_selections(mut self, required_selections: u32) -> Self {
+        self.required_selections = required_selections;
+        self
+    }
+
+    /// Authorizes the counters of the poll to decrypt, so that partially
+    /// decrypted results of other counter shares are rejected.
+    pub fn authorized_counters(
+        mut self,
+        counter_parameters: &CounterParametersStorage,
+    ) -> Self {
+        self.authorized_counter_shares.extend(
+            counter_parameters
+                .get_counter_parameters_share()
+                .iter()
+                .map(|share| share.get_poll_point_share().to_vec()),
+        );
+        self
+    }
+
+    /// Builds the parameters, rejecting a missing or invalid poll point, an
+    /// empty or repeated candidate list and more required selections than
+    /// candidates.
+    pub fn build(self) -> Result<PollParametersStorage, WedprError> {
+        let poll_point = match self.poll_point {
+            Some(poll_point) => poll_point,
+            None => {
+                wedpr_println!("missing poll point");
+                return Err(WedprError::ArgumentError);
+            },
+        };
+        bytes_to_point(&poll_point)?;
+        let mut candidate_list = CandidateList::new();
+        candidate_list.set_candidate(self.candidates.into());
+        check_candidate_list(&candidate_list)?;
+        check_required_selections(
+            self.required_selections,
+            candidate_list.get_candidate().len(),
+        )?;
+
+        let mut storage = PollParametersStorage::new();
+        storage.set_poll_point(poll_point);
+        storage.set_candidates(canonical_candidate_list(&candidate_list));
+        storage.set_poll_epoch(self.poll_epoch);
+        storage.set_poll_id(self.poll_id);
+        storage.set_required_selections(self.required_selections);
+        storage.set_authorized_counter_share(
+            self.authorized_counter_shares.into(),
+        );
+        Ok(storage)
+    }
+}
+
+fn check_required_selections(
+    required_selections: u32,
+    candidate_count: usize,
+) -> Result<(), WedprError> {
+    if required_selections as usize > candidate_count {
+        wedpr_println!(
+            "{} required selections among {} candidates",
+            required_selections,
+            candidate_count
+        );
+        return Err(WedprError::ArgumentError);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context, coordinator,
+        test_fixtures::{make_candidate_list, make_counters, verify_detailed},
+        verifier, voter,
+    };
+    use std::convert::TryFrom;
+    use wedpr_l_crypto_zkp_utils::{
+        get_random_scalar, point_to_bytes, BASEPOINT_G1,
+    };
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::{
+        config::SIGNATURE, generated::acv::PollParametersStorage,
+    };
+
+    #[test]
+    fn test_poll_parameters_builder() {
+        let poll_point = point_to_bytes(&(get_random_scalar() * *BASEPOINT_G1));
+        let poll_parameters = PollParametersBuilder::new()
+            .poll_point(&poll_point)
+            .candidates(["Kitten", "Doge"])
+            .candidates(vec!["Bunny".to_string()])
+            .poll_epoch(3)
+            .build()
+            .unwrap();
+        assert_eq!(poll_parameters.get_poll_point(), poll_point.as_slice());
+        assert_eq!(poll_parameters.get_candidates().get_candidate(), [
+            "Bunny", "Doge", "Kitten"
+        ]);
+        assert_eq!(poll_parameters.get_poll_epoch(), 3);
+
+        // A missing poll point.
+        assert!(matches!(
+            PollParametersBuilder::new().candidates(["Kitten"]).build(),
+            Err(WedprError::ArgumentError)
+        ));
+        // An invalid poll point.
+        assert!(PollParametersBuilder::new()
+            .poll_point(&[0xffu8; 32])
+            .candidates(["Kitten"])
+            .build()
+            .is_err());
+        // An empty candidate list.
+        assert!(matches!(
+            PollParametersBuilder::new().poll_point(&poll_point).build(),
+            Err(WedprError::ArgumentError)
+        ));
+        // Duplicate candidates, also after normalization.
+        for candidates in [["Kitten", "Kitten"], ["Kitten", " Kitten "]] {
+            assert!(matches!(
+                PollParametersBuilder::new()
+                    .poll_point(&poll_point)
+                    .candidates(candidates)
+                    .build(),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_canonical_candidate_order() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let mut serialized_list = vec![];
+        for candidates in
+            [["Kitten", "Doge", "Bunny"], ["Bunny", "Kitten", "Doge"]]
+        {
+            let candidate_list = make_candidate_list(&candidates);
+            let poll_parameters = coordinator::make_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+            )
+            .unwrap();
+            assert_eq!(poll_parameters.get_candidates().get_candidate(), [
+                "Bunny", "Doge", "Kitten"
+            ]);
+            serialized_list.push(
+                wedpr_s_protos::proto_to_bytes(&poll_parameters).unwrap(),
+            );
+        }
+        assert_eq!(serialized_list[0], serialized_list[1]);
+
+        // Verifiers reject parameters whose candidates are not canonical.
+        let mut poll_parameters: PollParametersStorage =
+            wedpr_s_protos::bytes_to_proto(&serialized_list[0]).unwrap();
+        assert!(ParsedPollParameters::try_from(&poll_parameters).is_ok());
+        poll_parameters.mut_candidates().mut_candidate().swap(0, 2);
+        assert!(matches!(
+            ParsedPollParameters::try_from(&poll_parameters),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+
+    #[test]
+    fn test_poll_epoch() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let mut poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        poll_parameters.set_poll_epoch(5);
+
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2], &candidate_list);
+        let registration_response = coordinator::certify_voter_in_epoch(
+            &secret_key,
+            &registration_request,
+            10,
+            &poll_parameters,
+        )
+        .unwrap();
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(
+            verify_detailed(&poll_parameters, &vote_request, &public_key)
+                .is_ok()
+        );
+
+        // A ballot certified in the previous epoch is stale.
+        let mut next_poll_parameters = poll_parameters.clone();
+        next_poll_parameters.set_poll_epoch(6);
+        assert!(matches!(
+            verify_detailed(&next_poll_parameters, &vote_request, &public_key),
+            Err(verifier::VoteVerifyFailure::Signature)
+        ));
+
+        // A ballot certified without an epoch is not valid in an epoch.
+        let legacy_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 10)
+                .unwrap();
+        let legacy_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &legacy_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &legacy_request, &public_key),
+            Err(verifier::VoteVerifyFailure::Signature)
+        ));
+
+        // Without an epoch, the signed message is unchanged.
+        let mut legacy_poll_parameters = poll_parameters.clone();
+        legacy_poll_parameters.set_poll_epoch(0);
+        assert!(verify_detailed(
+            &legacy_poll_parameters,
+            &legacy_request,
+            &public_key
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_poll_id() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let legacy_poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut poll_parameters = legacy_poll_parameters.clone();
+        poll_parameters.set_poll_id(b"poll-2022-1".to_vec());
+
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2], &candidate_list);
+        let registration_response = coordinator::certify_voter_in_epoch(
+            &secret_key,
+            &registration_request,
+            10,
+            &poll_parameters,
+        )
+        .unwrap();
+        // The coordinator signs the domain-separated message hash.
+        assert!(SIGNATURE.verify(
+            &public_key,
+            &wedpr_s_protos::vote_message_hash(
+                &poll_parameters,
+                registration_response.get_ballot()
+            ),
+            &registration_response.get_signature().to_vec()
+        ));
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(
+            verify_detailed(&poll_parameters, &vote_request, &public_key)
+                .is_ok()
+        );
+
+        // A signature for a poll without an id is not a vote of the poll.
+        let legacy_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 10)
+                .unwrap();
+        let legacy_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &legacy_response,
+            &legacy_poll_parameters,
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &legacy_request, &public_key),
+            Err(verifier::VoteVerifyFailure::Signature)
+        ));
+        assert!(verify_detailed(
+            &legacy_poll_parameters,
+            &legacy_request,
+            &public_key
+        )
+        .is_ok());
+
+        // Nor is a vote certified for another poll, whether or not it carries
+        // its poll id.
+        let mut other_poll_parameters = poll_parameters.clone();
+        other_poll_parameters.set_poll_id(b"poll-2022-2".to_vec());
+        assert!(matches!(
+            verify_detailed(&other_poll_parameters, &vote_request, &public_key),
+            Err(verifier::VoteVerifyFailure::PollMismatch)
+        ));
+        assert!(matches!(
+            verify_detailed(
+                &legacy_poll_parameters,
+                &vote_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::PollMismatch)
+        ));
+        let mut unlabeled_request = vote_request.clone();
+        unlabeled_request.clear_poll_id();
+        assert!(matches!(
+            verify_detailed(
+                &other_poll_parameters,
+                &unlabeled_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::Signature)
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_polls_sharing_counters() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001", "1002"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let shared_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        // Both polls use the same counters, hence the same poll point.
+        let make_poll = |poll_id: &[u8]| {
+            PollParametersBuilder::new()
+                .poll_point(shared_parameters.get_poll_point())
+                .candidates(["Kitten", "Doge"])
+                .poll_id(poll_id)
+                .build()
+                .unwrap()
+        };
+        let poll_a = make_poll(b"poll-a");
+        let poll_b = make_poll(b"poll-b");
+
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_a).unwrap();
+        let registration_response = coordinator::certify_voter_in_epoch(
+            &secret_key,
+            &registration_request,
+            10,
+            &poll_a,
+        )
+        .unwrap();
+        let vote_request = voter::vote(
+            &voter_secret,
+            &voter::make_vote_choices(&vec![3, 4], poll_a.get_candidates()),
+            &registration_response,
+            &poll_a,
+        )
+        .unwrap();
+        assert_eq!(vote_request.get_poll_id(), b"poll-a");
+        assert!(verifier::verify_vote_request(
+            &poll_a,
+            &vote_request,
+            &public_key
+        )
+        .unwrap());
+
+        // A ballot cast in poll A is not accepted in poll B.
+        assert!(matches!(
+            verify_detailed(&poll_b, &vote_request, &public_key),
+            Err(verifier::VoteVerifyFailure::PollMismatch)
+        ));
+        assert!(matches!(
+            verifier::verify_vote_request(&poll_b, &vote_request, &public_key),
+            Err(WedprError::ArgumentError)
+        ));
+        assert!(matches!(
+            verifier::verify_vote_request_report(
+                &poll_b,
+                &vote_request,
+                &public_key
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+        assert!(context::VerificationContext::new(&poll_b)
+            .unwrap()
+            .verify(&vote_request, &public_key)
+            .is_err());
+    }
+}
@@ -0,0 +1,394 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of plain Rust mirrors of vote requests, for callers that should
+//! not depend on the generated protobuf types.
+//!
+//! The structs hold the same bytes as the protobuf messages and convert to
+//! and from them without loss. Converting from protobuf rejects messages
+//! missing a nested ballot or proof, which the verifiers would reject anyway.
+
+use std::convert::TryFrom;
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    Ballot, BallotProof, CandidateBallot, CipherPoints,
+    CipherPointsToBallotPair, CipherPointsToBallotProofPair,
+    StringToBallotProofPair, VoteRequest, VoteStorage,
+};
+
+/// Ciphertext ballot, or the cipher of an unlisted candidate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublicBallot {
+    pub ciphertext1: Vec<u8>,
+    pub ciphertext2: Vec<u8>,
+}
+
+/// Proofs attached to the ballot of one candidate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublicBallotProof {
+    pub format_proof: Vec<u8>,
+    /// Empty except in unbounded polls.
+    pub either_equality_proof: Vec<u8>,
+}
+
+/// Ballots of a vote, or a sum of votes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublicVote {
+    pub signature: Vec<u8>,
+    pub blank_ballot: PublicBallot,
+    pub rest_ballot: Option<PublicBallot>,
+    /// Ballot of each candidate, by candidate name.
+    pub voted_ballots: Vec<(String, PublicBallot)>,
+    /// Ballot of each unlisted candidate, by candidate cipher.
+    pub unlisted_ballots: Vec<(PublicBallot, PublicBallot)>,
+    pub zero_ballot: Option<PublicBallot>,
+}
+
+/// Vote request of a voter, mirroring [`VoteRequest`]. Fields unset by the
+/// poll are empty or zero, as in the protobuf message.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublicVoteRequest {
+    pub vote: PublicVote,
+    /// Proofs of each candidate ballot, by candidate name.
+    pub ballot_proofs: Vec<(String, PublicBallotProof)>,
+    pub range_proof: Vec<u8>,
+    pub sum_balance_proof: Vec<u8>,
+    /// Proofs of each unlisted candidate ballot, by candidate cipher.
+    pub unlisted_ballot_proofs: Vec<(PublicBallot, PublicBallotProof)>,
+    pub voter_weight: u32,
+    pub weight_proof: Vec<u8>,
+    pub cap_range_proof: Vec<u8>,
+    pub authorization_token: Vec<u8>,
+    pub poll_id: Vec<u8>,
+    pub authorization_nonce: Vec<u8>,
+}
+
+impl From<&Ballot> for PublicBallot {
+    fn from(ballot: &Ballot) -> Self {
+        PublicBallot {
+            ciphertext1: ballot.get_ciphertext1().to_vec(),
+            ciphertext2: ballot.get_ciphertext2().to_vec(),
+        }
+    }
+}
+
+impl From<&PublicBallot> for Ballot {
+    fn from(ballot: &PublicBallot) -> Self {
+        let mut encoded_ballot = Ballot::new();
+        encoded_ballot.set_ciphertext1(ballot.ciphertext1.clone());
+        encoded_ballot.set_ciphertext2(ballot.ciphertext2.clone());
+        encoded_ballot
+    }
+}
+
+impl From<&CipherPoints> for PublicBallot {
+    fn from(cipher: &CipherPoints) -> Self {
+        PublicBallot {
+            ciphertext1: cipher.get_ciphertext1().to_vec(),
+            ciphertext2: cipher.get_ciphertext2().to_vec(),
+        }
+    }
+}
+
+impl From<&PublicBallot> for CipherPoints {
+    fn from(cipher: &PublicBallot) -> Self {
+        let mut encoded_cipher = CipherPoints::new();
+        encoded_cipher.set_ciphertext1(cipher.ciphertext1.clone());
+        encoded_cipher.set_ciphertext2(cipher.ciphertext2.clone());
+        encoded_cipher
+    }
+}
+
+impl From<&BallotProof> for PublicBallotProof {
+    fn from(ballot_proof: &BallotProof) -> Self {
+        PublicBallotProof {
+            format_proof: ballot_proof.get_format_proof().to_vec(),
+            either_equality_proof: ballot_proof
+                .get_either_equality_proof()
+                .to_vec(),
+        }
+    }
+}
+
+impl From<&PublicBallotProof> for BallotProof {
+    fn from(ballot_proof: &PublicBallotProof) -> Self {
+        let mut encoded_proof = BallotProof::new();
+        encoded_proof.set_format_proof(ballot_proof.format_proof.clone());
+        encoded_proof.set_either_equality_proof(
+            ballot_proof.either_equality_proof.clone(),
+        );
+        encoded_proof
+    }
+}
+
+impl TryFrom<&VoteStorage> for PublicVote {
+    type Error = WedprError;
+
+    fn try_from(vote: &VoteStorage) -> Result<Self, Self::Error> {
+        if !vote.has_blank_ballot() {
+            wedpr_println!("vote has no blank ballot");
+            return Err(WedprError::FormatError);
+        }
+        let mut voted_ballots =
+            Vec::with_capacity(vote.get_voted_ballot().len());
+        for pair in vote.get_voted_ballot() {
+            if !pair.has_ballot() {
+                wedpr_println!(
+                    "candidate {} has no ballot",
+                    pair.get_candidate()
+                );
+                return Err(WedprError::FormatError);
+            }
+            voted_ballots.push((
+                pair.get_candidate().to_string(),
+                PublicBallot::from(pair.get_ballot()),
+            ));
+        }
+        let mut unlisted_ballots =
+            Vec::with_capacity(vote.get_voted_ballot_unlisted().len());
+        for pair in vote.get_voted_ballot_unlisted() {
+            if !pair.has_key() || !pair.has_ballot() {
+                wedpr_println!("unlisted candidate has no cipher or ballot");
+                return Err(WedprError::FormatError);
+            }
+            unlisted_ballots.push((
+                PublicBallot::from(pair.get_key()),
+                PublicBallot::from(pair.get_ballot()),
+            ));
+        }
+        Ok(PublicVote {
+            signature: vote.get_signature().to_vec(),
+            blank_ballot: PublicBallot::from(vote.get_blank_ballot()),
+            rest_ballot: if vote.has_rest_ballot() {
+                Some(PublicBallot::from(vote.get_rest_ballot()))
+            } else {
+                None
+            },
+            voted_ballots,
+            unlisted_ballots,
+            zero_ballot: if vote.has_zero_ballot() {
+                Some(PublicBallot::from(vote.get_zero_ballot()))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl From<&PublicVote> for VoteStorage {
+    fn from(vote: &PublicVote) -> Self {
+        let mut encoded_vote = VoteStorage::new();
+        encoded_vote.set_signature(vote.signature.clone());
+        encoded_vote.set_blank_ballot(Ballot::from(&vote.blank_ballot));
+        if let Some(rest_ballot) = &vote.rest_ballot {
+            encoded_vote.set_rest_ballot(Ballot::from(rest_ballot));
+        }
+        for (candidate, ballot) in &vote.voted_ballots {
+            let mut pair = CandidateBallot::new();
+            pair.set_candidate(candidate.clone());
+            pair.set_ballot(Ballot::from(ballot));
+            encoded_vote.mut_voted_ballot().push(pair);
+        }
+        for (cipher, ballot) in &vote.unlisted_ballots {
+            let mut pair = CipherPointsToBallotPair::new();
+            pair.set_key(CipherPoints::from(cipher));
+            pair.set_ballot(Ballot::from(ballot));
+            encoded_vote.mut_voted_ballot_unlisted().push(pair);
+        }
+        if let Some(zero_ballot) = &vote.zero_ballot {
+            encoded_vote.set_zero_ballot(Ballot::from(zero_ballot));
+        }
+        encoded_vote
+    }
+}
+
+impl TryFrom<&VoteRequest> for PublicVoteRequest {
+    type Error = WedprError;
+
+    fn try_from(vote_request: &VoteRequest) -> Result<Self, Self::Error> {
+        if !vote_request.has_vote() {
+            wedpr_println!("vote request has no vote");
+            return Err(WedprError::FormatError);
+        }
+        let mut ballot_proofs =
+            Vec::with_capacity(vote_request.get_ballot_proof().len());
+        for pair in vote_request.get_ballot_proof() {
+            if !pair.has_value() {
+                wedpr_println!(
+                    "candidate {} has no ballot proof",
+                    pair.get_key()
+                );
+                return Err(WedprError::FormatError);
+            }
+            ballot_proofs.push((
+                pair.get_key().to_string(),
+                PublicBallotProof::from(pair.get_value()),
+            ));
+        }
+        let mut unlisted_ballot_proofs =
+            Vec::with_capacity(vote_request.get_unlisted_ballot_proof().len());
+        for pair in vote_request.get_unlisted_ballot_proof() {
+            if !pair.has_key() || !pair.has_value() {
+                wedpr_println!("unlisted candidate has no cipher or proof");
+                return Err(WedprError::FormatError);
+            }
+            unlisted_ballot_proofs.push((
+                PublicBallot::from(pair.get_key()),
+                PublicBallotProof::from(pair.get_value()),
+            ));
+        }
+        Ok(PublicVoteRequest {
+            vote: PublicVote::try_from(vote_request.get_vote())?,
+            ballot_proofs,
+            range_proof: vote_request.get_range_proof().to_vec(),
+            sum_balance_proof: vote_request.get_sum_balance_proof().to_vec(),
+            unlisted_ballot_proofs,
+            voter_weight: vote_request.get_voter_weight(),
+            weight_proof: vote_request.get_weight_proof().to_vec(),
+            cap_range_proof: vote_request.get_cap_range_proof().to_vec(),
+            authorization_token: vote_request
+                .get_authorization_token()
+                .to_vec(),
+            poll_id: vote_request.get_poll_id().to_vec(),
+            authorization_nonce: vote_request
+                .get_authorization_nonce()
+                .to_vec(),
+        })
+    }
+}
+
+impl From<&PublicVoteRequest> for VoteRequest {
+    fn from(vote_request: &PublicVoteRequest) -> Self {
+        let mut encoded_request = VoteRequest::new();
+        encoded_request.set_vote(VoteStorage::from(&vote_request.vote));
+        for (candidate, ballot_proof) in &vote_request.ballot_proofs {
+            let mut pair = StringToBallotProofPair::new();
+            pair.set_key(candidate.clone());
+            pair.set_value(BallotProof::from(ballot_proof));
+            encoded_request.mut_ballot_proof().push(pair);
+        }
+        encoded_request.set_range_proof(vote_request.range_proof.clone());
+        encoded_request
+            .set_sum_balance_proof(vote_request.sum_balance_proof.clone());
+        for (cipher, ballot_proof) in &vote_request.unlisted_ballot_proofs {
+            let mut pair = CipherPointsToBallotProofPair::new();
+            pair.set_key(CipherPoints::from(cipher));
+            pair.set_value(BallotProof::from(ballot_proof));
+            encoded_request.mut_unlisted_ballot_proof().push(pair);
+        }
+        encoded_request.set_voter_weight(vote_request.voter_weight);
+        encoded_request.set_weight_proof(vote_request.weight_proof.clone());
+        encoded_request
+            .set_cap_range_proof(vote_request.cap_range_proof.clone());
+        encoded_request
+            .set_authorization_token(vote_request.authorization_token.clone());
+        encoded_request.set_poll_id(vote_request.poll_id.clone());
+        encoded_request
+            .set_authorization_nonce(vote_request.authorization_nonce.clone());
+        encoded_request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+        verifier,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_public_vote_request() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2,
+            ]);
+
+        let public_request =
+            PublicVoteRequest::try_from(&vote_request).unwrap();
+        assert_eq!(VoteRequest::from(&public_request), vote_request);
+        assert_eq!(public_request.vote.voted_ballots.len(), 2);
+        assert!(public_request.vote.rest_ballot.is_some());
+        assert!(public_request.vote.zero_ballot.is_none());
+        assert!(verifier::verify_vote_request(
+            &poll_parameters,
+            &VoteRequest::from(&public_request),
+            &public_key
+        )
+        .unwrap());
+
+        // Unlisted ballots and proofs round trip too.
+        let mut unlisted_request = vote_request.clone();
+        let mut cipher = CipherPoints::new();
+        cipher.set_ciphertext1(vec![1u8; 32]);
+        cipher.set_ciphertext2(vec![2u8; 32]);
+        let mut ballot = Ballot::new();
+        ballot.set_ciphertext1(vec![3u8; 32]);
+        ballot.set_ciphertext2(vec![4u8; 32]);
+        let mut ballot_pair = CipherPointsToBallotPair::new();
+        ballot_pair.set_key(cipher.clone());
+        ballot_pair.set_ballot(ballot);
+        unlisted_request
+            .mut_vote()
+            .mut_voted_ballot_unlisted()
+            .push(ballot_pair);
+        let mut ballot_proof = BallotProof::new();
+        ballot_proof.set_format_proof(vec![5u8; 8]);
+        ballot_proof.set_either_equality_proof(vec![6u8; 8]);
+        let mut proof_pair = CipherPointsToBallotProofPair::new();
+        proof_pair.set_key(cipher);
+        proof_pair.set_value(ballot_proof);
+        unlisted_request
+            .mut_unlisted_ballot_proof()
+            .push(proof_pair);
+        unlisted_request.mut_vote().set_zero_ballot(Ballot::new());
+        let public_unlisted_request =
+            PublicVoteRequest::try_from(&unlisted_request).unwrap();
+        assert_eq!(public_unlisted_request.unlisted_ballot_proofs, vec![(
+            PublicBallot {
+                ciphertext1: vec![1u8; 32],
+                ciphertext2: vec![2u8; 32],
+            },
+            PublicBallotProof {
+                format_proof: vec![5u8; 8],
+                either_equality_proof: vec![6u8; 8],
+            }
+        )]);
+        assert_eq!(
+            public_unlisted_request.vote.zero_ballot,
+            Some(PublicBallot::default())
+        );
+        assert_eq!(
+            VoteRequest::from(&public_unlisted_request),
+            unlisted_request
+        );
+
+        // Requests missing a nested message cannot be mirrored.
+        let mut incomplete_request = vote_request.clone();
+        incomplete_request.mut_vote().clear_blank_ballot();
+        assert!(matches!(
+            PublicVoteRequest::try_from(&incomplete_request),
+            Err(WedprError::FormatError)
+        ));
+        incomplete_request = vote_request.clone();
+        incomplete_request.mut_ballot_proof()[0].clear_value();
+        assert!(matches!(
+            PublicVoteRequest::try_from(&incomplete_request),
+            Err(WedprError::FormatError)
+        ));
+        assert!(matches!(
+            PublicVoteRequest::try_from(&VoteRequest::new()),
+            Err(WedprError::FormatError)
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for re-randomizing ciphertext ballots, e.g. inside a re-encryption
+//! mixnet.
+//!
+//! A ballot (v * G1 + r * poll_point, r * G2) becomes
+//! (v * G1 + (r + s) * poll_point, (r + s) * G2), which decrypts to the same
+//! value but cannot be linked to the original without knowing s. The mix
+//! proves that both parts were shifted by the same s, so the plaintext is
+//! unchanged.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
+    prove_equality_relationship_proof, verify_equality_relationship_proof,
+};
+use wedpr_l_crypto_zkp_utils::{
+    bytes_to_point, get_random_scalar, point_to_bytes, Deserialize, Serialize,
+    BASEPOINT_G2,
+};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::Ballot;
+
+/// Re-randomizes a ballot with the given randomness.
+pub fn rerandomize_ballot(
+    ballot: &Ballot,
+    poll_point: &RistrettoPoint,
+    randomness: &Scalar,
+) -> Result<Ballot, WedprError> {
+    let ciphertext1 =
+        bytes_to_point(ballot.get_ciphertext1())? + randomness * poll_point;
+    let ciphertext2 =
+        bytes_to_point(ballot.get_ciphertext2())? + randomness * *BASEPOINT_G2;
+    let mut rerandomized_ballot = Ballot::new();
+    rerandomized_ballot.set_ciphertext1(point_to_bytes(&ciphertext1));
+    rerandomized_ballot.set_ciphertext2(point_to_bytes(&ciphertext2));
+    Ok(rerandomized_ballot)
+}
+
+/// Proves that a ballot was re-randomized with the given randomness, without
+/// revealing it.
+pub fn prove_rerandomization(
+    poll_point: &RistrettoPoint,
+    randomness: &Scalar,
+) -> Vec<u8> {
+    prove_equality_relationship_proof(randomness, &BASEPOINT_G2, poll_point)
+        .serialize()
+}
+
+/// Re-randomizes a ballot with fresh randomness, and proves it.
+pub fn rerandomize_ballot_with_proof(
+    ballot: &Ballot,
+    poll_point: &RistrettoPoint,
+) -> Result<(Ballot, Vec<u8>), WedprError> {
+    let randomness = get_random_scalar();
+    let rerandomized_ballot =
+        rerandomize_ballot(ballot, poll_point, &randomness)?;
+    Ok((
+        rerandomized_ballot,
+        prove_rerandomization(poll_point, &randomness),
+    ))
+}
+
+/// Verifies whether a ballot is a re-randomization of the original ballot,
+/// encrypting the same value.
+pub fn verify_rerandomization_proof(
+    original_ballot: &Ballot,
+    rerandomized_ballot: &Ballot,
+    poll_point: &RistrettoPoint,
+    proof: &[u8],
+) -> Result<bool, WedprError> {
+    let c1_shift = bytes_to_point(rerandomized_ballot.get_ciphertext1())?
+        - bytes_to_point(original_ballot.get_ciphertext1())?;
+    let c2_shift = bytes_to_point(rerandomized_ballot.get_ciphertext2())?
+        - bytes_to_point(original_ballot.get_ciphertext2())?;
+    let proof = Deserialize::deserialize(proof)?;
+    verify_equality_relationship_proof(
+        &c2_shift,
+        &c1_shift,
+        &proof,
+        &BASEPOINT_G2,
+        poll_point,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, counter,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+    };
+    use wedpr_l_crypto_zkp_utils::BASEPOINT_G1;
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::{
+        config::SIGNATURE,
+        generated::acv::{DecryptedResultPartStorage, VoteStorage},
+    };
+
+    #[test]
+    fn test_rerandomize_ballot() {
+        let counter_id_list = ["1001", "1002"];
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&counter_id_list);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let poll_point =
+            bytes_to_point(poll_parameters.get_poll_point()).unwrap();
+
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for (voter_weight, choices) in [(10, vec![3, 5]), (20, vec![7, 1])] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                voter_weight,
+                &choices,
+            );
+            assert!(coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum
+            )
+            .unwrap());
+        }
+
+        // Pass every ballot of the sum through the mix.
+        let mut mixed_vote_sum = encrypted_vote_sum.clone();
+        let (blank_ballot, blank_proof) = rerandomize_ballot_with_proof(
+            encrypted_vote_sum.get_blank_ballot(),
+            &poll_point,
+        )
+        .unwrap();
+        assert_ne!(&blank_ballot, encrypted_vote_sum.get_blank_ballot());
+        assert!(verify_rerandomization_proof(
+            encrypted_vote_sum.get_blank_ballot(),
+            &blank_ballot,
+            &poll_point,
+            &blank_proof
+        )
+        .unwrap());
+        mixed_vote_sum.set_blank_ballot(blank_ballot);
+        for pair in mixed_vote_sum.mut_voted_ballot() {
+            let (ballot, proof) =
+                rerandomize_ballot_with_proof(pair.get_ballot(), &poll_point)
+                    .unwrap();
+            assert!(verify_rerandomization_proof(
+                pair.get_ballot(),
+                &ballot,
+                &poll_point,
+                &proof
+            )
+            .unwrap());
+            pair.set_ballot(ballot);
+        }
+
+        // A mix changing the plaintext cannot prove the re-randomization.
+        let original_ballot =
+            encrypted_vote_sum.get_voted_ballot()[0].get_ballot();
+        let randomness = get_random_scalar();
+        let mut tampered_ballot =
+            rerandomize_ballot(original_ballot, &poll_point, &randomness)
+                .unwrap();
+        let tampered_c1 = bytes_to_point(tampered_ballot.get_ciphertext1())
+            .unwrap()
+            + *BASEPOINT_G1;
+        tampered_ballot.set_ciphertext1(point_to_bytes(&tampered_c1));
+        assert!(!verify_rerandomization_proof(
+            original_ballot,
+            &tampered_ballot,
+            &poll_point,
+            &prove_rerandomization(&poll_point, &randomness)
+        )
+        .unwrap());
+
+        // Both sums decrypt to the same tally.
+        let mut vote_result_list = vec![];
+        for vote_sum in [&encrypted_vote_sum, &mixed_vote_sum] {
+            let mut aggregated_decrypted_result =
+                DecryptedResultPartStorage::new();
+            for index in 0..counter_secret_list.len() {
+                let partially_decrypted_result = counter::count(
+                    &counter_id_list[index],
+                    &counter_secret_list[index],
+                    vote_sum,
+                )
+                .unwrap();
+                assert!(coordinator::aggregate_decrypted_part_sum(
+                    &poll_parameters,
+                    &partially_decrypted_result,
+                    &mut aggregated_decrypted_result
+                )
+                .unwrap());
+            }
+            vote_result_list.push(
+                coordinator::finalize_vote_result(
+                    &poll_parameters,
+                    vote_sum,
+                    &aggregated_decrypted_result,
+                    100,
+                )
+                .unwrap(),
+            );
+        }
+        assert_eq!(vote_result_list[0], vote_result_list[1]);
+    }
+}
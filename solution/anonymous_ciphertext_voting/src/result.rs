@@ -0,0 +1,161 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of typed poll results.
+
+use crate::{
+    candidate::CandidateId, config::POLL_RESULT_KEY_TOTAL_BALLOTS,
+    utils::checked_result_value,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::VoteResultStorage;
+
+/// Decrypted tallies of a poll.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteResult {
+    /// Total ballots of all voters, stored under
+    /// [`POLL_RESULT_KEY_TOTAL_BALLOTS`].
+    pub total: u64,
+    /// Ballots received by each candidate.
+    pub per_candidate: HashMap<String, u64>,
+}
+
+/// Decodes a vote result into typed tallies, rejecting a missing total,
+/// negative tallies and candidates repeated after normalization.
+pub fn decode_vote_result(
+    vote_result: &VoteResultStorage,
+) -> Result<VoteResult, WedprError> {
+    let mut total = None;
+    let mut per_candidate =
+        HashMap::with_capacity(vote_result.get_result().len());
+    let mut candidate_set =
+        HashSet::with_capacity(vote_result.get_result().len());
+    for pair in vote_result.get_result() {
+        let value = checked_result_value(pair.get_value())?;
+        if pair.get_key() == POLL_RESULT_KEY_TOTAL_BALLOTS {
+            if total.replace(value).is_some() {
+                wedpr_println!("duplicate total ballots");
+                return Err(WedprError::ArgumentError);
+            }
+            continue;
+        }
+        if !candidate_set.insert(CandidateId::new(pair.get_key())) {
+            wedpr_println!("duplicate result for candidate {}", pair.get_key());
+            return Err(WedprError::ArgumentError);
+        }
+        per_candidate.insert(pair.get_key().to_string(), value);
+    }
+    match total {
+        Some(total) => Ok(VoteResult {
+            total,
+            per_candidate,
+        }),
+        None => {
+            wedpr_println!("missing total ballots");
+            Err(WedprError::ArgumentError)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_decode_vote_result() {
+        use wedpr_s_protos::generated::acv::StringToInt64Pair;
+
+        let make_vote_result = |pairs: &[(&str, i64)]| {
+            let mut vote_result = VoteResultStorage::new();
+            for (key, value) in pairs {
+                let mut pair = StringToInt64Pair::new();
+                pair.set_key(key.to_string());
+                pair.set_value(*value);
+                vote_result.mut_result().push(pair);
+            }
+            vote_result
+        };
+
+        let decoded = decode_vote_result(&make_vote_result(&[
+            ("Kitten", 33),
+            (POLL_RESULT_KEY_TOTAL_BALLOTS, 140),
+            ("Doge", 31),
+        ]))
+        .unwrap();
+        assert_eq!(decoded.total, 140);
+        assert_eq!(decoded.per_candidate.len(), 2);
+        assert_eq!(decoded.per_candidate["Kitten"], 33);
+        assert_eq!(decoded.per_candidate["Doge"], 31);
+
+        // A missing total, a repeated total, a repeated candidate, also after
+        // normalization, and a negative tally are all rejected.
+        for pairs in [
+            vec![("Kitten", 33), ("Doge", 31)],
+            vec![
+                (POLL_RESULT_KEY_TOTAL_BALLOTS, 140),
+                (POLL_RESULT_KEY_TOTAL_BALLOTS, 140),
+            ],
+            vec![
+                (POLL_RESULT_KEY_TOTAL_BALLOTS, 140),
+                ("Kitten", 33),
+                ("Kitten", 1),
+            ],
+            vec![
+                (POLL_RESULT_KEY_TOTAL_BALLOTS, 140),
+                ("Kitten", 33),
+                (" Kitten", 1),
+            ],
+            vec![(POLL_RESULT_KEY_TOTAL_BALLOTS, 140), ("Kitten", -1)],
+        ] {
+            assert!(matches!(
+                decode_vote_result(&make_vote_result(&pairs)),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_result_serde() {
+        let mut vote_result = VoteResult::default();
+        vote_result.total = 60;
+        vote_result.per_candidate.insert("Kitten".to_string(), 10);
+        vote_result.per_candidate.insert("Doge".to_string(), 50);
+        let json = serde_json::to_value(&vote_result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "total": 60,
+                "per_candidate": {"Kitten": 10, "Doge": 50}
+            })
+        );
+        assert!(json["total"].is_u64());
+        let decoded: VoteResult =
+            serde_json::from_str(&json.to_string()).unwrap();
+        assert_eq!(decoded, vote_result);
+        // Negative tallies do not fit the typed result.
+        assert!(serde_json::from_str::<VoteResult>(
+            r#"{"total": -1, "per_candidate": {}}"#
+        )
+        .is_err());
+
+        let cost_estimate = estimate::VoteRequestCostEstimate {
+            serialized_bytes: 2048,
+            point_ops: 120,
+            hash_ops: 9,
+        };
+        let json = serde_json::to_value(&cost_estimate).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "serialized_bytes": 2048,
+                "point_ops": 120,
+                "hash_ops": 9
+            })
+        );
+        let decoded: estimate::VoteRequestCostEstimate =
+            serde_json::from_str(&json.to_string()).unwrap();
+        assert_eq!(decoded, cost_estimate);
+    }
+}
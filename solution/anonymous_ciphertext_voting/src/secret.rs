@@ -0,0 +1,133 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of secret key material that is wiped from memory after use.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_crypto_zkp_utils::{bytes_to_scalar, scalar_to_bytes};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{CounterSecret, VoterSecret};
+use zeroize::Zeroize;
+
+/// Secret scalar, such as a counter secret share or a voter blinding, which
+/// is zeroized when dropped. Arithmetic on the secret goes through its
+/// methods, so no copy of it outlives the wrapper.
+pub struct SecretScalar(Scalar);
+
+impl SecretScalar {
+    pub fn new(scalar: Scalar) -> Self {
+        SecretScalar(scalar)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WedprError> {
+        Ok(SecretScalar(bytes_to_scalar(bytes)?))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        scalar_to_bytes(&self.0)
+    }
+
+    /// Borrows the secret, e.g. to pass it to a prover.
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Multiplies a point by the secret. The product is public.
+    pub fn mul_point(&self, point: &RistrettoPoint) -> RistrettoPoint {
+        &self.0 * point
+    }
+
+    /// Subtracts another secret from this one in place.
+    pub fn sub_assign(&mut self, other: &SecretScalar) {
+        self.0 -= &other.0;
+    }
+}
+
+impl Zeroize for SecretScalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Overwrites a scalar with zero in a way the compiler cannot elide.
+pub fn zeroize_scalar(scalar: &mut Scalar) {
+    scalar.zeroize();
+}
+
+/// Overwrites a list of scalars, such as vote blindings, with zeros.
+pub fn zeroize_scalars(scalars: &mut [Scalar]) {
+    for scalar in scalars.iter_mut() {
+        scalar.zeroize();
+    }
+}
+
+/// Wipes the secret share held by a counter secret.
+pub fn zeroize_counter_secret(counter_secret: &mut CounterSecret) {
+    counter_secret.mut_poll_secret_share().zeroize();
+}
+
+/// Wipes the secret held by a voter secret.
+pub fn zeroize_voter_secret(voter_secret: &mut VoterSecret) {
+    voter_secret.mut_voter_secret().zeroize();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{counter, voter};
+    use std::mem::ManuallyDrop;
+    use wedpr_l_crypto_zkp_utils::{get_random_scalar, BASEPOINT_G2};
+
+    #[test]
+    fn test_secret_scalar_arithmetic() {
+        let scalar = get_random_scalar();
+        let other_scalar = get_random_scalar();
+        let mut secret_scalar = SecretScalar::new(scalar);
+        assert_eq!(secret_scalar.to_bytes(), scalar_to_bytes(&scalar));
+        assert_eq!(
+            secret_scalar.mul_point(&BASEPOINT_G2),
+            scalar * *BASEPOINT_G2
+        );
+        secret_scalar.sub_assign(&SecretScalar::new(other_scalar));
+        assert_eq!(*secret_scalar.as_scalar(), scalar - other_scalar);
+    }
+
+    #[test]
+    fn test_secret_scalar_wiped_on_drop() {
+        let mut secret_scalar =
+            ManuallyDrop::new(SecretScalar::new(get_random_scalar()));
+        assert_ne!(*secret_scalar.as_scalar(), Scalar::zero());
+        // Runs the destructor in place, so the storage can still be read.
+        unsafe { ManuallyDrop::drop(&mut secret_scalar) };
+        assert_eq!(*secret_scalar.as_scalar(), Scalar::zero());
+    }
+
+    #[test]
+    fn test_secret_zeroization() {
+        let mut secret_scalar = SecretScalar::new(get_random_scalar());
+        secret_scalar.zeroize();
+        assert_eq!(*secret_scalar.as_scalar(), Scalar::zero());
+
+        let mut blinding_list = vec![get_random_scalar(), get_random_scalar()];
+        zeroize_scalars(&mut blinding_list);
+        assert!(blinding_list.iter().all(|scalar| *scalar == Scalar::zero()));
+
+        let mut counter_secret = counter::make_counter_secret();
+        zeroize_counter_secret(&mut counter_secret);
+        assert!(counter_secret
+            .get_poll_secret_share()
+            .iter()
+            .all(|byte| *byte == 0));
+        let mut voter_secret = voter::make_voter_secret();
+        zeroize_voter_secret(&mut voter_secret);
+        assert!(voter_secret
+            .get_voter_secret()
+            .iter()
+            .all(|byte| *byte == 0));
+    }
+}
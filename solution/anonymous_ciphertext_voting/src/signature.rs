@@ -0,0 +1,168 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of ed25519 signatures for ballots signed with keys from existing
+//! identity systems.
+
+use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
+use rand::{rngs::OsRng, RngCore};
+use std::convert::TryFrom;
+use wedpr_l_utils::{error::WedprError, traits::Signature};
+
+/// Implements ed25519 (RFC 8032) as a Signature instance. Secret keys are
+/// 32-byte seeds and public keys are 32-byte compressed points.
+#[derive(Default, Debug, Clone)]
+pub struct WedprEd25519 {}
+
+impl Signature for WedprEd25519 {
+    fn sign<T: ?Sized + AsRef<[u8]>>(
+        &self,
+        private_key: &T,
+        msg_hash: &T,
+    ) -> Result<Vec<u8>, WedprError> {
+        let secret_key = match SecretKey::from_bytes(private_key.as_ref()) {
+            Ok(v) => v,
+            Err(_) => {
+                wedpr_println!("ed25519 parsing secret key failed");
+                return Err(WedprError::FormatError);
+            },
+        };
+        let public_key = PublicKey::from(&secret_key);
+        let signature = ExpandedSecretKey::from(&secret_key)
+            .sign(msg_hash.as_ref(), &public_key);
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    fn verify<T: ?Sized + AsRef<[u8]>>(
+        &self,
+        public_key: &T,
+        msg_hash: &T,
+        signature: &T,
+    ) -> bool {
+        let public_key = match PublicKey::from_bytes(public_key.as_ref()) {
+            Ok(v) => v,
+            Err(_) => {
+                wedpr_println!("ed25519 parsing public key failed");
+                return false;
+            },
+        };
+        let signature =
+            match ed25519_dalek::Signature::try_from(signature.as_ref()) {
+                Ok(v) => v,
+                Err(_) => {
+                    wedpr_println!("ed25519 parsing signature failed");
+                    return false;
+                },
+            };
+        public_key
+            .verify_strict(msg_hash.as_ref(), &signature)
+            .is_ok()
+    }
+
+    fn generate_keypair(&self) -> (Vec<u8>, Vec<u8>) {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        // Any 32 bytes form a valid ed25519 secret key.
+        let secret_key = SecretKey::from_bytes(&seed)
+            .expect("32-byte seed should be a valid secret key");
+        let public_key = PublicKey::from(&secret_key);
+        (public_key.to_bytes().to_vec(), seed.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, parameters, suite,
+        test_fixtures::{make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use std::convert::TryFrom;
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::HASH;
+
+    #[test]
+    fn test_ed25519_signature() {
+        use crate::config::SIGNATURE_ED25519;
+        use wedpr_l_common_coder_hex::WedprHex;
+        use wedpr_l_utils::traits::Coder;
+
+        // Known-answer tests 1 and 2 from RFC 8032, section 7.1.
+        let hex = WedprHex::default();
+        let test_vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+        ];
+        for (secret_key, public_key, message, expected_signature) in
+            test_vectors
+        {
+            let secret_key = hex.decode(secret_key).unwrap();
+            let public_key = hex.decode(public_key).unwrap();
+            let message = hex.decode(message).unwrap();
+            let expected_signature = hex.decode(expected_signature).unwrap();
+            let signature =
+                SIGNATURE_ED25519.sign(&secret_key, &message).unwrap();
+            assert_eq!(signature, expected_signature);
+            assert!(SIGNATURE_ED25519.verify(
+                &public_key,
+                &message,
+                &signature
+            ));
+            assert!(!SIGNATURE_ED25519.verify(
+                &public_key,
+                &"tampered".as_bytes().to_vec(),
+                &signature
+            ));
+        }
+
+        // Ed25519 drops into the crypto suite for a full vote.
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        let suite = suite::VoteCryptoSuite::new(&*HASH, &*SIGNATURE_ED25519);
+        let (public_key, secret_key) = suite.signature.generate_keypair();
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response = coordinator::certify_voter_with_suite(
+            &suite,
+            &secret_key,
+            &registration_request,
+            20,
+        )
+        .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2, 3], &candidate_list);
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&suite)
+        )
+        .is_ok());
+    }
+}
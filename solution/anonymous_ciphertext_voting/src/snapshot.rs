@@ -0,0 +1,261 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of compact snapshots of vote sums, for checkpointing aggregation
+//! more cheaply than with protobuf.
+//!
+//! A snapshot is a version byte followed by the compressed points of the
+//! blank ballot and of the ballot of each candidate, in the order of the poll
+//! candidates, and then a big-endian u32 count of unlisted ballots followed
+//! by the points of each unlisted cipher and its ballot. Candidate names are
+//! not stored, so a snapshot is decoded with the parameters of its poll.
+
+use crate::config::{RISTRETTO_POINT_SIZE_IN_BYTES, VOTE_SUM_SNAPSHOT_VERSION};
+use std::convert::TryInto;
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    Ballot, CandidateBallot, CipherPoints, CipherPointsToBallotPair,
+    PollParametersStorage, VoteStorage,
+};
+
+const BALLOT_SIZE_IN_BYTES: usize = 2 * RISTRETTO_POINT_SIZE_IN_BYTES;
+
+/// Encodes a vote sum of the poll into a snapshot. Only the fields of a vote
+/// sum can be encoded, so vote parts with a signature, a rest ballot or a
+/// zero ballot, and sums whose ballots are not in the order of the poll
+/// candidates, are rejected rather than encoded lossily.
+pub fn encode_vote_sum_snapshot(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+) -> Result<Vec<u8>, WedprError> {
+    if !vote_sum.has_blank_ballot()
+        || !vote_sum.get_signature().is_empty()
+        || vote_sum.has_rest_ballot()
+        || vote_sum.has_zero_ballot()
+    {
+        wedpr_println!("only vote sums can be snapshotted");
+        return Err(WedprError::ArgumentError);
+    }
+    let candidates = poll_parameters.get_candidates().get_candidate();
+    let voted_ballots = vote_sum.get_voted_ballot();
+    if voted_ballots.len() != candidates.len()
+        || voted_ballots
+            .iter()
+            .zip(candidates)
+            .any(|(pair, candidate)| pair.get_candidate() != candidate)
+    {
+        wedpr_println!("vote sum does not follow the poll candidates");
+        return Err(WedprError::ArgumentError);
+    }
+    let unlisted_ballots = vote_sum.get_voted_ballot_unlisted();
+    let unlisted_count: u32 = unlisted_ballots
+        .len()
+        .try_into()
+        .map_err(|_| WedprError::ArgumentError)?;
+
+    let mut snapshot = Vec::with_capacity(
+        1 + BALLOT_SIZE_IN_BYTES * (1 + candidates.len())
+            + 4
+            + 2 * BALLOT_SIZE_IN_BYTES * unlisted_ballots.len(),
+    );
+    snapshot.push(VOTE_SUM_SNAPSHOT_VERSION);
+    write_points(
+        &mut snapshot,
+        vote_sum.get_blank_ballot().get_ciphertext1(),
+        vote_sum.get_blank_ballot().get_ciphertext2(),
+    )?;
+    for pair in voted_ballots {
+        write_points(
+            &mut snapshot,
+            pair.get_ballot().get_ciphertext1(),
+            pair.get_ballot().get_ciphertext2(),
+        )?;
+    }
+    snapshot.extend_from_slice(&unlisted_count.to_be_bytes());
+    for pair in unlisted_ballots {
+        write_points(
+            &mut snapshot,
+            pair.get_key().get_ciphertext1(),
+            pair.get_key().get_ciphertext2(),
+        )?;
+        write_points(
+            &mut snapshot,
+            pair.get_ballot().get_ciphertext1(),
+            pair.get_ballot().get_ciphertext2(),
+        )?;
+    }
+    Ok(snapshot)
+}
+
+/// Decodes a snapshot made by [`encode_vote_sum_snapshot`] with the
+/// parameters of the same poll.
+pub fn decode_vote_sum_snapshot(
+    poll_parameters: &PollParametersStorage,
+    snapshot: &[u8],
+) -> Result<VoteStorage, WedprError> {
+    let candidates = poll_parameters.get_candidates().get_candidate();
+    let listed_size = 1 + BALLOT_SIZE_IN_BYTES * (1 + candidates.len());
+    if snapshot.len() < listed_size + 4
+        || snapshot[0] != VOTE_SUM_SNAPSHOT_VERSION
+    {
+        wedpr_println!("invalid vote sum snapshot");
+        return Err(WedprError::DecodeError);
+    }
+    let count_bytes = &snapshot[listed_size..listed_size + 4];
+    let unlisted_count =
+        u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    if unlisted_count.checked_mul(2 * BALLOT_SIZE_IN_BYTES)
+        != Some(snapshot.len() - listed_size - 4)
+    {
+        wedpr_println!("invalid vote sum snapshot");
+        return Err(WedprError::DecodeError);
+    }
+
+    let mut ballots = snapshot[1..listed_size].chunks(BALLOT_SIZE_IN_BYTES);
+    let mut vote_sum = VoteStorage::new();
+    vote_sum.set_blank_ballot(read_ballot(ballots.next().unwrap()));
+    for (candidate, ballot) in candidates.iter().zip(ballots) {
+        let mut pair = CandidateBallot::new();
+        pair.set_candidate(candidate.to_string());
+        pair.set_ballot(read_ballot(ballot));
+        vote_sum.mut_voted_ballot().push(pair);
+    }
+    for entry in snapshot[listed_size + 4..].chunks(2 * BALLOT_SIZE_IN_BYTES) {
+        let key_ballot = read_ballot(&entry[..BALLOT_SIZE_IN_BYTES]);
+        let mut key = CipherPoints::new();
+        key.set_ciphertext1(key_ballot.get_ciphertext1().to_vec());
+        key.set_ciphertext2(key_ballot.get_ciphertext2().to_vec());
+        let mut pair = CipherPointsToBallotPair::new();
+        pair.set_key(key);
+        pair.set_ballot(read_ballot(&entry[BALLOT_SIZE_IN_BYTES..]));
+        vote_sum.mut_voted_ballot_unlisted().push(pair);
+    }
+    Ok(vote_sum)
+}
+
+fn write_points(
+    snapshot: &mut Vec<u8>,
+    ciphertext1: &[u8],
+    ciphertext2: &[u8],
+) -> Result<(), WedprError> {
+    if ciphertext1.len() != RISTRETTO_POINT_SIZE_IN_BYTES
+        || ciphertext2.len() != RISTRETTO_POINT_SIZE_IN_BYTES
+    {
+        wedpr_println!("vote sum ciphertexts must be compressed points");
+        return Err(WedprError::ArgumentError);
+    }
+    snapshot.extend_from_slice(ciphertext1);
+    snapshot.extend_from_slice(ciphertext2);
+    Ok(())
+}
+
+fn read_ballot(bytes: &[u8]) -> Ballot {
+    let mut ballot = Ballot::new();
+    ballot.set_ciphertext1(bytes[..RISTRETTO_POINT_SIZE_IN_BYTES].to_vec());
+    ballot.set_ciphertext2(bytes[RISTRETTO_POINT_SIZE_IN_BYTES..].to_vec());
+    ballot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+    };
+    use wedpr_l_crypto_zkp_utils::{
+        get_random_scalar, point_to_bytes, BASEPOINT_G1,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_vote_sum_snapshot() {
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_sum = VoteStorage::new();
+        for choices in [vec![1, 2, 3], vec![0, 4, 1]] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                10,
+                &choices,
+            );
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut vote_sum,
+            )
+            .unwrap();
+        }
+
+        let snapshot =
+            encode_vote_sum_snapshot(&poll_parameters, &vote_sum).unwrap();
+        assert_eq!(
+            decode_vote_sum_snapshot(&poll_parameters, &snapshot).unwrap(),
+            vote_sum
+        );
+        assert!(
+            snapshot.len()
+                < wedpr_s_protos::proto_to_bytes(&vote_sum).unwrap().len()
+        );
+
+        // Unlisted ballots round-trip too.
+        let random_bytes =
+            || point_to_bytes(&(get_random_scalar() * *BASEPOINT_G1));
+        let mut unlisted_sum = vote_sum.clone();
+        for _ in 0..2 {
+            let mut key = CipherPoints::new();
+            key.set_ciphertext1(random_bytes());
+            key.set_ciphertext2(random_bytes());
+            let mut ballot = Ballot::new();
+            ballot.set_ciphertext1(random_bytes());
+            ballot.set_ciphertext2(random_bytes());
+            let mut pair = CipherPointsToBallotPair::new();
+            pair.set_key(key);
+            pair.set_ballot(ballot);
+            unlisted_sum.mut_voted_ballot_unlisted().push(pair);
+        }
+        let unlisted_snapshot =
+            encode_vote_sum_snapshot(&poll_parameters, &unlisted_sum).unwrap();
+        assert_eq!(
+            decode_vote_sum_snapshot(&poll_parameters, &unlisted_snapshot)
+                .unwrap(),
+            unlisted_sum
+        );
+
+        // Storages that cannot be encoded losslessly are rejected.
+        let mut reordered_sum = vote_sum.clone();
+        reordered_sum.mut_voted_ballot().swap(0, 1);
+        assert!(matches!(
+            encode_vote_sum_snapshot(&poll_parameters, &reordered_sum),
+            Err(WedprError::ArgumentError)
+        ));
+        let mut signed_sum = vote_sum.clone();
+        signed_sum.set_signature(vec![1; 64]);
+        assert!(matches!(
+            encode_vote_sum_snapshot(&poll_parameters, &signed_sum),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // Truncated or extended snapshots are rejected.
+        let mut extended_snapshot = snapshot.clone();
+        extended_snapshot.push(0);
+        for invalid_snapshot in [
+            &snapshot[..snapshot.len() - 1],
+            &extended_snapshot[..],
+            &unlisted_snapshot[..unlisted_snapshot.len() - 64],
+        ] {
+            assert!(matches!(
+                decode_vote_sum_snapshot(&poll_parameters, invalid_snapshot),
+                Err(WedprError::DecodeError)
+            ));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of the crypto suite used to sign and verify ballots.
+
+use crate::config::{HASH_SM3, SIGNATURE_SM2};
+use wedpr_l_utils::traits::{Hash, Signature};
+use wedpr_s_protos::{
+    config::{HASH, SIGNATURE},
+    BallotSignatureParams,
+};
+
+/// Hash and signature algorithms used by a poll. Functions taking a suite
+/// behave like their counterparts without the `_with_suite` suffix, which use
+/// the algorithms selected by the crate features.
+pub struct VoteCryptoSuite<'a, H: Hash, S: Signature> {
+    pub hash: &'a H,
+    pub signature: &'a S,
+}
+
+impl<'a, H: Hash, S: Signature> VoteCryptoSuite<'a, H, S> {
+    pub fn new(hash: &'a H, signature: &'a S) -> Self {
+        VoteCryptoSuite { hash, signature }
+    }
+
+    /// Returns the ballot signature parameters of the suite, bound to no
+    /// poll id or epoch.
+    pub fn signature_params(&self) -> BallotSignatureParams<'_, H, S> {
+        BallotSignatureParams::new(self.hash, self.signature)
+    }
+}
+
+/// Returns the suite selected by the crate features.
+pub fn default_suite() -> VoteCryptoSuite<'static, impl Hash, impl Signature> {
+    VoteCryptoSuite::new(&*HASH, &*SIGNATURE)
+}
+
+/// Returns the suite of SM2 signatures and SM3 hashes.
+pub fn sm_suite() -> VoteCryptoSuite<'static, impl Hash, impl Signature> {
+    VoteCryptoSuite::new(&*HASH_SM3, &*SIGNATURE_SM2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, nullifier, parameters,
+        test_fixtures::{make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use std::convert::TryFrom;
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::{HASH, SIGNATURE};
+
+    #[test]
+    fn test_vote_crypto_suite() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        let suite = VoteCryptoSuite::new(&*HASH, &*SIGNATURE);
+        let (public_key, secret_key) = suite.signature.generate_keypair();
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response = coordinator::certify_voter_with_suite(
+            &suite,
+            &secret_key,
+            &registration_request,
+            20,
+        )
+        .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2, 3], &candidate_list);
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&suite)
+        )
+        .is_ok());
+        // The suite matches the feature defaults, so both paths agree.
+        assert!(verifier::verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .unwrap());
+        assert_eq!(
+            nullifier::derive_vote_nullifier_with_suite(
+                &suite,
+                &public_key,
+                &poll_parameters
+            ),
+            nullifier::derive_vote_nullifier(&public_key, &poll_parameters)
+        );
+    }
+
+    #[test]
+    fn test_sm_crypto_suite() {
+        use crate::config::{HASH_SM3, SIGNATURE_SM2};
+        use wedpr_l_common_coder_hex::WedprHex;
+        use wedpr_l_utils::traits::{Coder, Hash};
+
+        // Examples 1 and 2 from GB/T 32905-2016.
+        let hex = WedprHex::default();
+        assert_eq!(
+            HASH_SM3.hash("abc"),
+            hex.decode(
+                "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            HASH_SM3.hash(&"abcd".repeat(16)),
+            hex.decode(
+                "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"
+            )
+            .unwrap()
+        );
+
+        // SM2 signatures are randomized, so check round trips instead.
+        let (public_key, secret_key) = SIGNATURE_SM2.generate_keypair();
+        let (other_public_key, _) = SIGNATURE_SM2.generate_keypair();
+        let message_hash = HASH_SM3.hash("ballot");
+        let signature = SIGNATURE_SM2.sign(&secret_key, &message_hash).unwrap();
+        assert!(SIGNATURE_SM2.verify(&public_key, &message_hash, &signature));
+        assert!(!SIGNATURE_SM2.verify(
+            &other_public_key,
+            &message_hash,
+            &signature
+        ));
+
+        // Ballot message hashes work unchanged under SM3.
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        let suite = sm_suite();
+        let (public_key, secret_key) = suite.signature.generate_keypair();
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response = coordinator::certify_voter_with_suite(
+            &suite,
+            &secret_key,
+            &registration_request,
+            20,
+        )
+        .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![1, 2, 3], &candidate_list);
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request_with_options(
+            &parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap(),
+            &vote_request,
+            &public_key,
+            verifier::VoteVerifyOptions::new(&suite)
+        )
+        .is_ok());
+    }
+}
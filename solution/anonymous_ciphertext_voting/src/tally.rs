@@ -0,0 +1,143 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for recovering decrypted tallies.
+//!
+//! A decrypted tally is the point v * G1, so recovering v is a discrete log
+//! bounded by the largest possible tally. It is solved with a baby-step
+//! giant-step search, taking about 2 * sqrt(max) point additions instead of
+//! max scalar multiplications.
+//!
+//! The baby steps are capped at MAX_TALLY_BABY_STEPS, so a generous bound
+//! never allocates more than the cap. Beyond the square of the cap, recovery
+//! takes v / MAX_TALLY_BABY_STEPS giant steps, stopping at the tally like a
+//! linear scan would.
+
+use crate::config::MAX_TALLY_BABY_STEPS;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use std::collections::HashMap;
+use wedpr_l_crypto_zkp_utils::BASEPOINT_G1;
+
+/// Baby steps for recovering tallies up to a bound, built once and reused
+/// for all tallies of a poll.
+pub struct TallyTable {
+    max: u64,
+    baby_step_count: u64,
+    giant_step_count: u64,
+    baby_steps: HashMap<[u8; 32], u64>,
+    giant_step: RistrettoPoint,
+}
+
+impl TallyTable {
+    /// Makes the baby steps for tallies in [0, max].
+    pub fn new(max: u64) -> Self {
+        // The tallies [0, max] count max + 1 values, which overflows u64 for
+        // max = u64::MAX.
+        let value_count = max as u128 + 1;
+        let baby_step_count =
+            ceil_sqrt(value_count).min(MAX_TALLY_BABY_STEPS as u128) as u64;
+        let giant_step_count = ((value_count + baby_step_count as u128 - 1)
+            / baby_step_count as u128) as u64;
+        let mut baby_steps = HashMap::with_capacity(baby_step_count as usize);
+        let mut point = RistrettoPoint::default();
+        for j in 0..baby_step_count {
+            baby_steps.insert(point.compress().to_bytes(), j);
+            point += *BASEPOINT_G1;
+        }
+        TallyTable {
+            max,
+            baby_step_count,
+            giant_step_count,
+            baby_steps,
+            giant_step: -(*BASEPOINT_G1 * Scalar::from(baby_step_count)),
+        }
+    }
+
+    /// Recovers the tally v of the point v * G1, or None when v exceeds the
+    /// bound of the table.
+    pub fn recover(&self, point: &RistrettoPoint) -> Option<u64> {
+        let mut gamma = *point;
+        for i in 0..self.giant_step_count {
+            if let Some(j) = self.baby_steps.get(&gamma.compress().to_bytes()) {
+                let value =
+                    i as u128 * self.baby_step_count as u128 + *j as u128;
+                return if value <= self.max as u128 {
+                    Some(value as u64)
+                } else {
+                    None
+                };
+            }
+            gamma += self.giant_step;
+        }
+        None
+    }
+}
+
+// The smallest s with s * s >= n, for n >= 1.
+fn ceil_sqrt(n: u128) -> u128 {
+    // The float estimate is off by a few units at most for n < 2^65.
+    let mut s = (n as f64).sqrt() as u128;
+    while s * s > n {
+        s -= 1;
+    }
+    while s * s < n {
+        s += 1;
+    }
+    s.max(1)
+}
+
+/// Recovers the tally v of the point v * G1, or None when v exceeds max.
+pub fn recover_tally(point: &RistrettoPoint, max: u64) -> Option<u64> {
+    TallyTable::new(max).recover(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wedpr_l_crypto_zkp_utils::get_random_scalar;
+
+    #[test]
+    fn test_recover_tally() {
+        let max = 1000u64;
+        let tally_table = TallyTable::new(max);
+        // Cover both ends and the edges of the giant steps.
+        for value in [0u64, 1, 31, 32, 33, 500, 999, max] {
+            let point = *BASEPOINT_G1 * Scalar::from(value);
+            assert_eq!(tally_table.recover(&point), Some(value));
+            assert_eq!(recover_tally(&point, max), Some(value));
+        }
+        // Tallies beyond the bound are not recovered.
+        for value in [max + 1, max + 100, 1_000_000] {
+            let point = *BASEPOINT_G1 * Scalar::from(value);
+            assert_eq!(tally_table.recover(&point), None);
+        }
+        assert_eq!(
+            recover_tally(&(get_random_scalar() * *BASEPOINT_G1), max),
+            None
+        );
+        assert_eq!(recover_tally(&RistrettoPoint::default(), 0), Some(0));
+    }
+
+    #[test]
+    fn test_recover_tally_large_bound() {
+        // The largest bounds build a capped table and still recover small
+        // tallies, including ones past the first giant step.
+        let tally_table = TallyTable::new(u64::MAX);
+        assert_eq!(tally_table.baby_steps.len() as u64, MAX_TALLY_BABY_STEPS);
+        for value in
+            [0u64, 1, MAX_TALLY_BABY_STEPS, 3 * MAX_TALLY_BABY_STEPS + 7]
+        {
+            let point = *BASEPOINT_G1 * Scalar::from(value);
+            assert_eq!(tally_table.recover(&point), Some(value));
+        }
+        assert_eq!(
+            TallyTable::new(i64::MAX as u64)
+                .recover(&(*BASEPOINT_G1 * Scalar::from(42u64))),
+            Some(42)
+        );
+
+        assert_eq!(ceil_sqrt(1), 1);
+        assert_eq!(ceil_sqrt(1000), 32);
+        assert_eq!(ceil_sqrt(1024), 32);
+        assert_eq!(ceil_sqrt(u64::MAX as u128 + 1), 1 << 32);
+    }
+}
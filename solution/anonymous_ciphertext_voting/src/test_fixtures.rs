@@ -0,0 +1,212 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Shared setup of the unit tests: counters, polls and complete poll runs.
+
+use crate::{
+    coordinator, counter,
+    parameters::ParsedPollParameters,
+    suite::default_suite,
+    verifier::{self, VoteVerifyFailure, VoteVerifyOptions},
+    voter,
+};
+use curve25519_dalek::scalar::Scalar;
+use std::convert::TryFrom;
+use wedpr_l_crypto_zkp_utils::{bytes_to_point, point_to_bytes, BASEPOINT_G1};
+use wedpr_l_utils::traits::Signature;
+use wedpr_s_protos::{
+    config::SIGNATURE,
+    generated::acv::{
+        CandidateBallot, CandidateList, CounterParametersStorage,
+        CounterSecret, DecryptedResultPartStorage, PollParametersStorage,
+        VoteRequest, VoteResultStorage, VoteStorage,
+    },
+};
+
+/// Makes a counter for each id, returning the counter secrets and the
+/// parameters collecting their shares.
+pub fn make_counters(
+    counter_ids: &[&str],
+) -> (Vec<CounterSecret>, CounterParametersStorage) {
+    let mut counter_secret_list = vec![];
+    let mut counter_parameters = CounterParametersStorage::default();
+    for counter_id in counter_ids {
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share(counter_id, &counter_secret)
+                .unwrap(),
+        );
+        counter_secret_list.push(counter_secret);
+    }
+    (counter_secret_list, counter_parameters)
+}
+
+/// Makes a candidate list of the given names, in order.
+pub fn make_candidate_list(candidates: &[&str]) -> CandidateList {
+    let mut candidate_list = CandidateList::new();
+    for candidate in candidates {
+        candidate_list.mut_candidate().push(candidate.to_string());
+    }
+    candidate_list
+}
+
+/// Makes a vote storage over the candidates of the poll whose ciphertexts
+/// are multiples of G1: the blank ballot takes the first two seeds and each
+/// candidate ballot the next two, in candidate order.
+pub fn make_seeded_vote_storage(
+    poll_parameters: &PollParametersStorage,
+    seeds: &[u64],
+) -> VoteStorage {
+    let candidates = poll_parameters.get_candidates().get_candidate();
+    assert_eq!(seeds.len(), 2 + 2 * candidates.len());
+    let point_bytes =
+        |seed: u64| point_to_bytes(&(*BASEPOINT_G1 * Scalar::from(seed)));
+    let mut vote_storage = VoteStorage::new();
+    vote_storage
+        .mut_blank_ballot()
+        .set_ciphertext1(point_bytes(seeds[0]));
+    vote_storage
+        .mut_blank_ballot()
+        .set_ciphertext2(point_bytes(seeds[1]));
+    for (index, candidate) in candidates.iter().enumerate() {
+        let mut pair = CandidateBallot::new();
+        pair.set_candidate(candidate.to_string());
+        pair.mut_ballot()
+            .set_ciphertext1(point_bytes(seeds[2 + 2 * index]));
+        pair.mut_ballot()
+            .set_ciphertext2(point_bytes(seeds[3 + 2 * index]));
+        vote_storage.mut_voted_ballot().push(pair);
+    }
+    vote_storage
+}
+
+/// Registers a new voter of the given weight and casts a vote request for
+/// the choices, one per candidate of the list.
+pub fn cast_vote(
+    secret_key: &[u8],
+    poll_parameters: &PollParametersStorage,
+    candidate_list: &CandidateList,
+    voter_weight: u32,
+    choices: &[u32],
+) -> VoteRequest {
+    let voter_secret = voter::make_voter_secret();
+    let registration_request =
+        voter::make_registration_request(&voter_secret, poll_parameters)
+            .unwrap();
+    let registration_response = coordinator::certify_voter(
+        secret_key,
+        &registration_request,
+        voter_weight,
+    )
+    .unwrap();
+    voter::vote(
+        &voter_secret,
+        &voter::make_vote_choices(&choices.to_vec(), candidate_list),
+        &registration_response,
+        poll_parameters,
+    )
+    .unwrap()
+}
+
+/// Verifies a vote request with the default options, reporting which check
+/// failed.
+pub fn verify_detailed(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<(), VoteVerifyFailure> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    verifier::verify_vote_request_with_options(
+        &parsed_parameters,
+        vote_request,
+        public_key,
+        VoteVerifyOptions::new(&default_suite()),
+    )
+}
+
+/// Poll run to completion like the baseline end-to-end test: three counters,
+/// the candidates Kitten, Doge and Bunny, and four voters.
+pub struct CompletedPoll {
+    pub poll_parameters: PollParametersStorage,
+    pub vote_request_list: Vec<VoteRequest>,
+    pub encrypted_vote_sum: VoteStorage,
+    pub aggregated_decrypted_result: DecryptedResultPartStorage,
+    pub vote_result: VoteResultStorage,
+}
+
+pub fn run_poll() -> CompletedPoll {
+    let counter_id_list = ["1001", "1002", "1003"];
+    let (counter_secret_list, counter_parameters) =
+        make_counters(&counter_id_list);
+    let (_, secret_key) = SIGNATURE.generate_keypair();
+    let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+    let poll_parameters =
+        coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+            .unwrap();
+
+    let voter_choice_list: Vec<(u32, Vec<u32>)> = vec![
+        (10, vec![1, 2, 3]),
+        (20, vec![2, 4, 6]),
+        (50, vec![10, 15, 25]),
+        (60, vec![20, 10, 5]),
+    ];
+    let mut vote_request_list = vec![];
+    let mut encrypted_vote_sum = VoteStorage::new();
+    for (voter_weight, voter_choice) in voter_choice_list {
+        let vote_request = cast_vote(
+            &secret_key,
+            &poll_parameters,
+            &candidate_list,
+            voter_weight,
+            &voter_choice,
+        );
+        assert!(coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut encrypted_vote_sum
+        )
+        .unwrap());
+        vote_request_list.push(vote_request);
+    }
+
+    let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+    for (index, counter_id) in counter_id_list.iter().enumerate() {
+        let partially_decrypted_result = counter::count(
+            counter_id,
+            &counter_secret_list[index],
+            &encrypted_vote_sum,
+        )
+        .unwrap();
+        let counter_share = bytes_to_point(
+            counter_parameters.get_counter_parameters_share()[index]
+                .get_poll_point_share(),
+        )
+        .unwrap();
+        assert!(verifier::verify_count_request(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &counter_share,
+            &partially_decrypted_result
+        )
+        .unwrap());
+        assert!(coordinator::aggregate_decrypted_part_sum(
+            &poll_parameters,
+            &partially_decrypted_result,
+            &mut aggregated_decrypted_result
+        )
+        .unwrap());
+    }
+    let vote_result = coordinator::finalize_vote_result(
+        &poll_parameters,
+        &encrypted_vote_sum,
+        &aggregated_decrypted_result,
+        200,
+    )
+    .unwrap();
+    CompletedPoll {
+        poll_parameters,
+        vote_request_list,
+        encrypted_vote_sum,
+        aggregated_decrypted_result,
+        vote_result,
+    }
+}
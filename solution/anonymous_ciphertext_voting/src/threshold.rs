@@ -0,0 +1,585 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for t-of-n threshold counting.
+//!
+//! The poll secret is split with Shamir secret sharing, so any `threshold`
+//! counters can decrypt the poll result together. Counters are identified by
+//! the share index carried in their secret and copied into their parameters
+//! share by [`crate::counter::make_parameters_share`], so shares may be listed
+//! in any order.
+//!
+//! [`make_threshold_counter_secrets`] is a trusted dealer: whoever runs it
+//! picks the polynomial and so knows the whole poll secret, and can decrypt
+//! every ballot alone. It must run on a party trusted with the poll secret,
+//! which discards the polynomial and hands each secret to its counter over a
+//! private channel. Polls that cannot trust a dealer should use the n-of-n
+//! counters of [`crate::counter`] instead.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use std::{collections::BTreeSet, error::Error, fmt};
+use wedpr_l_crypto_zkp_utils::{
+    bytes_to_point, get_random_scalar, point_to_bytes, scalar_to_bytes,
+};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    CandidateList, CounterParametersShareRequest, CounterParametersStorage,
+    CounterSecret, CountingPart, DecryptedResultPartStorage,
+    PollParametersStorage, StringToCountingPartPair, VoteStorage,
+};
+
+use crate::{
+    utils::{
+        canonical_candidate_list, check_candidate_list,
+        index_counting_parts_by_candidate, lookup_by_candidate,
+    },
+    verifier::verify_count_request,
+};
+
+/// Reason why threshold counting failed.
+#[derive(Debug)]
+pub enum ThresholdError {
+    /// Fewer than `threshold` distinct counters supplied valid parts.
+    InsufficientParts { required: usize, supplied: usize },
+    /// The input is malformed.
+    Malformed(WedprError),
+}
+
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThresholdError::InsufficientParts { required, supplied } => write!(
+                f,
+                "{} valid parts supplied, {} required",
+                supplied, required
+            ),
+            ThresholdError::Malformed(error) => {
+                write!(f, "input is malformed: {:?}", error)
+            },
+        }
+    }
+}
+
+impl Error for ThresholdError {}
+
+impl From<WedprError> for ThresholdError {
+    fn from(error: WedprError) -> Self {
+        ThresholdError::Malformed(error)
+    }
+}
+
+/// Makes secrets for `counter_count` counters, any `threshold` of whom can
+/// decrypt the poll result. The secrets carry the share indexes 1 to
+/// `counter_count`. The caller acts as a trusted dealer, see the module
+/// documentation.
+pub fn make_threshold_counter_secrets(
+    threshold: usize,
+    counter_count: usize,
+) -> Result<Vec<CounterSecret>, WedprError> {
+    if threshold == 0 || threshold > counter_count {
+        return Err(WedprError::ArgumentError);
+    }
+    let coefficients: Vec<Scalar> =
+        (0..threshold).map(|_| get_random_scalar()).collect();
+    let mut counter_secrets = Vec::with_capacity(counter_count);
+    for index in 1..=counter_count {
+        // Evaluate the polynomial at the counter index with Horner's rule.
+        let x = Scalar::from(index as u64);
+        let mut secret_share = Scalar::zero();
+        for coefficient in coefficients.iter().rev() {
+            secret_share = secret_share * x + coefficient;
+        }
+        let mut counter_secret = CounterSecret::new();
+        counter_secret.set_poll_secret_share(scalar_to_bytes(&secret_share));
+        counter_secret.set_share_index(index as u64);
+        counter_secrets.push(counter_secret);
+    }
+    Ok(counter_secrets)
+}
+
+/// Makes system parameters for a new poll counted by threshold counters.
+pub fn make_threshold_poll_parameters(
+    candidate_list: &CandidateList,
+    counter_parameters: &CounterParametersStorage,
+    threshold: usize,
+) -> Result<PollParametersStorage, WedprError> {
+    let shares = checked_shares(counter_parameters, threshold)?;
+    let indexes: Vec<u64> = shares[..threshold]
+        .iter()
+        .map(|share| share.get_share_index())
+        .collect();
+    let mut poll_point = RistrettoPoint::default();
+    for (share, index) in shares.iter().zip(&indexes) {
+        poll_point += bytes_to_point(share.get_poll_point_share())?
+            * lagrange_coefficient(*index, &indexes);
+    }
+
+    check_candidate_list(candidate_list)?;
+    let mut storage = PollParametersStorage::default();
+    storage.set_candidates(canonical_candidate_list(candidate_list));
+    storage.set_poll_point(point_to_bytes(&poll_point));
+    Ok(storage)
+}
+
+/// Combines partially decrypted results from at least `threshold` counters.
+/// Parts failing [`verify_count_request`] and repeated counters are skipped.
+pub fn aggregate_threshold_decrypted_parts(
+    poll_parameters: &PollParametersStorage,
+    counter_parameters: &CounterParametersStorage,
+    encrypted_vote_sum: &VoteStorage,
+    threshold: usize,
+    partially_decrypted_results: &[DecryptedResultPartStorage],
+) -> Result<DecryptedResultPartStorage, ThresholdError> {
+    let selected_parts = select_valid_parts(
+        poll_parameters,
+        counter_parameters,
+        encrypted_vote_sum,
+        threshold,
+        partially_decrypted_results,
+    )?;
+    Ok(combine_parts(poll_parameters, &selected_parts)?)
+}
+
+/// Verifies whether a threshold aggregated result is the Lagrange combination
+/// of valid parts from at least `threshold` counters, and whether the
+/// committed shares of those counters reconstruct the poll point.
+pub fn verify_threshold_count_request(
+    poll_parameters: &PollParametersStorage,
+    counter_parameters: &CounterParametersStorage,
+    encrypted_vote_sum: &VoteStorage,
+    threshold: usize,
+    partially_decrypted_results: &[DecryptedResultPartStorage],
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+) -> Result<bool, ThresholdError> {
+    let selected_parts = select_valid_parts(
+        poll_parameters,
+        counter_parameters,
+        encrypted_vote_sum,
+        threshold,
+        partially_decrypted_results,
+    )?;
+
+    let indexes: Vec<u64> =
+        selected_parts.iter().map(|part| part.index).collect();
+    let mut reconstructed_poll_point = RistrettoPoint::default();
+    for part in &selected_parts {
+        reconstructed_poll_point +=
+            part.poll_point_share * lagrange_coefficient(part.index, &indexes);
+    }
+    if point_to_bytes(&reconstructed_poll_point)
+        != poll_parameters.get_poll_point()
+    {
+        wedpr_println!("threshold shares do not match the poll point!");
+        return Ok(false);
+    }
+
+    let expected = combine_parts(poll_parameters, &selected_parts)?;
+    if expected.get_blank_part().get_blinding_c2()
+        != aggregated_decrypted_result
+            .get_blank_part()
+            .get_blinding_c2()
+    {
+        return Ok(false);
+    }
+    let counting_part_index =
+        index_counting_parts_by_candidate(aggregated_decrypted_result);
+    for pair in expected.get_candidate_part() {
+        let counting_part =
+            lookup_by_candidate(&counting_part_index, pair.get_key())?;
+        if pair.get_value().get_blinding_c2() != counting_part.get_blinding_c2()
+        {
+            wedpr_println!("verify candidate {} failed!", pair.get_key());
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Valid part of a counter, with the share index and poll point share of the
+// counter.
+struct SelectedPart<'a> {
+    index: u64,
+    poll_point_share: RistrettoPoint,
+    part: &'a DecryptedResultPartStorage,
+}
+
+// Returns the counter shares, checking that there are at least `threshold`
+// of them and that their share indexes are set and distinct.
+fn checked_shares(
+    counter_parameters: &CounterParametersStorage,
+    threshold: usize,
+) -> Result<&[CounterParametersShareRequest], WedprError> {
+    let shares = counter_parameters.get_counter_parameters_share();
+    if threshold == 0 || threshold > shares.len() {
+        return Err(WedprError::ArgumentError);
+    }
+    let mut indexes = BTreeSet::new();
+    for share in shares {
+        if share.get_share_index() == 0
+            || !indexes.insert(share.get_share_index())
+        {
+            wedpr_println!(
+                "counter {} has a missing or repeated share index",
+                share.get_counter_id()
+            );
+            return Err(WedprError::ArgumentError);
+        }
+    }
+    Ok(shares)
+}
+
+// Picks the first `threshold` valid parts from distinct counters.
+fn select_valid_parts<'a>(
+    poll_parameters: &PollParametersStorage,
+    counter_parameters: &CounterParametersStorage,
+    encrypted_vote_sum: &VoteStorage,
+    threshold: usize,
+    partially_decrypted_results: &'a [DecryptedResultPartStorage],
+) -> Result<Vec<SelectedPart<'a>>, ThresholdError> {
+    let shares = checked_shares(counter_parameters, threshold)?;
+    let mut used_indexes = BTreeSet::new();
+    let mut selected_parts = Vec::with_capacity(threshold);
+    for part in partially_decrypted_results {
+        let counter_id = part.get_blank_part().get_counter_id();
+        let share = match shares
+            .iter()
+            .find(|share| share.get_counter_id() == counter_id)
+        {
+            Some(share) => share,
+            None => continue,
+        };
+        let index = share.get_share_index();
+        if used_indexes.contains(&index) {
+            continue;
+        }
+        let poll_point_share = bytes_to_point(share.get_poll_point_share())?;
+        match verify_count_request(
+            poll_parameters,
+            encrypted_vote_sum,
+            &poll_point_share,
+            part,
+        ) {
+            Ok(true) => {},
+            _ => {
+                wedpr_println!("skip invalid part from counter {}", counter_id);
+                continue;
+            },
+        }
+        used_indexes.insert(index);
+        selected_parts.push(SelectedPart {
+            index,
+            poll_point_share,
+            part,
+        });
+        if selected_parts.len() == threshold {
+            return Ok(selected_parts);
+        }
+    }
+    Err(ThresholdError::InsufficientParts {
+        required: threshold,
+        supplied: selected_parts.len(),
+    })
+}
+
+fn combine_parts(
+    poll_parameters: &PollParametersStorage,
+    selected_parts: &[SelectedPart],
+) -> Result<DecryptedResultPartStorage, WedprError> {
+    let indexes: Vec<u64> =
+        selected_parts.iter().map(|part| part.index).collect();
+    let coefficients: Vec<Scalar> = indexes
+        .iter()
+        .map(|index| lagrange_coefficient(*index, &indexes))
+        .collect();
+
+    let mut blank_c2_r_sum = RistrettoPoint::default();
+    for (selected, coefficient) in selected_parts.iter().zip(&coefficients) {
+        blank_c2_r_sum +=
+            bytes_to_point(selected.part.get_blank_part().get_blinding_c2())?
+                * coefficient;
+    }
+    let mut result = DecryptedResultPartStorage::new();
+    let blank_part = result.mut_blank_part();
+    blank_part.set_counter_id("sum".to_string());
+    blank_part.set_blinding_c2(point_to_bytes(&blank_c2_r_sum));
+
+    let part_indexes: Vec<_> = selected_parts
+        .iter()
+        .map(|selected| index_counting_parts_by_candidate(selected.part))
+        .collect();
+    for candidate in poll_parameters.get_candidates().get_candidate() {
+        let mut candidate_c2_r_sum = RistrettoPoint::default();
+        for (part_index, coefficient) in part_indexes.iter().zip(&coefficients)
+        {
+            let counting_part = lookup_by_candidate(part_index, candidate)?;
+            candidate_c2_r_sum +=
+                bytes_to_point(counting_part.get_blinding_c2())? * coefficient;
+        }
+        let mut candidate_part = CountingPart::new();
+        candidate_part.set_blinding_c2(point_to_bytes(&candidate_c2_r_sum));
+        let mut new_pair = StringToCountingPartPair::new();
+        new_pair.set_key(candidate.to_string());
+        new_pair.set_value(candidate_part);
+        result.mut_candidate_part().push(new_pair);
+    }
+    Ok(result)
+}
+
+// Computes the Lagrange coefficient of `index` for interpolating at zero over
+// the distinct `indexes`.
+fn lagrange_coefficient(index: u64, indexes: &[u64]) -> Scalar {
+    let mut numerator = Scalar::one();
+    let mut denominator = Scalar::one();
+    for other in indexes {
+        if *other == index {
+            continue;
+        }
+        numerator *= Scalar::from(*other);
+        denominator *= Scalar::from(*other) - Scalar::from(index);
+    }
+    numerator * denominator.invert()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, counter,
+        test_fixtures::{make_candidate_list, make_counters},
+        verifier, voter,
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_threshold_counting() {
+        let counter_id_list = ["1001", "1002", "1003"];
+        let threshold = 2;
+        let counter_secret_list =
+            make_threshold_counter_secrets(threshold, counter_id_list.len())
+                .unwrap();
+        // The shares are listed out of order, and matched to the secrets by
+        // their share indexes.
+        let mut counter_parameters = CounterParametersStorage::default();
+        for (counter_id, counter_secret) in
+            counter_id_list.iter().zip(&counter_secret_list).rev()
+        {
+            counter_parameters.mut_counter_parameters_share().push(
+                counter::make_parameters_share(counter_id, counter_secret)
+                    .unwrap(),
+            );
+        }
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = make_threshold_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+            threshold,
+        )
+        .unwrap();
+
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for (voter_weight, voter_choice) in
+            [(10, vec![1, 2, 3]), (20, vec![4, 5, 6])]
+        {
+            let voter_secret = voter::make_voter_secret();
+            let registration_request = voter::make_registration_request(
+                &voter_secret,
+                &poll_parameters,
+            )
+            .unwrap();
+            let registration_response = coordinator::certify_voter(
+                &secret_key,
+                &registration_request,
+                voter_weight,
+            )
+            .unwrap();
+            let vote_choices =
+                voter::make_vote_choices(&voter_choice, &candidate_list);
+            let vote_request = voter::vote(
+                &voter_secret,
+                &vote_choices,
+                &registration_response,
+                &poll_parameters,
+            )
+            .unwrap();
+            assert!(verifier::verify_vote_request(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .unwrap());
+            assert!(coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                &vote_request.get_vote(),
+                &mut encrypted_vote_sum
+            )
+            .unwrap());
+        }
+
+        // The second counter is offline.
+        let mut decrypted_parts = vec![];
+        for index in [0, 2] {
+            decrypted_parts.push(
+                counter::count(
+                    counter_id_list[index],
+                    &counter_secret_list[index],
+                    &encrypted_vote_sum,
+                )
+                .unwrap(),
+            );
+        }
+        assert!(matches!(
+            aggregate_threshold_decrypted_parts(
+                &poll_parameters,
+                &counter_parameters,
+                &encrypted_vote_sum,
+                threshold,
+                &decrypted_parts[..1],
+            ),
+            Err(ThresholdError::InsufficientParts {
+                required: 2,
+                supplied: 1
+            })
+        ));
+        assert_eq!(
+            ThresholdError::InsufficientParts {
+                required: 2,
+                supplied: 1
+            }
+            .to_string(),
+            "1 valid parts supplied, 2 required"
+        );
+        // A repeated part does not count twice.
+        assert!(matches!(
+            aggregate_threshold_decrypted_parts(
+                &poll_parameters,
+                &counter_parameters,
+                &encrypted_vote_sum,
+                threshold,
+                &[decrypted_parts[0].clone(), decrypted_parts[0].clone()],
+            ),
+            Err(ThresholdError::InsufficientParts { .. })
+        ));
+
+        let aggregated_decrypted_result = aggregate_threshold_decrypted_parts(
+            &poll_parameters,
+            &counter_parameters,
+            &encrypted_vote_sum,
+            threshold,
+            &decrypted_parts,
+        )
+        .unwrap();
+        assert!(verify_threshold_count_request(
+            &poll_parameters,
+            &counter_parameters,
+            &encrypted_vote_sum,
+            threshold,
+            &decrypted_parts,
+            &aggregated_decrypted_result,
+        )
+        .unwrap());
+
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            100,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+        for pair in vote_result.get_result() {
+            let expected_value = match pair.get_key() {
+                "Kitten" => 5,
+                "Doge" => 7,
+                "Bunny" => 9,
+                _ => 30,
+            };
+            assert_eq!(pair.get_value(), expected_value);
+        }
+    }
+
+    #[test]
+    fn test_share_index_required() {
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        // Counters outside a threshold scheme carry no share index.
+        let (_, counter_parameters) = make_counters(&["1001", "1002"]);
+        assert!(matches!(
+            make_threshold_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+                2
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // Neither can two counters share an index.
+        let counter_secret_list = make_threshold_counter_secrets(2, 2).unwrap();
+        let mut counter_parameters = CounterParametersStorage::default();
+        for counter_id in ["1001", "1002"] {
+            counter_parameters.mut_counter_parameters_share().push(
+                counter::make_parameters_share(
+                    counter_id,
+                    &counter_secret_list[0],
+                )
+                .unwrap(),
+            );
+        }
+        assert!(matches!(
+            make_threshold_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+                2
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+
+    #[test]
+    fn test_threshold_counter_reshare() {
+        let counter_id_list = ["1001", "1002", "1003"];
+        let counter_secret_list =
+            make_threshold_counter_secrets(2, counter_id_list.len()).unwrap();
+        let mut counter_parameters = CounterParametersStorage::default();
+        for (counter_id, counter_secret) in
+            counter_id_list.iter().zip(&counter_secret_list)
+        {
+            counter_parameters.mut_counter_parameters_share().push(
+                counter::make_parameters_share(counter_id, counter_secret)
+                    .unwrap(),
+            );
+        }
+        let new_counter_ids = vec!["2001".to_string(), "2002".to_string()];
+        assert!(matches!(
+            counter::reshare_counter(
+                "1001",
+                &counter_secret_list[0],
+                &new_counter_ids
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // A reshare made without the share index is rejected too, as it would
+        // replace one Lagrange share with two.
+        let mut unindexed_secret = counter_secret_list[0].clone();
+        unindexed_secret.clear_share_index();
+        let (_, reshare_proof) = counter::reshare_counter(
+            "1001",
+            &unindexed_secret,
+            &new_counter_ids,
+        )
+        .unwrap();
+        assert!(matches!(
+            coordinator::apply_counter_reshare(
+                &counter_parameters,
+                &reshare_proof
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+}
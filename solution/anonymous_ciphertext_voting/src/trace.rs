@@ -0,0 +1,58 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of optional tracing around verification stages.
+//!
+//! With the `tracing` feature, each stage runs inside an `acv_verify` span
+//! and reports its elapsed time in microseconds as the `elapsed_us` field.
+//! Without it, the stage closure is called directly.
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// Runs a verification stage of a whole request.
+#[cfg(feature = "tracing")]
+pub fn trace_stage<T>(stage: &'static str, run: impl FnOnce() -> T) -> T {
+    let span = tracing::debug_span!("acv_verify", stage);
+    let _guard = span.enter();
+    let start = Instant::now();
+    let result = run();
+    tracing::debug!(
+        elapsed_us = start.elapsed().as_micros() as u64,
+        "stage finished"
+    );
+    result
+}
+
+/// Runs a verification stage of a single candidate.
+#[cfg(feature = "tracing")]
+pub fn trace_candidate_stage<T>(
+    stage: &'static str,
+    candidate: &str,
+    run: impl FnOnce() -> T,
+) -> T {
+    let span = tracing::debug_span!("acv_verify", stage, candidate);
+    let _guard = span.enter();
+    let start = Instant::now();
+    let result = run();
+    tracing::debug!(
+        elapsed_us = start.elapsed().as_micros() as u64,
+        "stage finished"
+    );
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub fn trace_stage<T>(_stage: &'static str, run: impl FnOnce() -> T) -> T {
+    run()
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub fn trace_candidate_stage<T>(
+    _stage: &'static str,
+    _candidate: &str,
+    run: impl FnOnce() -> T,
+) -> T {
+    run()
+}
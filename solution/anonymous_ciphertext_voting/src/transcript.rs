@@ -0,0 +1,131 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for exporting the public transcript of a finished poll, so that
+//! third-party auditors can re-verify the result offline.
+
+use crate::verifier::verify_vote_result;
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_protos::generated::acv::{
+    DecryptedResultPartStorage, PollParametersStorage, PollTranscript,
+    VoteResultStorage, VoteStorage,
+};
+
+/// Bundles everything [`verify_vote_result`] checks into one transcript,
+/// which can be encoded with the helpers in [`crate::codec`].
+pub fn export_poll_transcript(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+    vote_result: &VoteResultStorage,
+) -> PollTranscript {
+    let mut transcript = PollTranscript::new();
+    transcript.set_poll_parameters(poll_parameters.clone());
+    transcript.set_vote_sum(vote_sum.clone());
+    transcript
+        .set_aggregated_decrypted_result(aggregated_decrypted_result.clone());
+    transcript.set_vote_result(vote_result.clone());
+    transcript
+}
+
+/// Re-runs the public check of the vote result in a transcript.
+pub fn verify_poll_transcript(
+    transcript: &PollTranscript,
+) -> Result<bool, WedprError> {
+    if !transcript.has_poll_parameters()
+        || !transcript.has_vote_sum()
+        || !transcript.has_aggregated_decrypted_result()
+        || !transcript.has_vote_result()
+    {
+        wedpr_println!("incomplete poll transcript");
+        return Err(WedprError::ArgumentError);
+    }
+    verify_vote_result(
+        transcript.get_poll_parameters(),
+        transcript.get_vote_sum(),
+        transcript.get_aggregated_decrypted_result(),
+        transcript.get_vote_result(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codec, coordinator, counter,
+        test_fixtures::{cast_vote, make_candidate_list, make_counters},
+    };
+    use wedpr_l_utils::traits::Signature;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_poll_transcript() {
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&["1001"]);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for choices in [vec![1, 2, 3], vec![4, 0, 1]] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                10,
+                &choices,
+            );
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum,
+            )
+            .unwrap();
+        }
+        let partially_decrypted_result = counter::count(
+            "1001",
+            &counter_secret_list[0],
+            &encrypted_vote_sum,
+        )
+        .unwrap();
+        let aggregated_decrypted_result =
+            coordinator::aggregate_decrypted_parts(&poll_parameters, &[
+                partially_decrypted_result,
+            ])
+            .unwrap();
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            20,
+        )
+        .unwrap();
+
+        // Export, serialize, reload and verify offline.
+        let transcript = export_poll_transcript(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        );
+        let encoded = codec::poll_transcript_to_base64(&transcript).unwrap();
+        let reloaded = codec::poll_transcript_from_base64(&encoded).unwrap();
+        assert_eq!(reloaded, transcript);
+        assert!(verify_poll_transcript(&reloaded).unwrap());
+
+        // A forged tally does not verify.
+        let mut forged = reloaded.clone();
+        let forged_value = forged.get_vote_result().get_result()[1].get_value();
+        forged.mut_vote_result().mut_result()[1].set_value(forged_value + 1);
+        assert!(!verify_poll_transcript(&forged).unwrap());
+        // Nor does a transcript missing a part.
+        let mut incomplete = reloaded;
+        incomplete.clear_vote_sum();
+        assert!(matches!(
+            verify_poll_transcript(&incomplete),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+}
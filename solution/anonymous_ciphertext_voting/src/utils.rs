@@ -2,19 +2,29 @@
 
 //! Library of ACV utility functions.
 
+use crate::{
+    candidate::CandidateId,
+    config::{
+        IDENTITY_POINT, RANGE_PROOF_BITS, RANGE_PROOF_FIXED_ELEMENTS,
+        RISTRETTO_POINT_SIZE_IN_BYTES,
+    },
+};
 use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use std::collections::{HashMap, HashSet};
+use wedpr_l_crypto_zkp_utils::bytes_to_point;
 use wedpr_l_utils::error::WedprError;
 use wedpr_s_protos::generated::acv::{
-    Ballot, CountingPart, DecryptedResultPartStorage, VoteResultStorage,
-    VoteStorage,
+    Ballot, CandidateList, CountingPart, DecryptedResultPartStorage,
+    VoteResultStorage, VoteStorage,
 };
 
 pub fn get_counting_part_by_candidate(
     decrypted_result: &DecryptedResultPartStorage,
     candidate: &str,
 ) -> Result<CountingPart, WedprError> {
+    let candidate_id = CandidateId::new(candidate);
     for pair in decrypted_result.get_candidate_part() {
-        if pair.get_key() == candidate {
+        if CandidateId::new(pair.get_key()) == candidate_id {
             return Ok(pair.get_value().clone());
         }
     }
@@ -25,49 +35,483 @@ pub fn get_ballot_by_candidate(
     vote_storage: &VoteStorage,
     candidate: &str,
 ) -> Result<Ballot, WedprError> {
+    let candidate_id = CandidateId::new(candidate);
     for pair in vote_storage.get_voted_ballot() {
-        if pair.get_candidate() == candidate {
+        if CandidateId::new(pair.get_candidate()) == candidate_id {
             return Ok(pair.get_ballot().clone());
         }
     }
     Err(WedprError::ArgumentError)
 }
 
-pub fn get_int64_by_candidate(
+/// Candidate-keyed index over a repeated protobuf field. When a candidate
+/// appears more than once, the first entry wins, as with the linear lookups
+/// above.
+pub type CandidateIndex<'a, T> = HashMap<CandidateId, &'a T>;
+
+pub fn index_ballots_by_candidate(
+    vote_storage: &VoteStorage,
+) -> CandidateIndex<'_, Ballot> {
+    let mut index =
+        HashMap::with_capacity(vote_storage.get_voted_ballot().len());
+    for pair in vote_storage.get_voted_ballot() {
+        index
+            .entry(CandidateId::new(pair.get_candidate()))
+            .or_insert(pair.get_ballot());
+    }
+    index
+}
+
+pub fn index_counting_parts_by_candidate(
+    decrypted_result: &DecryptedResultPartStorage,
+) -> CandidateIndex<'_, CountingPart> {
+    let mut index =
+        HashMap::with_capacity(decrypted_result.get_candidate_part().len());
+    for pair in decrypted_result.get_candidate_part() {
+        index
+            .entry(CandidateId::new(pair.get_key()))
+            .or_insert(pair.get_value());
+    }
+    index
+}
+
+pub fn index_int64_by_candidate(
     vote_result: &VoteResultStorage,
-    candidate: &str,
-) -> Result<i64, WedprError> {
+) -> HashMap<CandidateId, i64> {
+    let mut index = HashMap::with_capacity(vote_result.get_result().len());
     for pair in vote_result.get_result() {
-        if pair.get_key() == candidate {
-            return Ok(pair.get_value());
+        index
+            .entry(CandidateId::new(pair.get_key()))
+            .or_insert(pair.get_value());
+    }
+    index
+}
+
+pub fn lookup_by_candidate<'a, T>(
+    index: &CandidateIndex<'a, T>,
+    candidate: &str,
+) -> Result<&'a T, WedprError> {
+    match index.get(&CandidateId::new(candidate)) {
+        Some(value) => Ok(*value),
+        None => Err(WedprError::ArgumentError),
+    }
+}
+
+/// Looks up the result of a candidate, rejecting negative values since vote
+/// counts are stored as int64 in protobuf.
+pub fn lookup_result_by_candidate(
+    index: &HashMap<CandidateId, i64>,
+    candidate: &str,
+) -> Result<u64, WedprError> {
+    match index.get(&CandidateId::new(candidate)) {
+        Some(value) => checked_result_value(*value),
+        None => Err(WedprError::ArgumentError),
+    }
+}
+
+pub fn checked_result_value(value: i64) -> Result<u64, WedprError> {
+    if value < 0 {
+        wedpr_println!("negative vote result: {}", value);
+        return Err(WedprError::ArgumentError);
+    }
+    Ok(value as u64)
+}
+
+/// Looks up the result of a candidate as a scalar, rejecting negative values
+/// like [`lookup_result_by_candidate`].
+pub fn lookup_result_scalar_by_candidate(
+    index: &HashMap<CandidateId, i64>,
+    candidate: &str,
+) -> Result<Scalar, WedprError> {
+    match index.get(&CandidateId::new(candidate)) {
+        Some(value) => scalar_from_u64_checked(*value),
+        None => Err(WedprError::ArgumentError),
+    }
+}
+
+/// Converts a non-negative protobuf int64 to a scalar. Casting a negative
+/// value to u64 would wrap it to a huge positive scalar instead.
+pub fn scalar_from_u64_checked(value: i64) -> Result<Scalar, WedprError> {
+    Ok(Scalar::from(checked_result_value(value)?))
+}
+
+/// Checks whether the names are exactly the poll candidates after
+/// normalization, with no name missing, unexpected or repeated.
+pub fn is_candidate_set<'a>(
+    candidates: &[String],
+    names: impl ExactSizeIterator<Item = &'a str>,
+) -> bool {
+    is_indexed_candidate_set(&index_candidates(candidates), names)
+}
+
+/// Indexes the normalized candidates of a poll for repeated
+/// [`is_indexed_candidate_set`] checks.
+pub fn index_candidates(candidates: &[String]) -> HashSet<CandidateId> {
+    candidates
+        .iter()
+        .map(|candidate| CandidateId::new(candidate))
+        .collect()
+}
+
+/// Checks like [`is_candidate_set`] against candidates indexed in advance.
+pub fn is_indexed_candidate_set<'a>(
+    candidate_set: &HashSet<CandidateId>,
+    names: impl ExactSizeIterator<Item = &'a str>,
+) -> bool {
+    if names.len() != candidate_set.len() {
+        return false;
+    }
+    let mut seen_set = HashSet::with_capacity(candidate_set.len());
+    for name in names {
+        let name_id = CandidateId::new(name);
+        if !candidate_set.contains(&name_id) || !seen_set.insert(name_id) {
+            return false;
         }
     }
-    Err(WedprError::ArgumentError)
+    true
+}
+
+/// Returns the first name repeated after normalization, if any.
+pub fn find_duplicate_candidate<'a>(
+    names: impl Iterator<Item = &'a str>,
+) -> Option<CandidateId> {
+    let mut seen_set = HashSet::new();
+    names
+        .map(CandidateId::new)
+        .find(|name_id| !seen_set.insert(name_id.clone()))
 }
 
-pub fn align_commitment_list_if_needed(list: &mut Vec<RistrettoPoint>) {
+/// Checks that no two candidates of a poll are the same after normalization.
+pub fn check_candidate_list(
+    candidate_list: &CandidateList,
+) -> Result<(), WedprError> {
+    // A poll without candidates has nothing to vote for.
+    if candidate_list.get_candidate().is_empty() {
+        wedpr_println!("empty candidate list");
+        return Err(WedprError::ArgumentError);
+    }
+    let mut candidate_set = HashSet::new();
+    for candidate in candidate_list.get_candidate() {
+        if !candidate_set.insert(CandidateId::new(candidate)) {
+            wedpr_println!("duplicate candidate {}", candidate);
+            return Err(WedprError::ArgumentError);
+        }
+    }
+    Ok(())
+}
+
+/// Sorts the candidates of a poll by their normalized names, so that every
+/// implementation lays out ballots and results in the same order.
+pub fn canonical_candidate_list(
+    candidate_list: &CandidateList,
+) -> CandidateList {
+    let mut candidates = candidate_list.get_candidate().to_vec();
+    candidates.sort_by_cached_key(|candidate| CandidateId::new(candidate));
+    let mut canonical_list = candidate_list.clone();
+    canonical_list.set_candidate(candidates.into());
+    canonical_list
+}
+
+/// Checks whether the candidates are in canonical order, strictly ascending
+/// by their normalized names.
+pub fn is_canonical_candidate_order(candidates: &[String]) -> bool {
+    candidates
+        .windows(2)
+        .all(|pair| CandidateId::new(&pair[0]) < CandidateId::new(&pair[1]))
+}
+
+/// Decompresses a list of points, aborting on the first malformed one.
+pub fn bytes_to_points(
+    slices: &[&[u8]],
+) -> Result<Vec<RistrettoPoint>, WedprError> {
+    let mut points = Vec::with_capacity(slices.len());
+    for (index, slice) in slices.iter().enumerate() {
+        match bytes_to_point(slice) {
+            Ok(point) => points.push(point),
+            Err(error) => {
+                wedpr_println!("malformed point at index {}", index);
+                return Err(error);
+            },
+        }
+    }
+    Ok(points)
+}
+
+/// Pads the commitments with identity points up to the next power of two, as
+/// the batch range proof requires, and returns the number of points added.
+pub fn align_commitment_list_if_needed(
+    list: &mut Vec<RistrettoPoint>,
+) -> usize {
     let pending_length = compute_pending_size(list.len());
     for _ in 0..pending_length {
-        list.push(RistrettoPoint::default());
+        list.push(*IDENTITY_POINT);
     }
+    pending_length
 }
 
-pub fn align_u64_list_if_needed(list: &mut Vec<u64>) {
+/// Gets the number of values a serialized batch range proof was generated
+/// for, from its number of inner product rounds. Sizes matching no batch
+/// give None.
+pub fn range_proof_batch_size(range_proof: &[u8]) -> Option<usize> {
+    if range_proof.len() % RISTRETTO_POINT_SIZE_IN_BYTES != 0 {
+        return None;
+    }
+    let element_count = range_proof.len() / RISTRETTO_POINT_SIZE_IN_BYTES;
+    if element_count < RANGE_PROOF_FIXED_ELEMENTS
+        || (element_count - RANGE_PROOF_FIXED_ELEMENTS) % 2 != 0
+    {
+        return None;
+    }
+    let rounds = (element_count - RANGE_PROOF_FIXED_ELEMENTS) / 2;
+    let bits = 1usize.checked_shl(rounds as u32)?;
+    if bits < RANGE_PROOF_BITS {
+        return None;
+    }
+    Some(bits / RANGE_PROOF_BITS)
+}
+
+pub fn align_u64_list_if_needed(list: &mut Vec<u64>) -> usize {
     let pending_length = compute_pending_size(list.len());
     for _ in 0..pending_length {
         list.push(0u64);
     }
+    pending_length
 }
 
-pub fn align_scalar_list_if_needed(list: &mut Vec<Scalar>) {
+pub fn align_scalar_list_if_needed(list: &mut Vec<Scalar>) -> usize {
     let pending_length = compute_pending_size(list.len());
     for _ in 0..pending_length {
         list.push(Scalar::default());
     }
+    pending_length
 }
 
 fn compute_pending_size(length: usize) -> usize {
-    let log_length = (length as f64).log2().ceil() as u32;
-    let aligned_length = 2_i32.pow(log_length) as usize;
-    aligned_length - length
+    // An empty list stays empty rather than being padded to one element.
+    if length == 0 {
+        return 0;
+    }
+    length.next_power_of_two() - length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator,
+        test_fixtures::{cast_vote, make_counters, verify_detailed},
+        verifier,
+    };
+    use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+    use wedpr_l_crypto_zkp_range_proof::{
+        prove_value_range_in_batch, verify_value_range_in_batch,
+    };
+    use wedpr_l_crypto_zkp_utils::{
+        bytes_to_point, get_random_scalar, point_to_bytes, BASEPOINT_G1,
+        BASEPOINT_G2,
+    };
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::{config::SIGNATURE, generated::acv::CandidateList};
+
+    #[test]
+    fn test_range_proof_batch_size_of_real_proofs() {
+        // The layout read by range_proof_batch_size is not exported by the
+        // range proof crate, so check it against proofs the crate makes.
+        for batch_size in [1usize, 2, 4, 8, 16, 32, 64] {
+            let values: Vec<u64> = (0..batch_size as u64).collect();
+            let blindings: Vec<Scalar> =
+                (0..batch_size).map(|_| get_random_scalar()).collect();
+            let (range_proof, commitments) =
+                prove_value_range_in_batch(&values, &blindings, &BASEPOINT_G2)
+                    .unwrap();
+            assert!(verify_value_range_in_batch(
+                &commitments,
+                &range_proof,
+                &BASEPOINT_G2
+            ));
+            assert_eq!(range_proof_batch_size(&range_proof), Some(batch_size));
+            // Cutting off one round makes a proof for half the batch, and
+            // cutting off a single point matches no batch.
+            let point_size = RISTRETTO_POINT_SIZE_IN_BYTES;
+            if batch_size > 1 {
+                assert_eq!(
+                    range_proof_batch_size(
+                        &range_proof[..range_proof.len() - 2 * point_size]
+                    ),
+                    Some(batch_size / 2)
+                );
+            }
+            assert_eq!(
+                range_proof_batch_size(
+                    &range_proof[..range_proof.len() - point_size]
+                ),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_align_commitment_list() {
+        for (length, expected_padding) in
+            [(0, 0), (1, 0), (2, 0), (3, 1), (5, 3), (8, 0)]
+        {
+            let mut commitments = vec![RistrettoPoint::default(); length];
+            assert_eq!(
+                align_commitment_list_if_needed(&mut commitments),
+                expected_padding
+            );
+            assert_eq!(commitments.len(), length + expected_padding);
+        }
+
+        // Padding is the group identity, which commits to 0 with blinding 0.
+        use crate::config::ZERO_BALLOT;
+        use curve25519_dalek::traits::Identity;
+        let mut commitments = vec![*BASEPOINT_G1; 3];
+        align_commitment_list_if_needed(&mut commitments);
+        assert_eq!(commitments[3], RistrettoPoint::identity());
+        assert_eq!(*IDENTITY_POINT, RistrettoPoint::identity());
+        assert_eq!(*IDENTITY_POINT + *BASEPOINT_G1, *BASEPOINT_G1);
+        assert_eq!(*BASEPOINT_G1 * Scalar::zero(), *IDENTITY_POINT);
+        for ciphertext in
+            [ZERO_BALLOT.get_ciphertext1(), ZERO_BALLOT.get_ciphertext2()]
+        {
+            assert_eq!(bytes_to_point(ciphertext).unwrap(), *IDENTITY_POINT);
+        }
+    }
+
+    #[test]
+    fn test_bytes_to_points() {
+        let points: Vec<RistrettoPoint> = (0..3)
+            .map(|_| get_random_scalar() * *BASEPOINT_G1)
+            .collect();
+        let bytes_list: Vec<Vec<u8>> =
+            points.iter().map(point_to_bytes).collect();
+        let mut slices: Vec<&[u8]> =
+            bytes_list.iter().map(|bytes| bytes.as_slice()).collect();
+        assert_eq!(bytes_to_points(&slices).unwrap(), points);
+        assert!(bytes_to_points(&[]).unwrap().is_empty());
+
+        // A malformed point aborts the whole list.
+        let malformed = [0xffu8; 32];
+        slices[1] = &malformed;
+        assert!(bytes_to_points(&slices).is_err());
+    }
+
+    #[test]
+    fn test_scalar_from_u64_checked() {
+        for value in [0i64, 1, 42, i64::MAX] {
+            assert_eq!(
+                scalar_from_u64_checked(value).unwrap(),
+                Scalar::from(value as u64)
+            );
+        }
+        for value in [-1i64, i64::MIN] {
+            assert!(matches!(
+                scalar_from_u64_checked(value),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_range_proof_batch_size() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let mut vote_requests = vec![];
+        let mut poll_parameters_list = vec![];
+        // Together with the rest ballot, these prove 4 and 8 commitments.
+        for candidate_count in [3, 7] {
+            let mut candidate_list = CandidateList::new();
+            for index in 0..candidate_count {
+                candidate_list
+                    .mut_candidate()
+                    .push(format!("candidate_{}", index));
+            }
+            let poll_parameters = coordinator::make_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+            )
+            .unwrap();
+            vote_requests.push(cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                candidate_count,
+                &vec![1; candidate_count as usize],
+            ));
+            poll_parameters_list.push(poll_parameters);
+        }
+
+        // A proof generated for another count is reported with both counts.
+        let mut mismatched_request = vote_requests[0].clone();
+        mismatched_request
+            .set_range_proof(vote_requests[1].get_range_proof().to_vec());
+        assert!(matches!(
+            verify_detailed(
+                &poll_parameters_list[0],
+                &mismatched_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::RangeProofBatchSize(4, 8))
+        ));
+        assert!(matches!(
+            verifier::verify_vote_request(
+                &poll_parameters_list[0],
+                &mismatched_request,
+                &public_key
+            ),
+            Err(WedprError::FormatError)
+        ));
+
+        // A proof of a size matching no batch is malformed.
+        let mut truncated_request = vote_requests[0].clone();
+        truncated_request.mut_range_proof().truncate(32 * 9);
+        assert!(matches!(
+            verify_detailed(
+                &poll_parameters_list[0],
+                &truncated_request,
+                &public_key
+            ),
+            Err(verifier::VoteVerifyFailure::Malformed(
+                WedprError::FormatError
+            ))
+        ));
+        assert_eq!(range_proof_batch_size(&[0u8; 33]), None);
+    }
+
+    #[test]
+    fn test_range_proof_size() {
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let mut range_proof_size_list = vec![];
+        let mut range_proof_batch_list = vec![];
+        // Together with the rest ballot, these prove 4 and 32 commitments.
+        for candidate_count in [3, 31] {
+            let mut candidate_list = CandidateList::new();
+            for index in 0..candidate_count {
+                candidate_list
+                    .mut_candidate()
+                    .push(format!("candidate_{}", index));
+            }
+            let poll_parameters = coordinator::make_poll_parameters(
+                &candidate_list,
+                &counter_parameters,
+            )
+            .unwrap();
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                candidate_count,
+                &vec![1; candidate_count as usize],
+            );
+            range_proof_size_list.push(vote_request.get_range_proof().len());
+            range_proof_batch_list
+                .push(range_proof_batch_size(vote_request.get_range_proof()));
+        }
+        assert_eq!(range_proof_batch_list, vec![Some(4), Some(32)]);
+        // The batch range proof is aggregated, so 8 times more commitments
+        // only add a few logarithmic rounds instead of 8 times the size.
+        assert!(range_proof_size_list[1] < 2 * range_proof_size_list[0]);
+    }
 }
@@ -3,6 +3,13 @@
 //! Library for a poll verifier.
 
 use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use rand::RngCore;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+};
+use subtle::{Choice, ConstantTimeEq};
 use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
     verify_either_equality_relationship_proof,
     verify_equality_relationship_proof, verify_format_proof,
@@ -10,95 +17,838 @@ use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
 };
 use wedpr_l_crypto_zkp_range_proof::verify_value_range_in_batch;
 use wedpr_l_crypto_zkp_utils::{
-    bytes_to_point, Deserialize, BASEPOINT_G1, BASEPOINT_G2,
+    bytes_to_point, point_to_bytes, Deserialize, BASEPOINT_G1, BASEPOINT_G2,
+};
+use wedpr_l_utils::{
+    error::WedprError,
+    traits::{Hash, Signature},
 };
-use wedpr_l_utils::error::WedprError;
 use wedpr_s_protos::{
-    generated::acv::BallotProof, verify_ballot_signature,
-    verify_ballots_signature,
+    generated::acv::BallotProof, verify_ballot_signature_with_params,
+    verify_ballots_signature_with_params,
 };
 
 use wedpr_s_protos::generated::acv::{
     Ballot, CipherPointsToBallotPair, CipherPointsToBallotProofPair,
-    DecryptedResultPartStorage, PollParametersStorage, StringToBallotProofPair,
+    CounterParametersStorage, CounterReshareProof, DecryptedResultPartStorage,
+    PollParametersStorage, StringToBallotProofPair,
     UnlistedBallotDecryptedResult, VoteRequest, VoteResultStorage, VoteStorage,
 };
 
 use crate::{
+    blind_token::{is_valid_token_nonce, verify_token},
+    candidate::CandidateId,
+    common::{
+        ballot_points, verify_ballot_format, verify_candidate_format_proof,
+        verify_candidate_format_proofs,
+        verify_candidate_format_proofs_shuffled, verify_candidate_set,
+        verify_input_limits, verify_poll_id, verify_range_proof_batch_size,
+        verify_vote_signature, vote_verify_result,
+    },
     config::POLL_RESULT_KEY_TOTAL_BALLOTS,
+    coordinator::{finalize_vote_result, VoterWeightAssignment},
+    counter::reshare_binding_base,
+    generators::GeneratorSet,
+    nullifier::{derive_vote_request_nullifier, NullifierSet},
+    parameters::ParsedPollParameters,
+    result::{decode_vote_result, VoteResult},
+    suite::{default_suite, VoteCryptoSuite},
+    trace::trace_stage,
     utils::{
-        align_commitment_list_if_needed, get_ballot_by_candidate,
-        get_counting_part_by_candidate, get_int64_by_candidate,
+        align_commitment_list_if_needed, bytes_to_points,
+        get_ballot_by_candidate, get_counting_part_by_candidate,
+        index_ballots_by_candidate, index_counting_parts_by_candidate,
+        index_int64_by_candidate, lookup_by_candidate,
+        lookup_result_by_candidate, lookup_result_scalar_by_candidate,
     },
 };
+/// Reason why a vote request or a count request failed verification.
+#[derive(Debug)]
+pub enum VoteVerifyFailure {
+    /// The coordinator signature on the blank ballot is invalid.
+    Signature,
+    /// The range proof over the voted and rest ballots is invalid.
+    RangeProof,
+    /// The range proof was generated for another number of values than the
+    /// padded ballots, given as (expected, proved).
+    RangeProofBatchSize(usize, usize),
+    /// The format proof for the ballot of the given candidate is invalid.
+    FormatProof(String),
+    /// The voted ballots and the rest ballot do not sum up to the blank
+    /// ballot.
+    SumBalance,
+    /// The equality proof for the counting part of the given candidate is
+    /// invalid.
+    Equality(String),
+    /// The blank ballot does not commit to the claimed voter weight.
+    Weight,
+    /// The candidates in the request are not exactly the poll candidates.
+    CandidateMismatch,
+    /// The request was cast in another poll than the verified one.
+    PollMismatch,
+    /// The given candidate appears more than once in the voted ballots or
+    /// the ballot proofs of the request.
+    DuplicateCandidate(String),
+    /// A capped candidate may have received more than its cap.
+    CandidateCap,
+    /// The yes/no ballots may not select exactly the required number of
+    /// candidates.
+    SelectionCount,
+    /// The counter share is not among the authorized counter shares of the
+    /// poll.
+    UnauthorizedCounter,
+    /// The request has more ballots or proof bytes than the verify limits
+    /// allow, and was rejected before any proof was checked.
+    InputTooLarge,
+    /// The request is malformed and could not be verified at all.
+    Malformed(WedprError),
+}
+
+impl fmt::Display for VoteVerifyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoteVerifyFailure::Signature => {
+                write!(f, "blank ballot signature is invalid")
+            },
+            VoteVerifyFailure::RangeProof => {
+                write!(f, "range proof is invalid")
+            },
+            VoteVerifyFailure::RangeProofBatchSize(expected, proved) => write!(
+                f,
+                "range proof is for {} values instead of {}",
+                proved, expected
+            ),
+            VoteVerifyFailure::FormatProof(candidate) => {
+                write!(f, "format proof is invalid for candidate {}", candidate)
+            },
+            VoteVerifyFailure::SumBalance => {
+                write!(f, "voted and rest ballots do not sum to blank ballot")
+            },
+            VoteVerifyFailure::Equality(candidate) => write!(
+                f,
+                "equality proof is invalid for candidate {}",
+                candidate
+            ),
+            VoteVerifyFailure::Weight => {
+                write!(f, "blank ballot does not commit to voter weight")
+            },
+            VoteVerifyFailure::CandidateMismatch => {
+                write!(f, "candidates do not match the poll candidates")
+            },
+            VoteVerifyFailure::PollMismatch => {
+                write!(f, "request was cast in another poll")
+            },
+            VoteVerifyFailure::DuplicateCandidate(candidate) => {
+                write!(f, "candidate {} appears more than once", candidate)
+            },
+            VoteVerifyFailure::CandidateCap => {
+                write!(f, "candidate cap range proof is invalid")
+            },
+            VoteVerifyFailure::SelectionCount => {
+                write!(f, "selection count proof is invalid")
+            },
+            VoteVerifyFailure::UnauthorizedCounter => {
+                write!(f, "counter share is not authorized for the poll")
+            },
+            VoteVerifyFailure::InputTooLarge => {
+                write!(f, "request exceeds the verify limits")
+            },
+            VoteVerifyFailure::Malformed(error) => {
+                write!(f, "request is malformed: {:?}", error)
+            },
+        }
+    }
+}
+
+impl From<WedprError> for VoteVerifyFailure {
+    fn from(error: WedprError) -> Self {
+        VoteVerifyFailure::Malformed(error)
+    }
+}
+
+/// Outcome of every check on a vote request, for audits that need all
+/// failures of a ballot rather than the first one.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VoteVerifyReport {
+    /// Whether the coordinator signature on the blank ballot is valid.
+    pub signature_valid: bool,
+    /// Whether the blank ballot commits to the claimed voter weight, or no
+    /// weight was claimed.
+    pub weight_valid: bool,
+    /// Whether the range proof over the voted and rest ballots is valid.
+    pub range_proof_valid: bool,
+    /// Whether no capped candidate may have received more than its cap.
+    pub candidate_cap_valid: bool,
+    /// Whether the voted ballots and the rest ballot sum up to the blank
+    /// ballot.
+    pub sum_balance_valid: bool,
+    /// Candidates whose format proof is invalid, in request order.
+    pub failed_candidates: Vec<String>,
+}
+
+impl VoteVerifyReport {
+    /// Whether every check passed, i.e. the strict verifier accepts the
+    /// request.
+    pub fn is_valid(&self) -> bool {
+        self.signature_valid
+            && self.weight_valid
+            && self.range_proof_valid
+            && self.candidate_cap_valid
+            && self.sum_balance_valid
+            && self.failed_candidates.is_empty()
+    }
+}
+
+/// Decompressed ballot points of a vote request, for callers that keep their
+/// own cache of points and want to skip decompressing them again. The points
+/// can only be made from a request, and keep its ballot bytes so that the
+/// verifiers can check they are used with the same request.
+#[derive(Clone, Debug)]
+pub struct VoteRequestPoints {
+    // Ciphertexts of the blank ballot.
+    blank_ballot: (RistrettoPoint, RistrettoPoint),
+    // First ciphertext of the rest ballot.
+    rest_ballot_c1: RistrettoPoint,
+    // Ciphertexts of the voted ballots, in the order of the request.
+    voted_ballots: Vec<(RistrettoPoint, RistrettoPoint)>,
+    // Compressed ciphertexts the points were decompressed from.
+    encoded_ballots: Vec<Vec<u8>>,
+}
+
+impl TryFrom<&VoteRequest> for VoteRequestPoints {
+    type Error = WedprError;
+
+    fn try_from(vote_request: &VoteRequest) -> Result<Self, Self::Error> {
+        let vote = vote_request.get_vote();
+        let blank_ballot = ballot_points(vote.get_blank_ballot())?;
+        let voted_ballots = vote
+            .get_voted_ballot()
+            .iter()
+            .map(|pair| ballot_points(pair.get_ballot()))
+            .collect::<Result<Vec<_>, WedprError>>()?;
+        Ok(VoteRequestPoints {
+            blank_ballot,
+            rest_ballot_c1: bytes_to_point(
+                vote.get_rest_ballot().get_ciphertext1(),
+            )?,
+            voted_ballots,
+            encoded_ballots: encoded_ballots(vote),
+        })
+    }
+}
+
+impl VoteRequestPoints {
+    /// Gets the number of voted ballots.
+    pub fn voted_ballot_count(&self) -> usize {
+        self.voted_ballots.len()
+    }
+
+    // Indexes the voted ballot points by candidate. When a candidate appears
+    // more than once, the first entry wins, as with the other indexes.
+    fn index_by_candidate(
+        &self,
+        vote: &VoteStorage,
+    ) -> HashMap<CandidateId, (RistrettoPoint, RistrettoPoint)> {
+        let mut index = HashMap::with_capacity(self.voted_ballots.len());
+        for (pair, points) in
+            vote.get_voted_ballot().iter().zip(&self.voted_ballots)
+        {
+            index
+                .entry(CandidateId::new(pair.get_candidate()))
+                .or_insert(*points);
+        }
+        index
+    }
+
+    // Builds the commitments proved by the range proof of a vote request:
+    // the voted ballots, then the rest ballot, padded to a power of two. Also
+    // returns the number of voted ballots.
+    fn range_commitments(&self) -> (Vec<RistrettoPoint>, usize) {
+        let mut commitments: Vec<RistrettoPoint> =
+            self.voted_ballots.iter().map(|(c1, _)| *c1).collect();
+        let voted_count = commitments.len();
+        commitments.push(self.rest_ballot_c1);
+        align_commitment_list_if_needed(&mut commitments);
+        (commitments, voted_count)
+    }
+}
+
+/// Options of [`verify_vote_request_with_options`]. The options made by
+/// [`VoteVerifyOptions::new`] run the checks of [`verify_vote_request`] with
+/// the given crypto suite, and the setters adjust them.
+pub struct VoteVerifyOptions<'a, H: Hash, S: Signature> {
+    suite: &'a VoteCryptoSuite<'a, H, S>,
+    check_signature: bool,
+    points: Option<&'a VoteRequestPoints>,
+    shuffle_rng: Option<&'a mut dyn RngCore>,
+}
+
+impl<'a, H: Hash, S: Signature> VoteVerifyOptions<'a, H, S> {
+    pub fn new(suite: &'a VoteCryptoSuite<'a, H, S>) -> Self {
+        VoteVerifyOptions {
+            suite,
+            check_signature: true,
+            points: None,
+            shuffle_rng: None,
+        }
+    }
+
+    /// Sets whether to check the blank ballot signature. Skipping it assumes
+    /// that the request was authenticated elsewhere, e.g. by a gateway that
+    /// already verified the signature against the voter public key. Without
+    /// that, anyone could cast ballots in the name of any voter, so never
+    /// skip it on requests from untrusted sources.
+    pub fn check_signature(mut self, check_signature: bool) -> Self {
+        self.check_signature = check_signature;
+        self
+    }
+
+    /// Uses the ballot points already decompressed by the caller. Points
+    /// made from another request are rejected as malformed.
+    pub fn points(mut self, points: &'a VoteRequestPoints) -> Self {
+        self.points = Some(points);
+        self
+    }
+
+    /// Checks the candidate format proofs in an order shuffled with the rng.
+    /// Where verification of an invalid request stops then does not reveal
+    /// which invalid candidate comes first in the request. When several
+    /// format proofs are invalid, the reported candidate depends on the
+    /// shuffle.
+    pub fn shuffle_rng(mut self, rng: &'a mut dyn RngCore) -> Self {
+        self.shuffle_rng = Some(rng);
+        self
+    }
+}
+
 /// Verifies whether ciphertext ballots from a certified voter are valid.
 pub fn verify_vote_request(
     poll_parameters: &PollParametersStorage,
     vote_request: &VoteRequest,
     public_key: &[u8],
 ) -> Result<bool, WedprError> {
-    let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
-    let signature = vote_request.get_vote().get_signature();
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    vote_verify_result(verify_vote_request_with_options(
+        &parsed_parameters,
+        vote_request,
+        public_key,
+        VoteVerifyOptions::new(&default_suite()),
+    ))
+}
 
-    let blank_ballot = vote_request.get_vote().get_blank_ballot();
-    let verify_result =
-        verify_ballot_signature(public_key, blank_ballot, &signature.to_vec())?;
-    if !verify_result {
-        return Err(WedprError::VerificationError);
-    }
+/// Verifies whether ciphertext ballots from a certified voter are valid with
+/// the given options, reusing poll parameters parsed in advance and
+/// reporting which check failed.
+pub fn verify_vote_request_with_options<H: Hash, S: Signature>(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+    options: VoteVerifyOptions<H, S>,
+) -> Result<(), VoteVerifyFailure> {
+    verify_input_limits(&parsed_parameters.limits, vote_request)?;
+    verify_candidate_set(&parsed_parameters.candidates, vote_request)?;
+    verify_vote_request_points(
+        parsed_parameters,
+        vote_request,
+        public_key,
+        options,
+    )
+}
 
-    let mut commitments: Vec<RistrettoPoint> = Vec::new();
-    let mut voted_ballot_sum = RistrettoPoint::default();
-    for candidate_ballot_pair in vote_request.get_vote().get_voted_ballot() {
-        let ballot = candidate_ballot_pair.get_ballot();
-        commitments.push(bytes_to_point(&ballot.get_ciphertext1())?);
-        voted_ballot_sum += bytes_to_point(&ballot.get_ciphertext1())?;
+/// Verifies whether ciphertext ballots from a certified voter are valid and
+/// the blank ballot commits to the voter weight the coordinator assigned to
+/// that voter. The assignment ties the claimed weight to the voter, and the
+/// weight proof ties it to the value of the blank ballot, which the
+/// coordinator signature alone does not reveal. A request of an unassigned
+/// voter, or claiming or proving another weight, returns `Ok(false)`; errors
+/// are kept for malformed input and other failed checks.
+pub fn verify_weighted_vote_request(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+    weight_assignment: &VoterWeightAssignment,
+) -> Result<bool, WedprError> {
+    let voter_weight =
+        weight_assignment.voter_weight(poll_parameters, vote_request);
+    if vote_request.get_weight_proof().is_empty()
+        || voter_weight != Some(vote_request.get_voter_weight())
+    {
+        wedpr_println!("verify_voter_weight failed!");
+        return Ok(false);
+    }
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    match verify_vote_request_with_options(
+        &parsed_parameters,
+        vote_request,
+        public_key,
+        VoteVerifyOptions::new(&default_suite()),
+    ) {
+        Err(VoteVerifyFailure::Weight) => Ok(false),
+        result => vote_verify_result(result),
     }
+}
 
-    let rest_ballot =
-        vote_request.get_vote().get_rest_ballot().get_ciphertext1();
-    let rest_ballot_point = bytes_to_point(rest_ballot)?;
-    commitments.push(rest_ballot_point);
-    align_commitment_list_if_needed(&mut commitments);
-    let range_proof = vote_request.get_range_proof();
-    if !verify_value_range_in_batch(&commitments, range_proof, &poll_point) {
-        return Err(WedprError::VerificationError);
+/// Verifies ciphertext ballots from many certified voters, returning a
+/// verification result for each request instead of failing the whole batch
+/// on one invalid ballot.
+pub fn verify_vote_request_batch(
+    poll_parameters: &PollParametersStorage,
+    vote_requests: &[VoteRequest],
+    public_keys: &[&[u8]],
+) -> Result<Vec<bool>, WedprError> {
+    if vote_requests.len() != public_keys.len() {
+        return Err(WedprError::ArgumentError);
     }
-    for candidate_ballot in vote_request.get_ballot_proof() {
-        let candidate = candidate_ballot.get_key();
-        let ballot_proof = candidate_ballot.get_value();
-        let candidate_ballot =
-            get_ballot_by_candidate(&vote_request.get_vote(), candidate)?;
+    if vote_requests.is_empty() {
+        return Ok(Vec::new());
+    }
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    Ok(vote_requests
+        .iter()
+        .zip(public_keys.iter())
+        .map(|(vote_request, public_key)| {
+            verify_vote_request_with_options(
+                &parsed_parameters,
+                vote_request,
+                public_key,
+                VoteVerifyOptions::new(&default_suite()),
+            )
+            .is_ok()
+        })
+        .collect())
+}
 
-        let ciphertext1 = bytes_to_point(&candidate_ballot.get_ciphertext1())?;
-        let ciphertext2 = bytes_to_point(&candidate_ballot.get_ciphertext2())?;
-        let format_proof =
-            Deserialize::deserialize(&ballot_proof.get_format_proof())?;
-        if !verify_format_proof(
-            &ciphertext1,
-            &ciphertext2,
-            &format_proof,
-            &*BASEPOINT_G1,
-            &*BASEPOINT_G2,
-            &poll_point,
-        )? {
-            return Err(WedprError::VerificationError);
+/// Verifies ciphertext ballots from many certified voters like
+/// [`verify_vote_request_batch`], additionally rejecting any request whose
+/// nullifier is already in `nullifier_set`. Nullifiers of valid requests are
+/// added to the set, so a later duplicate in the same batch is rejected too.
+pub fn verify_vote_request_batch_with_nullifiers(
+    poll_parameters: &PollParametersStorage,
+    vote_requests: &[VoteRequest],
+    public_keys: &[&[u8]],
+    nullifier_set: &mut NullifierSet,
+) -> Result<Vec<bool>, WedprError> {
+    if vote_requests.len() != public_keys.len() {
+        return Err(WedprError::ArgumentError);
+    }
+    if vote_requests.is_empty() {
+        return Ok(Vec::new());
+    }
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    let mut results = Vec::with_capacity(vote_requests.len());
+    for (vote_request, public_key) in vote_requests.iter().zip(public_keys) {
+        let nullifier =
+            derive_vote_request_nullifier(vote_request, poll_parameters);
+        if nullifier_set.contains(&nullifier) {
+            wedpr_println!("duplicate vote request rejected!");
+            results.push(false);
+            continue;
+        }
+        let valid = verify_vote_request_with_options(
+            &parsed_parameters,
+            vote_request,
+            public_key,
+            VoteVerifyOptions::new(&default_suite()),
+        )
+        .is_ok();
+        if valid {
+            nullifier_set.insert(nullifier);
         }
+        results.push(valid);
+    }
+    Ok(results)
+}
+
+/// Verifies whether ciphertext ballots from a certified voter are valid like
+/// [`verify_vote_request`], additionally requiring an authorization token
+/// issued by the poll authority of `authority_public_key` over the
+/// authorization nonce of the request. Requests without a valid token, or
+/// with a nonce already in `spent_nonces`, are rejected. Nonces of valid
+/// requests are added to the set, so each token authorizes one request.
+pub fn verify_vote_request_with_token(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+    authority_public_key: &[u8],
+    spent_nonces: &mut HashSet<Vec<u8>>,
+) -> Result<bool, WedprError> {
+    let nonce = vote_request.get_authorization_nonce();
+    let token = vote_request.get_authorization_token();
+    if !is_valid_token_nonce(nonce)
+        || token.is_empty()
+        || !verify_token(authority_public_key, nonce, token)?
+    {
+        wedpr_println!("verify_token failed!");
+        return Ok(false);
+    }
+    if spent_nonces.contains(nonce) {
+        wedpr_println!("authorization nonce was already spent!");
+        return Ok(false);
+    }
+    let valid = verify_vote_request(poll_parameters, vote_request, public_key)?;
+    if valid {
+        spent_nonces.insert(nonce.to_vec());
+    }
+    Ok(valid)
+}
+
+// Runs the checks of a vote request after its limits and candidates, which
+// callers check against their own candidate index.
+pub(crate) fn verify_vote_request_points<H: Hash, S: Signature>(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+    options: VoteVerifyOptions<H, S>,
+) -> Result<(), VoteVerifyFailure> {
+    // Decompress each ballot once for all the proofs and the sum.
+    let decompressed_points;
+    let points = match options.points {
+        Some(points) => points,
+        None => {
+            decompressed_points = VoteRequestPoints::try_from(vote_request)?;
+            &decompressed_points
+        },
+    };
+    verify_vote_request_binding(parsed_parameters, vote_request, points)?;
+    if options.check_signature {
+        verify_vote_signature(
+            options.suite,
+            parsed_parameters,
+            vote_request,
+            public_key,
+        )?;
+    }
+    verify_vote_request_proofs(
+        parsed_parameters,
+        vote_request,
+        points,
+        options.shuffle_rng,
+    )
+}
+
+// Checks that the points are those of the request and that the request was
+// cast in the poll.
+fn verify_vote_request_binding(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    points: &VoteRequestPoints,
+) -> Result<(), VoteVerifyFailure> {
+    if points.encoded_ballots != encoded_ballots(vote_request.get_vote()) {
+        wedpr_println!("voted ballot points do not match the request!");
+        return Err(VoteVerifyFailure::Malformed(WedprError::ArgumentError));
+    }
+    verify_poll_id(parsed_parameters, vote_request)
+}
+
+// Lists the compressed ciphertexts decompressed into VoteRequestPoints.
+fn encoded_ballots(vote: &VoteStorage) -> Vec<Vec<u8>> {
+    let blank_ballot = vote.get_blank_ballot();
+    let mut encoded_ballots = vec![
+        blank_ballot.get_ciphertext1().to_vec(),
+        blank_ballot.get_ciphertext2().to_vec(),
+        vote.get_rest_ballot().get_ciphertext1().to_vec(),
+    ];
+    for pair in vote.get_voted_ballot() {
+        encoded_ballots.push(pair.get_ballot().get_ciphertext1().to_vec());
+        encoded_ballots.push(pair.get_ballot().get_ciphertext2().to_vec());
+    }
+    encoded_ballots
+}
+
+// Checks the weight, range, format and balance proofs of a vote request,
+// shuffling the order of the format proofs if given an rng.
+fn verify_vote_request_proofs(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    points: &VoteRequestPoints,
+    shuffle_rng: Option<&mut dyn RngCore>,
+) -> Result<(), VoteVerifyFailure> {
+    let vote = vote_request.get_vote();
+    let poll_point = &parsed_parameters.poll_point;
+    // Votes without a weight proof keep the unweighted flow.
+    if !vote_request.get_weight_proof().is_empty() {
+        verify_voter_weight(poll_point, vote_request, &points.blank_ballot)?;
+    }
+
+    let (commitments, voted_count) = points.range_commitments();
+    let voted_commitments = &commitments[..voted_count];
+    let voted_ballot_sum: RistrettoPoint = voted_commitments.iter().sum();
+    verify_candidate_caps(parsed_parameters, vote_request, voted_commitments)?;
+
+    verify_range_proof_batch_size(
+        commitments.len(),
+        vote_request.get_range_proof(),
+    )?;
+    if !trace_stage("range_proof", || {
+        verify_value_range_in_batch(
+            &commitments,
+            vote_request.get_range_proof(),
+            poll_point,
+        )
+    }) {
+        wedpr_println!(
+            "verify_range failed for {} commitments with {} padding!",
+            voted_count + 1,
+            commitments.len() - voted_count - 1
+        );
+        return Err(VoteVerifyFailure::RangeProof);
+    }
+    let ballot_index = points.index_by_candidate(vote);
+    match shuffle_rng {
+        Some(rng) => verify_candidate_format_proofs_shuffled(
+            &ballot_index,
+            vote_request.get_ballot_proof(),
+            poll_point,
+            rng,
+        )?,
+        None => verify_candidate_format_proofs(
+            &ballot_index,
+            vote_request.get_ballot_proof(),
+            poll_point,
+        )?,
     }
     let balance_proof =
         Deserialize::deserialize(vote_request.get_sum_balance_proof())?;
     if !verify_sum_relationship(
         &voted_ballot_sum,
-        &bytes_to_point(&rest_ballot)?,
-        &bytes_to_point(&blank_ballot.get_ciphertext1())?,
+        &points.rest_ballot_c1,
+        &points.blank_ballot.0,
         &balance_proof,
         &BASEPOINT_G1,
-        &poll_point,
+        poll_point,
     )? {
         wedpr_println!("verify_balance failed!");
-        return Err(WedprError::VerificationError);
+        return Err(VoteVerifyFailure::SumBalance);
     }
-    Ok(true)
+    Ok(())
+}
+
+/// Verifies a vote request like [`verify_vote_request`], but continues past
+/// failed checks and reports all of them. Requests beyond the verify limits,
+/// whose candidates do not match the poll or whose ballots are malformed
+/// cannot be checked at all and still return an error.
+pub fn verify_vote_request_report(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<VoteVerifyReport, WedprError> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    if verify_input_limits(&parsed_parameters.limits, vote_request).is_err() {
+        return Err(WedprError::ArgumentError);
+    }
+    match verify_candidate_set(&parsed_parameters.candidates, vote_request) {
+        Ok(()) => {},
+        Err(VoteVerifyFailure::DuplicateCandidate(_)) => {
+            return Err(WedprError::FormatError)
+        },
+        Err(_) => return Err(WedprError::ArgumentError),
+    }
+    if verify_poll_id(&parsed_parameters, vote_request).is_err() {
+        return Err(WedprError::ArgumentError);
+    }
+    let vote = vote_request.get_vote();
+    let points = VoteRequestPoints::try_from(vote_request)?;
+    let poll_point = &parsed_parameters.poll_point;
+    let blank_ballot = vote.get_blank_ballot();
+    let suite = default_suite();
+
+    let signature_valid = verify_ballot_signature_with_params(
+        &suite
+            .signature_params()
+            .poll_id(&parsed_parameters.poll_id)
+            .poll_epoch(parsed_parameters.poll_epoch),
+        public_key,
+        blank_ballot,
+        &vote.get_signature().to_vec(),
+    )
+    .unwrap_or(false);
+    let weight_valid = vote_request.get_weight_proof().is_empty()
+        || verify_voter_weight(poll_point, vote_request, &points.blank_ballot)
+            .is_ok();
+
+    let (commitments, voted_count) = points.range_commitments();
+    let voted_commitments = &commitments[..voted_count];
+    let candidate_cap_valid = verify_candidate_caps(
+        &parsed_parameters,
+        vote_request,
+        voted_commitments,
+    )
+    .is_ok();
+    let range_proof_valid = verify_range_proof_batch_size(
+        commitments.len(),
+        vote_request.get_range_proof(),
+    )
+    .is_ok()
+        && verify_value_range_in_batch(
+            &commitments,
+            vote_request.get_range_proof(),
+            poll_point,
+        );
+    let ballot_index = points.index_by_candidate(vote);
+    let failed_candidates = vote_request
+        .get_ballot_proof()
+        .iter()
+        .filter(|candidate_ballot_proof| {
+            verify_candidate_format_proof(
+                &ballot_index,
+                candidate_ballot_proof,
+                poll_point,
+            )
+            .is_err()
+        })
+        .map(|candidate_ballot_proof| {
+            candidate_ballot_proof.get_key().to_string()
+        })
+        .collect();
+
+    let voted_ballot_sum: RistrettoPoint = voted_commitments.iter().sum();
+    let sum_balance_valid =
+        match Deserialize::deserialize(vote_request.get_sum_balance_proof()) {
+            Ok(balance_proof) => verify_sum_relationship(
+                &voted_ballot_sum,
+                &points.rest_ballot_c1,
+                &points.blank_ballot.0,
+                &balance_proof,
+                &BASEPOINT_G1,
+                poll_point,
+            )
+            .unwrap_or(false),
+            Err(_) => false,
+        };
+    Ok(VoteVerifyReport {
+        signature_valid,
+        weight_valid,
+        range_proof_valid,
+        candidate_cap_valid,
+        sum_balance_valid,
+        failed_candidates,
+    })
+}
+
+/// Checks that both ciphertexts of a ballot are 32-byte encodings of valid
+/// Ristretto points, before any proof is checked against them.
+pub fn validate_ballot(ballot: &Ballot) -> Result<(), WedprError> {
+    ballot_points(ballot).map(|_| ())
+}
+
+/// Verifies that a ballot is a well-formed ciphertext under the poll point,
+/// i.e. that its format proof binds ciphertext2 = r * G2 to the randomness r
+/// of ciphertext1 = v * G1 + r * poll_point. This is the check run on every
+/// candidate ballot of a vote request.
+pub fn verify_ballot_wellformed(
+    ballot: &Ballot,
+    poll_point: &RistrettoPoint,
+    format_proof: &[u8],
+) -> Result<bool, WedprError> {
+    verify_ballot_wellformed_with_generators(
+        ballot,
+        &GeneratorSet::default(),
+        poll_point,
+        format_proof,
+    )
+}
+
+/// Verifies that a ballot is a well-formed ciphertext under the poll point
+/// and the generators.
+pub fn verify_ballot_wellformed_with_generators(
+    ballot: &Ballot,
+    generators: &GeneratorSet,
+    poll_point: &RistrettoPoint,
+    format_proof: &[u8],
+) -> Result<bool, WedprError> {
+    let (ciphertext1, ciphertext2) = ballot_points(ballot)?;
+    verify_ballot_format(
+        &ciphertext1,
+        &ciphertext2,
+        format_proof,
+        generators,
+        poll_point,
+    )
+}
+
+/// Verifies only the range proof of a vote request, without the signature,
+/// format and balance checks, e.g. for debugging or fuzzing the range proof.
+pub fn verify_vote_range_only(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+) -> Result<bool, WedprError> {
+    let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
+    let (commitments, _) =
+        VoteRequestPoints::try_from(vote_request)?.range_commitments();
+    if verify_range_proof_batch_size(
+        commitments.len(),
+        vote_request.get_range_proof(),
+    )
+    .is_err()
+    {
+        return Ok(false);
+    }
+    Ok(trace_stage("range_proof", || {
+        verify_value_range_in_batch(
+            &commitments,
+            vote_request.get_range_proof(),
+            &poll_point,
+        )
+    }))
+}
+
+fn verify_candidate_caps(
+    parsed_parameters: &ParsedPollParameters,
+    vote_request: &VoteRequest,
+    voted_commitments: &[RistrettoPoint],
+) -> Result<(), VoteVerifyFailure> {
+    if parsed_parameters.candidate_caps.is_empty() {
+        return Ok(());
+    }
+    let mut cap_commitments = Vec::new();
+    for (pair, commitment) in vote_request
+        .get_vote()
+        .get_voted_ballot()
+        .iter()
+        .zip(voted_commitments)
+    {
+        if let Some(cap) = parsed_parameters
+            .candidate_caps
+            .get(&CandidateId::new(pair.get_candidate()))
+        {
+            cap_commitments
+                .push(*BASEPOINT_G1 * Scalar::from(*cap) - commitment);
+        }
+    }
+    align_commitment_list_if_needed(&mut cap_commitments);
+    if !trace_stage("cap_range_proof", || {
+        verify_value_range_in_batch(
+            &cap_commitments,
+            vote_request.get_cap_range_proof(),
+            &parsed_parameters.poll_point,
+        )
+    }) {
+        wedpr_println!("verify_candidate_caps failed!");
+        return Err(VoteVerifyFailure::CandidateCap);
+    }
+    Ok(())
+}
+
+fn verify_voter_weight(
+    poll_point: &RistrettoPoint,
+    vote_request: &VoteRequest,
+    blank_ballot: &(RistrettoPoint, RistrettoPoint),
+) -> Result<(), VoteVerifyFailure> {
+    let (blank_c1, blank_c2) = *blank_ballot;
+    let weight_proof =
+        Deserialize::deserialize(vote_request.get_weight_proof())?;
+    if !verify_equality_relationship_proof(
+        &blank_c2,
+        &(blank_c1
+            - *BASEPOINT_G1 * Scalar::from(vote_request.get_voter_weight())),
+        &weight_proof,
+        &BASEPOINT_G2,
+        poll_point,
+    )? {
+        wedpr_println!("verify_voter_weight failed!");
+        return Err(VoteVerifyFailure::Weight);
+    }
+    Ok(())
 }
 
 /// Verifies whether a partially decrypted result from a counter is valid.
@@ -108,6 +858,56 @@ pub fn verify_count_request(
     counter_share: &RistrettoPoint,
     partially_decrypted_result: &DecryptedResultPartStorage,
 ) -> Result<bool, WedprError> {
+    match verify_count_request_detailed(
+        poll_parameters,
+        encrypted_vote_sum,
+        counter_share,
+        partially_decrypted_result,
+    ) {
+        Ok(()) => Ok(true),
+        Err(VoteVerifyFailure::Malformed(error)) => Err(error),
+        Err(VoteVerifyFailure::UnauthorizedCounter) => {
+            Err(WedprError::ArgumentError)
+        },
+        Err(_) => Ok(false),
+    }
+}
+
+/// Verifies partially decrypted results from all counters of a counting
+/// round against the same encrypted vote sum, returning a verification result
+/// for each counter instead of failing the round on one invalid part. Each
+/// part is paired with the poll point share of its counter.
+pub fn verify_count_round(
+    poll_parameters: &PollParametersStorage,
+    encrypted_vote_sum: &VoteStorage,
+    counter_parts: &[(RistrettoPoint, DecryptedResultPartStorage)],
+) -> Result<Vec<bool>, WedprError> {
+    // A malformed vote sum fails the whole round rather than every counter.
+    bytes_to_point(encrypted_vote_sum.get_blank_ballot().get_ciphertext2())?;
+    Ok(counter_parts
+        .iter()
+        .map(|(counter_share, partially_decrypted_result)| {
+            verify_count_request_detailed(
+                poll_parameters,
+                encrypted_vote_sum,
+                counter_share,
+                partially_decrypted_result,
+            )
+            .is_ok()
+        })
+        .collect())
+}
+
+/// Verifies whether a partially decrypted result from a counter is valid,
+/// reporting which candidate failed. A failure of the total votes is reported
+/// under [`POLL_RESULT_KEY_TOTAL_BALLOTS`].
+pub fn verify_count_request_detailed(
+    poll_parameters: &PollParametersStorage,
+    encrypted_vote_sum: &VoteStorage,
+    counter_share: &RistrettoPoint,
+    partially_decrypted_result: &DecryptedResultPartStorage,
+) -> Result<(), VoteVerifyFailure> {
+    verify_counter_authorized(poll_parameters, counter_share)?;
     // Verify the total votes.
     let blank_c2_sum = bytes_to_point(
         &encrypted_vote_sum.get_blank_ballot().get_ciphertext2(),
@@ -129,19 +929,21 @@ pub fn verify_count_request(
         &BASEPOINT_G2,
         &blank_c2_sum,
     )? {
-        return Ok(false);
+        return Err(VoteVerifyFailure::Equality(
+            POLL_RESULT_KEY_TOTAL_BALLOTS.to_string(),
+        ));
     }
 
     // Verify the votes for each candidate.
+    let ballot_index = index_ballots_by_candidate(encrypted_vote_sum);
+    let counting_part_index =
+        index_counting_parts_by_candidate(partially_decrypted_result);
     for candidate in poll_parameters.get_candidates().get_candidate() {
-        let candidate_ballot =
-            get_ballot_by_candidate(encrypted_vote_sum, candidate)?;
+        let candidate_ballot = lookup_by_candidate(&ballot_index, candidate)?;
         let candidate_c2_sum =
             bytes_to_point(&candidate_ballot.get_ciphertext2())?;
-        let counting_part = get_counting_part_by_candidate(
-            partially_decrypted_result,
-            candidate,
-        )?;
+        let counting_part =
+            lookup_by_candidate(&counting_part_index, candidate)?;
         let candidate_c2_r = bytes_to_point(&counting_part.get_blinding_c2())?;
         let candidate_equality_proof =
             Deserialize::deserialize(counting_part.get_equality_proof())?;
@@ -153,10 +955,93 @@ pub fn verify_count_request(
             &candidate_c2_sum,
         )? {
             wedpr_println!("verify_equality failed!");
-            return Ok(false);
+            return Err(VoteVerifyFailure::Equality(candidate.to_string()));
         }
     }
-    Ok(true)
+    Ok(())
+}
+
+/// Checks that a counter share is among the authorized counter shares of the
+/// poll, if the poll lists any.
+pub fn verify_counter_authorized(
+    poll_parameters: &PollParametersStorage,
+    counter_share: &RistrettoPoint,
+) -> Result<(), VoteVerifyFailure> {
+    let authorized_shares = poll_parameters.get_authorized_counter_share();
+    if authorized_shares.is_empty() {
+        return Ok(());
+    }
+    // Compressed encodings are canonical, so equal points have equal bytes.
+    let counter_share_bytes = point_to_bytes(counter_share);
+    if !authorized_shares
+        .iter()
+        .any(|share| *share == counter_share_bytes)
+    {
+        wedpr_println!("counter share is not authorized!");
+        return Err(VoteVerifyFailure::UnauthorizedCounter);
+    }
+    Ok(())
+}
+
+/// Verifies whether a reshare splits the share of a listed counter into
+/// shares of new counters summing to it, made by the holder of the old
+/// secret. A valid reshare keeps the poll point unchanged. Shares of threshold
+/// counters are rejected, as the Lagrange coefficients of a threshold poll
+/// rely on one share per share index.
+pub fn verify_counter_reshare(
+    counter_parameters: &CounterParametersStorage,
+    reshare_proof: &CounterReshareProof,
+) -> Result<bool, WedprError> {
+    let counter_id = reshare_proof.get_counter_id();
+    let shares = counter_parameters.get_counter_parameters_share();
+    let old_share = match shares
+        .iter()
+        .find(|share| share.get_counter_id() == counter_id)
+    {
+        Some(share) if share.get_share_index() == 0 => {
+            bytes_to_point(share.get_poll_point_share())?
+        },
+        _ => return Err(WedprError::ArgumentError),
+    };
+    // New counter ids must be distinct and must not clash with the counters
+    // staying in the poll.
+    let new_shares = reshare_proof.get_new_share();
+    let mut new_counter_ids = HashSet::new();
+    for new_share in new_shares {
+        let new_counter_id = new_share.get_counter_id();
+        if new_share.get_share_index() != 0
+            || !new_counter_ids.insert(new_counter_id)
+            || shares.iter().any(|share| {
+                share.get_counter_id() == new_counter_id
+                    && share.get_counter_id() != counter_id
+            })
+        {
+            return Err(WedprError::ArgumentError);
+        }
+    }
+    if new_shares.is_empty() {
+        return Err(WedprError::ArgumentError);
+    }
+
+    let mut new_share_sum = RistrettoPoint::default();
+    for new_share in new_shares {
+        new_share_sum += bytes_to_point(new_share.get_poll_point_share())?;
+    }
+    if new_share_sum != old_share {
+        wedpr_println!("new shares do not sum to the share of {}", counter_id);
+        return Ok(false);
+    }
+    let binding_base = reshare_binding_base(counter_id, &old_share, new_shares);
+    let binding_point = bytes_to_point(reshare_proof.get_binding_point())?;
+    let equality_proof =
+        Deserialize::deserialize(reshare_proof.get_equality_proof())?;
+    verify_equality_relationship_proof(
+        &old_share,
+        &binding_point,
+        &equality_proof,
+        &BASEPOINT_G2,
+        &binding_base,
+    )
 }
 
 /// Verifies whether the final vote result is valid.
@@ -174,29 +1059,32 @@ pub fn verify_vote_result(
             .get_blinding_c2(),
     )?;
 
-    let blank_result =
-        get_int64_by_candidate(vote_result, POLL_RESULT_KEY_TOTAL_BALLOTS)?;
+    let result_index = index_int64_by_candidate(vote_result);
+    let blank_result = lookup_result_scalar_by_candidate(
+        &result_index,
+        POLL_RESULT_KEY_TOTAL_BALLOTS,
+    )?;
     let expected_blank_ballot_result = blank_c1_sum - (blank_c2_r_sum);
-    if expected_blank_ballot_result
-        .ne(&(*BASEPOINT_G1 * (Scalar::from(blank_result as u64))))
-    {
+    if expected_blank_ballot_result.ne(&(*BASEPOINT_G1 * blank_result)) {
         return Ok(false);
     }
 
+    let ballot_index = index_ballots_by_candidate(vote_sum);
+    let counting_part_index =
+        index_counting_parts_by_candidate(aggregated_decrypted_result);
     for candidate in poll_parameters.get_candidates().get_candidate() {
-        let ballot = get_ballot_by_candidate(vote_sum, candidate)?;
-        let candidate_counting_part = get_counting_part_by_candidate(
-            aggregated_decrypted_result,
-            candidate,
-        )?;
+        let ballot = lookup_by_candidate(&ballot_index, candidate)?;
+        let candidate_counting_part =
+            lookup_by_candidate(&counting_part_index, candidate)?;
         let candidate_c2_r_sum =
             bytes_to_point(&candidate_counting_part.get_blinding_c2())?;
 
-        let candidate_result = get_int64_by_candidate(vote_result, candidate)?;
+        let candidate_result =
+            lookup_result_scalar_by_candidate(&result_index, candidate)?;
         let expected_candidate_ballot_result =
             bytes_to_point(&ballot.get_ciphertext1())? - (candidate_c2_r_sum);
         if !expected_candidate_ballot_result
-            .eq(&(*BASEPOINT_G1 * (Scalar::from(candidate_result as u64))))
+            .eq(&(*BASEPOINT_G1 * candidate_result))
         {
             wedpr_println!("verify candidate {} failed!", candidate);
             return Ok(false);
@@ -205,6 +1093,172 @@ pub fn verify_vote_result(
     Ok(true)
 }
 
+/// Verifies whether the final vote result is valid like
+/// [`verify_vote_result`], additionally rejecting result keys that are
+/// neither the total ballots nor a poll candidate, e.g. rows injected into a
+/// corrupted result storage.
+pub fn verify_vote_result_strict(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+    vote_result: &VoteResultStorage,
+) -> Result<bool, WedprError> {
+    let candidate_set: HashSet<CandidateId> = poll_parameters
+        .get_candidates()
+        .get_candidate()
+        .iter()
+        .map(|candidate| CandidateId::new(candidate))
+        .collect();
+    for pair in vote_result.get_result() {
+        let key = pair.get_key();
+        if key != POLL_RESULT_KEY_TOTAL_BALLOTS
+            && !candidate_set.contains(&CandidateId::new(key))
+        {
+            wedpr_println!("unexpected vote result key: {}", key);
+            return Err(WedprError::ArgumentError);
+        }
+    }
+    verify_vote_result(
+        poll_parameters,
+        vote_sum,
+        aggregated_decrypted_result,
+        vote_result,
+    )
+}
+
+/// Verifies whether the final vote result is valid like
+/// [`verify_vote_result`], but compares the tallies in constant time and
+/// checks every candidate before returning, so the timing does not reveal
+/// which candidate mismatched. Malformed inputs still return an error early.
+pub fn verify_vote_result_ct(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+    vote_result: &VoteResultStorage,
+) -> Result<bool, WedprError> {
+    let blank_c1_sum =
+        bytes_to_point(&vote_sum.get_blank_ballot().get_ciphertext1())?;
+    let blank_c2_r_sum = bytes_to_point(
+        &aggregated_decrypted_result
+            .get_blank_part()
+            .get_blinding_c2(),
+    )?;
+
+    let result_index = index_int64_by_candidate(vote_result);
+    let blank_result = lookup_result_scalar_by_candidate(
+        &result_index,
+        POLL_RESULT_KEY_TOTAL_BALLOTS,
+    )?;
+    let expected_blank_ballot_result = blank_c1_sum - (blank_c2_r_sum);
+    let mut all_equal = ct_point_eq(
+        &expected_blank_ballot_result,
+        &(*BASEPOINT_G1 * blank_result),
+    );
+
+    let ballot_index = index_ballots_by_candidate(vote_sum);
+    let counting_part_index =
+        index_counting_parts_by_candidate(aggregated_decrypted_result);
+    for candidate in poll_parameters.get_candidates().get_candidate() {
+        let ballot = lookup_by_candidate(&ballot_index, candidate)?;
+        let candidate_counting_part =
+            lookup_by_candidate(&counting_part_index, candidate)?;
+        let candidate_c2_r_sum =
+            bytes_to_point(&candidate_counting_part.get_blinding_c2())?;
+
+        let candidate_result =
+            lookup_result_scalar_by_candidate(&result_index, candidate)?;
+        let expected_candidate_ballot_result =
+            bytes_to_point(&ballot.get_ciphertext1())? - (candidate_c2_r_sum);
+        all_equal &= ct_point_eq(
+            &expected_candidate_ballot_result,
+            &(*BASEPOINT_G1 * candidate_result),
+        );
+    }
+    Ok(bool::from(all_equal))
+}
+
+fn ct_point_eq(point1: &RistrettoPoint, point2: &RistrettoPoint) -> Choice {
+    point1
+        .compress()
+        .as_bytes()
+        .ct_eq(point2.compress().as_bytes())
+}
+
+/// Recovers the tallies of a poll with a bounded search up to
+/// `max_vote_limit` and verifies them like [`verify_vote_result`], returning
+/// the typed tallies. Tallies beyond the limit cannot be recovered and are
+/// reported as an argument error.
+pub fn compute_and_verify_vote_result(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+    max_vote_limit: i64,
+) -> Result<VoteResult, WedprError> {
+    let vote_result = finalize_vote_result(
+        poll_parameters,
+        vote_sum,
+        aggregated_decrypted_result,
+        max_vote_limit,
+    )?;
+    // The total and every candidate must have been recovered.
+    if vote_result.get_result().len()
+        != poll_parameters.get_candidates().get_candidate().len() + 1
+    {
+        wedpr_println!("tallies beyond {} votes", max_vote_limit);
+        return Err(WedprError::ArgumentError);
+    }
+    if !verify_vote_result(
+        poll_parameters,
+        vote_sum,
+        aggregated_decrypted_result,
+        &vote_result,
+    )? {
+        return Err(WedprError::VerificationError);
+    }
+    decode_vote_result(&vote_result)
+}
+
+/// Verifies whether the final vote result of a bounded poll is valid. Besides
+/// the checks of [`verify_vote_result`], the candidate results must not add up
+/// to more than the total ballots, since every bounded voter splits at most
+/// their weight across the candidates.
+pub fn verify_bounded_vote_result(
+    poll_parameters: &PollParametersStorage,
+    vote_sum: &VoteStorage,
+    aggregated_decrypted_result: &DecryptedResultPartStorage,
+    vote_result: &VoteResultStorage,
+) -> Result<bool, WedprError> {
+    let result_index = index_int64_by_candidate(vote_result);
+    let blank_result = lookup_result_by_candidate(
+        &result_index,
+        POLL_RESULT_KEY_TOTAL_BALLOTS,
+    )?;
+    let mut candidate_result_sum = 0u64;
+    for candidate in poll_parameters.get_candidates().get_candidate() {
+        let candidate_result =
+            lookup_result_by_candidate(&result_index, candidate)?;
+        candidate_result_sum =
+            match candidate_result_sum.checked_add(candidate_result) {
+                Some(sum) => sum,
+                None => return Err(WedprError::ArgumentError),
+            };
+    }
+    if candidate_result_sum > blank_result {
+        wedpr_println!(
+            "candidate results {} exceed total ballots {}!",
+            candidate_result_sum,
+            blank_result
+        );
+        return Err(WedprError::VerificationError);
+    }
+    verify_vote_result(
+        poll_parameters,
+        vote_sum,
+        aggregated_decrypted_result,
+        vote_result,
+    )
+}
+
 fn verify_count_request_for_unlisted_candidate(
     counter_share: &RistrettoPoint,
     candidate_vote_sum: &CipherPointsToBallotPair,
@@ -360,7 +1414,7 @@ pub fn verify_count_request_unlisted(
 }
 
 fn verify_ballot_proof(
-    poll_parameters: &PollParametersStorage,
+    poll_point: &RistrettoPoint,
     ballot_proof: &BallotProof,
     candiate_ballot: &Ballot,
     weight_ballot: &Ballot,
@@ -369,14 +1423,13 @@ fn verify_ballot_proof(
     // verify the format proof
     let format_proof =
         Deserialize::deserialize(&ballot_proof.get_format_proof())?;
-    let polling_point = bytes_to_point(&poll_parameters.get_poll_point())?;
     let ret = verify_format_proof(
         &bytes_to_point(candiate_ballot.get_ciphertext1())?,
         &bytes_to_point(candiate_ballot.get_ciphertext2())?,
         &format_proof,
         &BASEPOINT_G1,
         &BASEPOINT_G2,
-        &polling_point,
+        poll_point,
     )?;
     if !ret {
         return Err(WedprError::VerificationError);
@@ -390,7 +1443,7 @@ fn verify_ballot_proof(
         &bytes_to_point(zero_ballot.get_ciphertext1())?,
         &either_equality_proof,
         &BASEPOINT_G1,
-        &polling_point,
+        poll_point,
     )?;
     if !verify_ret {
         return Err(WedprError::VerificationError);
@@ -399,7 +1452,7 @@ fn verify_ballot_proof(
 }
 
 fn batch_verify_ballot_proof(
-    poll_parameters: &PollParametersStorage,
+    poll_point: &RistrettoPoint,
     vote_request: &VoteRequest,
     ballot_proof_infos: &Vec<StringToBallotProofPair>,
 ) -> Result<bool, WedprError> {
@@ -413,7 +1466,7 @@ fn batch_verify_ballot_proof(
             get_ballot_by_candidate(&vote_request.get_vote(), candidate)?;
         // verify the ballot proof
         let verify_result = verify_ballot_proof(
-            &poll_parameters,
+            poll_point,
             &ballot_proof,
             &candidate_ballot,
             &weight_ballot,
@@ -427,7 +1480,7 @@ fn batch_verify_ballot_proof(
 }
 
 fn batch_verify_unlisted_candidate_ballot_proof(
-    poll_parameters: &PollParametersStorage,
+    poll_point: &RistrettoPoint,
     vote_request: &VoteRequest,
     unlisted_ballot_proof_infos: &Vec<CipherPointsToBallotProofPair>,
 ) -> Result<bool, WedprError> {
@@ -447,7 +1500,7 @@ fn batch_verify_unlisted_candidate_ballot_proof(
             find_unlisted_candidate = true;
             // verify the found unlisted candidate ballot
             let verify_result = verify_ballot_proof(
-                &poll_parameters,
+                poll_point,
                 &ballot_proof,
                 &unlisted_vote_ballot.get_ballot(),
                 &weight_ballot,
@@ -468,12 +1521,42 @@ pub fn verify_unbounded_vote_request(
     poll_parameters: &PollParametersStorage,
     vote_request: &VoteRequest,
     public_key: &[u8],
+) -> Result<bool, WedprError> {
+    verify_unbounded_vote_request_with_suite(
+        &default_suite(),
+        poll_parameters,
+        vote_request,
+        public_key,
+    )
+}
+
+pub fn verify_unbounded_vote_request_with_suite<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<bool, WedprError> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    verify_unbounded_vote_request_with_poll_point(
+        suite,
+        &parsed_parameters.poll_point,
+        vote_request,
+        public_key,
+    )
+}
+
+fn verify_unbounded_vote_request_with_poll_point<H: Hash, S: Signature>(
+    suite: &VoteCryptoSuite<H, S>,
+    poll_point: &RistrettoPoint,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
 ) -> Result<bool, WedprError> {
     // check signature for the ballot with public key
     let signature = vote_request.get_vote().get_signature();
     let weight_ballot = vote_request.get_vote().get_blank_ballot();
     let zero_ballot = vote_request.get_vote().get_zero_ballot();
-    let verify_result = verify_ballots_signature(
+    let verify_result = verify_ballots_signature_with_params(
+        &suite.signature_params(),
         public_key,
         weight_ballot,
         zero_ballot,
@@ -484,7 +1567,7 @@ pub fn verify_unbounded_vote_request(
     }
     // verify the ballot proof
     let result = batch_verify_ballot_proof(
-        &poll_parameters,
+        poll_point,
         &vote_request,
         &(vote_request.get_ballot_proof()).to_vec(),
     )?;
@@ -499,9 +1582,28 @@ pub fn verify_unbounded_vote_request_unlisted(
     vote_request: &VoteRequest,
     public_key: &[u8],
 ) -> Result<bool, WedprError> {
+    verify_unbounded_vote_request_unlisted_with_suite(
+        &default_suite(),
+        poll_parameters,
+        vote_request,
+        public_key,
+    )
+}
+
+pub fn verify_unbounded_vote_request_unlisted_with_suite<
+    H: Hash,
+    S: Signature,
+>(
+    suite: &VoteCryptoSuite<H, S>,
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<bool, WedprError> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
     // verify the listed unbounded vote request
-    let verify_result = verify_unbounded_vote_request(
-        &poll_parameters,
+    let verify_result = verify_unbounded_vote_request_with_poll_point(
+        suite,
+        &parsed_parameters.poll_point,
         &vote_request,
         public_key,
     )?;
@@ -510,7 +1612,7 @@ pub fn verify_unbounded_vote_request_unlisted(
     }
     // verify the unlisted unbouded vote request
     let result = batch_verify_unlisted_candidate_ballot_proof(
-        &poll_parameters,
+        &parsed_parameters.poll_point,
         &vote_request,
         &(vote_request.get_unlisted_ballot_proof()).to_vec(),
     )?;
@@ -519,3 +1621,1341 @@ pub fn verify_unbounded_vote_request_unlisted(
     }
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinator, counter, parameters, result, suite,
+        test_fixtures::{
+            cast_vote, make_candidate_list, make_counters, run_poll,
+            verify_detailed, CompletedPoll,
+        },
+        voter,
+    };
+    use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+    use std::convert::TryFrom;
+    use wedpr_l_crypto_zkp_utils::{
+        bytes_to_point, get_random_scalar, point_to_bytes, BASEPOINT_G1,
+        BASEPOINT_G2,
+    };
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_protos::{
+        config::SIGNATURE,
+        generated::acv::{
+            Ballot, CandidateBallot, DecryptedResultPartStorage, VoteRequest,
+            VoteStorage,
+        },
+    };
+
+    #[test]
+    fn test_verify_vote_result_ct() {
+        let CompletedPoll {
+            poll_parameters,
+            encrypted_vote_sum,
+            aggregated_decrypted_result,
+            vote_result,
+            ..
+        } = run_poll();
+        assert!(verify_vote_result_ct(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+
+        // A tampered result is rejected like by verify_vote_result.
+        let mut tampered_vote_result = vote_result.clone();
+        let tampered_value = tampered_vote_result.get_result()[2].get_value();
+        tampered_vote_result.mut_result()[2].set_value(tampered_value + 1);
+        assert!(!verify_vote_result_ct(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &tampered_vote_result,
+        )
+        .unwrap());
+        assert!(!verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &tampered_vote_result,
+        )
+        .unwrap_or(false));
+    }
+
+    #[test]
+    fn test_verify_bounded_vote_result() {
+        let CompletedPoll {
+            poll_parameters,
+            encrypted_vote_sum,
+            aggregated_decrypted_result,
+            vote_result,
+            ..
+        } = run_poll();
+        assert!(verify_bounded_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result,
+        )
+        .unwrap());
+
+        // Negative results are rejected before any point is computed.
+        let mut negative_vote_result = vote_result.clone();
+        negative_vote_result.mut_result()[1].set_value(-1);
+        assert!(verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &negative_vote_result,
+        )
+        .is_err());
+        // Candidate results exceeding the total ballots are inconsistent.
+        let mut inconsistent_vote_result = vote_result.clone();
+        inconsistent_vote_result.mut_result()[1].set_value(i64::MAX);
+        assert!(verify_bounded_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &inconsistent_vote_result,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_ballot() {
+        let point = point_to_bytes(&(get_random_scalar() * *BASEPOINT_G1));
+        let mut ballot = Ballot::new();
+        ballot.set_ciphertext1(point.clone());
+        ballot.set_ciphertext2(point.clone());
+        assert!(validate_ballot(&ballot).is_ok());
+
+        for length in [31, 33] {
+            let mut malformed = point.clone();
+            malformed.resize(length, 0);
+            let mut short_c1 = ballot.clone();
+            short_c1.set_ciphertext1(malformed.clone());
+            assert!(matches!(
+                validate_ballot(&short_c1),
+                Err(WedprError::FormatError)
+            ));
+            let mut short_c2 = ballot.clone();
+            short_c2.set_ciphertext2(malformed);
+            assert!(matches!(
+                validate_ballot(&short_c2),
+                Err(WedprError::FormatError)
+            ));
+        }
+        // Right length, but not a valid point encoding.
+        let mut invalid = ballot.clone();
+        invalid.set_ciphertext1(vec![0xff; 32]);
+        assert!(matches!(
+            validate_ballot(&invalid),
+            Err(WedprError::FormatError)
+        ));
+
+        // The verifier rejects a truncated ballot up front.
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2,
+            ]);
+        vote_request.mut_vote().mut_voted_ballot()[0]
+            .mut_ballot()
+            .mut_ciphertext1()
+            .truncate(31);
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &vote_request, &public_key),
+            Err(VoteVerifyFailure::Malformed(WedprError::FormatError))
+        ));
+    }
+
+    #[test]
+    fn test_verify_ballot_wellformed() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let poll_point =
+            bytes_to_point(poll_parameters.get_poll_point()).unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                3, 4,
+            ]);
+        let format_proof = |candidate: &str| {
+            vote_request
+                .get_ballot_proof()
+                .iter()
+                .find(|pair| pair.get_key() == candidate)
+                .unwrap()
+                .get_value()
+                .get_format_proof()
+                .to_vec()
+        };
+        let ballot = |candidate: &str| {
+            vote_request
+                .get_vote()
+                .get_voted_ballot()
+                .iter()
+                .find(|pair| pair.get_candidate() == candidate)
+                .unwrap()
+                .get_ballot()
+                .clone()
+        };
+        for candidate in ["Kitten", "Doge"] {
+            assert!(verify_ballot_wellformed(
+                &ballot(candidate),
+                &poll_point,
+                &format_proof(candidate)
+            )
+            .unwrap());
+        }
+
+        // A ciphertext2 with randomness other than that of ciphertext1 is
+        // rejected, as is the proof of another ballot.
+        let mut mismatched_ballot = ballot("Kitten");
+        mismatched_ballot.set_ciphertext2(point_to_bytes(
+            &(*BASEPOINT_G2 * get_random_scalar()),
+        ));
+        assert!(!verify_ballot_wellformed(
+            &mismatched_ballot,
+            &poll_point,
+            &format_proof("Kitten")
+        )
+        .unwrap_or(false));
+        assert!(!verify_ballot_wellformed(
+            &ballot("Kitten"),
+            &poll_point,
+            &format_proof("Doge")
+        )
+        .unwrap_or(false));
+        // Nor is a ballot well-formed under another poll point.
+        assert!(!verify_ballot_wellformed(
+            &ballot("Kitten"),
+            &(*BASEPOINT_G1 * get_random_scalar()),
+            &format_proof("Kitten")
+        )
+        .unwrap_or(false));
+        assert!(
+            verify_ballot_wellformed(&ballot("Kitten"), &poll_point, &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_vote_range_only() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2, 3,
+            ]);
+        assert!(
+            verify_vote_range_only(&poll_parameters, &vote_request).unwrap()
+        );
+
+        // Other checks are skipped, so a broken signature does not matter.
+        let mut unsigned_request = vote_request.clone();
+        unsigned_request.mut_vote().clear_signature();
+        assert!(verify_vote_range_only(&poll_parameters, &unsigned_request)
+            .unwrap());
+        assert!(verify_vote_request(
+            &poll_parameters,
+            &unsigned_request,
+            &public_key
+        )
+        .is_err());
+
+        // A tampered range proof fails.
+        let mut tampered_request = vote_request.clone();
+        tampered_request.mut_range_proof()[0] ^= 1;
+        assert!(!verify_vote_range_only(&poll_parameters, &tampered_request)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_vote_request_report() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2, 3,
+            ]);
+        let report = verify_vote_request_report(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+        )
+        .unwrap();
+        assert!(report.is_valid());
+
+        // Break the signature and two format proofs at once.
+        let mut tampered_request = vote_request.clone();
+        tampered_request.mut_vote().clear_signature();
+        let ballot_proofs = tampered_request.mut_ballot_proof();
+        let first_proof = ballot_proofs[0].get_value().clone();
+        let second_proof = ballot_proofs[1].get_value().clone();
+        ballot_proofs[0].set_value(second_proof);
+        ballot_proofs[1].set_value(first_proof);
+        let expected_candidates = vec![
+            ballot_proofs[0].get_key().to_string(),
+            ballot_proofs[1].get_key().to_string(),
+        ];
+        let report = verify_vote_request_report(
+            &poll_parameters,
+            &tampered_request,
+            &public_key,
+        )
+        .unwrap();
+        assert!(!report.is_valid());
+        assert!(!report.signature_valid);
+        assert!(report.weight_valid);
+        assert!(report.range_proof_valid);
+        assert!(report.candidate_cap_valid);
+        assert!(report.sum_balance_valid);
+        assert_eq!(report.failed_candidates, expected_candidates);
+        // The strict verifier still stops at the first failure.
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &tampered_request, &public_key),
+            Err(VoteVerifyFailure::Signature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_vote_request_batch() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (other_public_key, _) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+
+        let mut vote_request_list = vec![];
+        for voter_choice in [vec![1, 2, 3], vec![2, 4, 6], vec![3, 3, 3]] {
+            vote_request_list.push(cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                20,
+                &voter_choice,
+            ));
+        }
+
+        // The second request is checked against a key that did not sign its
+        // blank ballot, which should only fail that request.
+        let public_keys =
+            vec![&public_key[..], &other_public_key[..], &public_key[..]];
+        assert_eq!(
+            verify_vote_request_batch(
+                &poll_parameters,
+                &vote_request_list,
+                &public_keys
+            )
+            .unwrap(),
+            vec![true, false, true]
+        );
+        assert!(verify_vote_request_batch(
+            &poll_parameters,
+            &vote_request_list,
+            &public_keys[..2]
+        )
+        .is_err());
+        assert!(verify_vote_request_batch(&poll_parameters, &[], &[])
+            .unwrap()
+            .is_empty());
+
+        let parsed_parameters =
+            parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap();
+        assert!(verify_vote_request_with_options(
+            &parsed_parameters,
+            &vote_request_list[0],
+            &public_key,
+            VoteVerifyOptions::new(&suite::default_suite())
+        )
+        .is_ok());
+        let mut invalid_poll_parameters = poll_parameters.clone();
+        invalid_poll_parameters.set_poll_point(vec![0xff; 32]);
+        assert!(parameters::ParsedPollParameters::try_from(
+            &invalid_poll_parameters
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_vote_request_detailed() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (other_public_key, _) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 20, &[
+                1, 2, 3,
+            ]);
+        assert!(
+            verify_detailed(&poll_parameters, &vote_request, &public_key)
+                .is_ok()
+        );
+        assert!(voter::self_verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .is_ok());
+
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &vote_request, &other_public_key),
+            Err(VoteVerifyFailure::Signature)
+        ));
+
+        // Swap in the format proof of another candidate.
+        let mut invalid_request = vote_request.clone();
+        let other_format_proof = invalid_request.get_ballot_proof()[1]
+            .get_value()
+            .get_format_proof()
+            .to_vec();
+        let tampered_candidate =
+            invalid_request.get_ballot_proof()[0].get_key().to_string();
+        invalid_request.mut_ballot_proof()[0]
+            .mut_value()
+            .set_format_proof(other_format_proof);
+        match verify_detailed(&poll_parameters, &invalid_request, &public_key) {
+            Err(failure @ VoteVerifyFailure::FormatProof(_)) => {
+                // The cause travels with the failure for structured logs.
+                assert_eq!(
+                    failure.to_string(),
+                    format!(
+                        "format proof is invalid for candidate {}",
+                        tampered_candidate
+                    )
+                )
+            },
+            _ => panic!("expected a format proof failure"),
+        }
+        assert!(matches!(
+            voter::self_verify_vote_request(
+                &poll_parameters,
+                &invalid_request,
+                &public_key
+            ),
+            Err(VoteVerifyFailure::FormatProof(_))
+        ));
+        assert!(verify_vote_request(
+            &poll_parameters,
+            &invalid_request,
+            &public_key
+        )
+        .is_err());
+
+        // Requests must carry exactly the poll candidates.
+        let mut missing_request = vote_request.clone();
+        missing_request.mut_vote().mut_voted_ballot().pop();
+        let mut extra_request = vote_request.clone();
+        let mut extra_ballot =
+            extra_request.get_vote().get_voted_ballot()[0].clone();
+        extra_ballot.set_candidate("Unknown".to_string());
+        extra_request
+            .mut_vote()
+            .mut_voted_ballot()
+            .push(extra_ballot);
+        let mut duplicate_request = vote_request.clone();
+        let duplicate_candidate =
+            duplicate_request.get_vote().get_voted_ballot()[0]
+                .get_candidate()
+                .to_string();
+        duplicate_request.mut_vote().mut_voted_ballot()[1]
+            .set_candidate(duplicate_candidate.clone());
+        let mut duplicate_proof_request = vote_request.clone();
+        let duplicate_key = duplicate_proof_request.get_ballot_proof()[0]
+            .get_key()
+            .to_string();
+        duplicate_proof_request.mut_ballot_proof()[1]
+            .set_key(duplicate_key.clone());
+        for mismatched_request in [missing_request, extra_request] {
+            assert!(matches!(
+                verify_detailed(
+                    &poll_parameters,
+                    &mismatched_request,
+                    &public_key
+                ),
+                Err(VoteVerifyFailure::CandidateMismatch)
+            ));
+            assert!(matches!(
+                verify_vote_request(
+                    &poll_parameters,
+                    &mismatched_request,
+                    &public_key
+                ),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+        // Repeated candidates are reported as such, before any proof.
+        for (duplicate_request, expected_candidate) in [
+            (duplicate_request, duplicate_candidate),
+            (duplicate_proof_request, duplicate_key),
+        ] {
+            match verify_detailed(
+                &poll_parameters,
+                &duplicate_request,
+                &public_key,
+            ) {
+                Err(VoteVerifyFailure::DuplicateCandidate(candidate)) => {
+                    assert_eq!(candidate, expected_candidate)
+                },
+                _ => panic!("duplicate candidate not rejected"),
+            }
+            assert!(matches!(
+                verify_vote_request(
+                    &poll_parameters,
+                    &duplicate_request,
+                    &public_key
+                ),
+                Err(WedprError::FormatError)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_weighted_vote_request() {
+        let (_, counter_parameters) = make_counters(&["1001", "1002", "1003"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 30)
+                .unwrap();
+        let vote_choices =
+            voter::make_vote_choices(&vec![10, 0, 5], &candidate_list);
+        let vote_request = voter::vote_weighted(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        let mut weight_assignment = coordinator::VoterWeightAssignment::new();
+        weight_assignment.assign(&poll_parameters, &registration_response);
+        assert!(verify_weighted_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &weight_assignment
+        )
+        .unwrap());
+
+        // A voter without an assigned weight, or assigned another weight,
+        // is rejected.
+        assert!(!verify_weighted_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &coordinator::VoterWeightAssignment::new()
+        )
+        .unwrap());
+        let mut reassigned_response = registration_response.clone();
+        reassigned_response.set_voter_weight(40);
+        let mut other_assignment = coordinator::VoterWeightAssignment::new();
+        other_assignment.assign(&poll_parameters, &reassigned_response);
+        assert!(!verify_weighted_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key,
+            &other_assignment
+        )
+        .unwrap());
+
+        // Claiming another weight breaks the weight proof.
+        let mut invalid_request = vote_request.clone();
+        invalid_request.set_voter_weight(40);
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &invalid_request, &public_key),
+            Err(VoteVerifyFailure::Weight)
+        ));
+        assert!(!verify_weighted_vote_request(
+            &poll_parameters,
+            &invalid_request,
+            &public_key,
+            &other_assignment
+        )
+        .unwrap());
+
+        // So does swapping in a blank ballot certified for another weight,
+        // even though its signature is valid.
+        let other_registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 40)
+                .unwrap();
+        let mut invalid_request = vote_request.clone();
+        invalid_request
+            .mut_vote()
+            .set_blank_ballot(other_registration_response.get_ballot().clone());
+        invalid_request.mut_vote().set_signature(
+            other_registration_response.get_signature().to_vec(),
+        );
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &invalid_request, &public_key),
+            Err(VoteVerifyFailure::Weight)
+        ));
+
+        // Unweighted votes are still accepted by the default verifier.
+        let unweighted_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verify_vote_request(
+            &poll_parameters,
+            &unweighted_request,
+            &public_key
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_sum_relationship_soundness() {
+        use curve25519_dalek::traits::MultiscalarMul;
+        use proptest::{
+            prelude::{any, Strategy},
+            test_runner::Config,
+        };
+        use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
+            prove_sum_relationship, verify_sum_relationship,
+        };
+
+        // Blindings are random or zero, so that commitments may also be
+        // bare values or the identity point.
+        let blinding_strategy = || {
+            (any::<bool>(), proptest::array::uniform32(any::<u8>())).prop_map(
+                |(is_zero, bytes)| {
+                    if is_zero {
+                        Scalar::zero()
+                    } else {
+                        Scalar::from_bytes_mod_order(bytes)
+                    }
+                },
+            )
+        };
+        proptest::proptest!(Config::with_cases(32), |(
+            value1 in 0u64..(1 << 32),
+            value2 in 0u64..(1 << 32),
+            blinding1 in blinding_strategy(),
+            blinding2 in blinding_strategy(),
+            blinding3 in blinding_strategy(),
+            delta in 1u64..,
+            perturb_blinding in any::<bool>(),
+        )| {
+            let blinding_basepoint = *BASEPOINT_G1 * get_random_scalar();
+            let commit = |value: u64, blinding: &Scalar| {
+                RistrettoPoint::multiscalar_mul(
+                    &[Scalar::from(value), *blinding],
+                    &[*BASEPOINT_G1, blinding_basepoint],
+                )
+            };
+            let commitment1 = commit(value1, &blinding1);
+            let commitment2 = commit(value2, &blinding2);
+            let commitment3 = commit(value1 + value2, &blinding3);
+            let proof = prove_sum_relationship(
+                value1,
+                value2,
+                &blinding1,
+                &blinding2,
+                &blinding3,
+                &BASEPOINT_G1,
+                &blinding_basepoint,
+            );
+            proptest::prop_assert!(verify_sum_relationship(
+                &commitment1,
+                &commitment2,
+                &commitment3,
+                &proof,
+                &BASEPOINT_G1,
+                &blinding_basepoint,
+            )
+            .unwrap());
+
+            // Moving the sum by a nonzero multiple of either basepoint
+            // breaks the relationship.
+            let perturbation = if perturb_blinding {
+                blinding_basepoint * Scalar::from(delta)
+            } else {
+                *BASEPOINT_G1 * Scalar::from(delta)
+            };
+            proptest::prop_assert!(!verify_sum_relationship(
+                &commitment1,
+                &commitment2,
+                &(commitment3 + perturbation),
+                &proof,
+                &BASEPOINT_G1,
+                &blinding_basepoint,
+            )
+            .unwrap_or(false));
+        });
+    }
+
+    #[test]
+    fn test_verify_vote_request_unsigned() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2,
+            ]);
+        let parsed_parameters =
+            parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap();
+        let suite = suite::default_suite();
+        let verify_unsigned = |vote_request: &VoteRequest| {
+            verify_vote_request_with_options(
+                &parsed_parameters,
+                vote_request,
+                &[],
+                VoteVerifyOptions::new(&suite).check_signature(false),
+            )
+        };
+        assert!(verify_unsigned(&vote_request).is_ok());
+
+        // A garbage signature fails the signed check but not the proofs.
+        vote_request.mut_vote().set_signature(vec![7u8; 64]);
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &vote_request, &public_key),
+            Err(VoteVerifyFailure::Signature)
+        ));
+        assert!(verify_unsigned(&vote_request).is_ok());
+
+        // Invalid proofs are still rejected.
+        let mut tampered_request = vote_request.clone();
+        let range_proof = vote_request.get_range_proof().to_vec();
+        tampered_request
+            .set_range_proof(range_proof[..range_proof.len() - 1].to_vec());
+        assert!(verify_unsigned(&tampered_request).is_err());
+    }
+
+    #[test]
+    fn test_verify_vote_request_shuffled() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2, 3,
+            ]);
+
+        // Swapping two format proofs makes both of them invalid.
+        let mut invalid_request = vote_request.clone();
+        let first_proof = invalid_request.get_ballot_proof()[0]
+            .get_value()
+            .get_format_proof()
+            .to_vec();
+        let second_proof = invalid_request.get_ballot_proof()[1]
+            .get_value()
+            .get_format_proof()
+            .to_vec();
+        invalid_request.mut_ballot_proof()[0]
+            .mut_value()
+            .set_format_proof(second_proof);
+        invalid_request.mut_ballot_proof()[1]
+            .mut_value()
+            .set_format_proof(first_proof);
+
+        // Every shuffle gives the outcome of the unshuffled verification.
+        assert!(verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .unwrap());
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &invalid_request, &public_key),
+            Err(VoteVerifyFailure::FormatProof(_))
+        ));
+        let parsed_parameters =
+            parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap();
+        let suite = suite::default_suite();
+        for seed in 0..8u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            assert!(verify_vote_request_with_options(
+                &parsed_parameters,
+                &vote_request,
+                &public_key,
+                VoteVerifyOptions::new(&suite).shuffle_rng(&mut rng)
+            )
+            .is_ok());
+            assert!(matches!(
+                verify_vote_request_with_options(
+                    &parsed_parameters,
+                    &invalid_request,
+                    &public_key,
+                    VoteVerifyOptions::new(&suite).shuffle_rng(&mut rng)
+                ),
+                Err(VoteVerifyFailure::FormatProof(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_verify_vote_request_with_points() {
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let parsed_parameters =
+            parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap();
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response =
+            coordinator::certify_voter(&secret_key, &registration_request, 10)
+                .unwrap();
+        let vote_request = voter::vote(
+            &voter_secret,
+            &voter::make_vote_choices(&vec![1, 2, 3], &candidate_list),
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+
+        let points = VoteRequestPoints::try_from(&vote_request).unwrap();
+        assert_eq!(points.voted_ballot_count(), 3);
+        let suite = suite::default_suite();
+        assert!(verify_vote_request_with_options(
+            &parsed_parameters,
+            &vote_request,
+            &public_key,
+            VoteVerifyOptions::new(&suite).points(&points)
+        )
+        .is_ok());
+
+        // Points of another request are rejected before any proof, even if
+        // they have as many ballots.
+        let other_request = voter::vote(
+            &voter_secret,
+            &voter::make_vote_choices(&vec![3, 2, 1], &candidate_list),
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        let other_points = VoteRequestPoints::try_from(&other_request).unwrap();
+        assert_eq!(other_points.voted_ballot_count(), 3);
+        assert!(matches!(
+            verify_vote_request_with_options(
+                &parsed_parameters,
+                &vote_request,
+                &public_key,
+                VoteVerifyOptions::new(&suite).points(&other_points)
+            ),
+            Err(VoteVerifyFailure::Malformed(WedprError::ArgumentError))
+        ));
+
+        // Malformed ballots cannot be decompressed.
+        let mut malformed_request = vote_request.clone();
+        malformed_request.mut_vote().mut_voted_ballot()[0]
+            .mut_ballot()
+            .set_ciphertext1(vec![0xff; 32]);
+        assert!(matches!(
+            VoteRequestPoints::try_from(&malformed_request),
+            Err(WedprError::FormatError)
+        ));
+    }
+
+    #[test]
+    fn test_verify_limits() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (_, counter_parameters) = make_counters(&["1001"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 3, &[
+                1, 1, 1,
+            ]);
+        let mut parsed_parameters =
+            parameters::ParsedPollParameters::try_from(&poll_parameters)
+                .unwrap();
+        assert_eq!(
+            parsed_parameters.limits,
+            parameters::VerifyLimits::default()
+        );
+        let suite = suite::default_suite();
+        assert!(verify_vote_request_with_options(
+            &parsed_parameters,
+            &vote_request,
+            &public_key,
+            VoteVerifyOptions::new(&suite)
+        )
+        .is_ok());
+
+        // Over-long ballot lists are rejected before their ballots are even
+        // decoded, so garbage ballots are not reported as malformed.
+        let mut flooded_request = VoteRequest::new();
+        for index in 0..=parsed_parameters.limits.max_candidates {
+            let mut ballot_pair = CandidateBallot::new();
+            ballot_pair.set_candidate(format!("candidate_{}", index));
+            ballot_pair.set_ballot(Ballot::new());
+            flooded_request
+                .mut_vote()
+                .mut_voted_ballot()
+                .push(ballot_pair);
+        }
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &flooded_request, &public_key),
+            Err(VoteVerifyFailure::InputTooLarge)
+        ));
+        assert!(matches!(
+            verify_vote_request(
+                &poll_parameters,
+                &flooded_request,
+                &public_key
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // So are oversized proofs.
+        let mut padded_request = vote_request.clone();
+        padded_request.set_sum_balance_proof(vec![
+            0;
+            parsed_parameters
+                .limits
+                .max_proof_bytes
+        ]);
+        assert!(matches!(
+            verify_detailed(&poll_parameters, &padded_request, &public_key),
+            Err(VoteVerifyFailure::InputTooLarge)
+        ));
+
+        // Limits can be tightened for a poll.
+        parsed_parameters.limits.max_candidates = 2;
+        assert!(matches!(
+            verify_vote_request_with_options(
+                &parsed_parameters,
+                &vote_request,
+                &public_key,
+                VoteVerifyOptions::new(&suite)
+            ),
+            Err(VoteVerifyFailure::InputTooLarge)
+        ));
+        parsed_parameters.limits = parameters::VerifyLimits {
+            max_candidates: 3,
+            max_proof_bytes: vote_request.get_range_proof().len(),
+        };
+        assert!(matches!(
+            verify_vote_request_with_options(
+                &parsed_parameters,
+                &vote_request,
+                &public_key,
+                VoteVerifyOptions::new(&suite)
+            ),
+            Err(VoteVerifyFailure::InputTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_verify_count_round() {
+        let counter_id_list = ["1001", "1002", "1003"];
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&counter_id_list);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for choices in [vec![1, 2, 3], vec![4, 0, 1]] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                10,
+                &choices,
+            );
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum,
+            )
+            .unwrap();
+        }
+
+        let mut counter_parts = vec![];
+        for (index, counter_id) in counter_id_list.iter().enumerate() {
+            let counter_share = bytes_to_point(
+                counter_parameters.get_counter_parameters_share()[index]
+                    .get_poll_point_share(),
+            )
+            .unwrap();
+            let partially_decrypted_result = counter::count(
+                counter_id,
+                &counter_secret_list[index],
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            counter_parts.push((counter_share, partially_decrypted_result));
+        }
+        assert_eq!(
+            verify_count_round(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &counter_parts
+            )
+            .unwrap(),
+            vec![true, true, true]
+        );
+        let valid_parts: Vec<DecryptedResultPartStorage> =
+            counter_parts.iter().map(|(_, part)| part.clone()).collect();
+        let aggregated_decrypted_result =
+            coordinator::aggregate_decrypted_parts(
+                &poll_parameters,
+                &valid_parts,
+            )
+            .unwrap();
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            20,
+        )
+        .unwrap();
+        assert!(verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result
+        )
+        .unwrap());
+
+        // One bad counter among good ones is singled out.
+        let mut bad_counter_parts = counter_parts.clone();
+        let other_blinding_c2 = bad_counter_parts[0]
+            .1
+            .get_blank_part()
+            .get_blinding_c2()
+            .to_vec();
+        bad_counter_parts[1]
+            .1
+            .mut_blank_part()
+            .set_blinding_c2(other_blinding_c2);
+        assert_eq!(
+            verify_count_round(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &bad_counter_parts
+            )
+            .unwrap(),
+            vec![true, false, true]
+        );
+        assert!(verify_count_round(
+            &poll_parameters,
+            &VoteStorage::new(),
+            &counter_parts
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_authorized_counters() {
+        let counter_id_list = ["1001", "1002", "1003"];
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&counter_id_list);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        // Only the first two counters are authorized to decrypt.
+        let mut listed_counters = counter_parameters.clone();
+        listed_counters.mut_counter_parameters_share().truncate(2);
+        let authorized_parameters = parameters::PollParametersBuilder::new()
+            .poll_point(poll_parameters.get_poll_point())
+            .candidates(["Kitten", "Doge"])
+            .authorized_counters(&listed_counters)
+            .build()
+            .unwrap();
+        assert_eq!(
+            authorized_parameters.get_authorized_counter_share().len(),
+            2
+        );
+
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                3, 4,
+            ]);
+        let mut encrypted_vote_sum = VoteStorage::new();
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut encrypted_vote_sum,
+        )
+        .unwrap();
+
+        for (index, counter_id) in counter_id_list.iter().enumerate() {
+            let counter_share = bytes_to_point(
+                counter_parameters.get_counter_parameters_share()[index]
+                    .get_poll_point_share(),
+            )
+            .unwrap();
+            let partially_decrypted_result = counter::count(
+                counter_id,
+                &counter_secret_list[index],
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            // Any counter share is accepted by polls without a list.
+            assert!(verify_count_request(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &counter_share,
+                &partially_decrypted_result
+            )
+            .unwrap());
+            let result = verify_count_request_detailed(
+                &authorized_parameters,
+                &encrypted_vote_sum,
+                &counter_share,
+                &partially_decrypted_result,
+            );
+            if index < 2 {
+                assert!(result.is_ok());
+            } else {
+                assert!(matches!(
+                    result,
+                    Err(VoteVerifyFailure::UnauthorizedCounter)
+                ));
+                assert!(matches!(
+                    verify_count_request(
+                        &authorized_parameters,
+                        &encrypted_vote_sum,
+                        &counter_share,
+                        &partially_decrypted_result
+                    ),
+                    Err(WedprError::ArgumentError)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_and_verify_vote_result() {
+        let (public_key, secret_key) = SIGNATURE.generate_keypair();
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&["1001", "1002", "1003"]);
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        // Choices are listed in the order of the candidate list.
+        let mut encrypted_vote_sum = VoteStorage::new();
+        for (voter_weight, choices) in [
+            (10, vec![1, 2, 3]),
+            (20, vec![0, 15, 5]),
+            (6, vec![6, 0, 0]),
+        ] {
+            let vote_request = cast_vote(
+                &secret_key,
+                &poll_parameters,
+                &candidate_list,
+                voter_weight,
+                &choices,
+            );
+            assert!(verify_vote_request(
+                &poll_parameters,
+                &vote_request,
+                &public_key
+            )
+            .unwrap());
+            coordinator::aggregate_vote_sum_response(
+                &poll_parameters,
+                vote_request.get_vote(),
+                &mut encrypted_vote_sum,
+            )
+            .unwrap();
+        }
+        let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+        for (share, counter_secret) in counter_parameters
+            .get_counter_parameters_share()
+            .iter()
+            .zip(&counter_secret_list)
+        {
+            let partially_decrypted_result = counter::count(
+                share.get_counter_id(),
+                counter_secret,
+                &encrypted_vote_sum,
+            )
+            .unwrap();
+            coordinator::aggregate_decrypted_part_sum(
+                &poll_parameters,
+                &partially_decrypted_result,
+                &mut aggregated_decrypted_result,
+            )
+            .unwrap();
+        }
+
+        let tally = compute_and_verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            50,
+        )
+        .unwrap();
+        assert_eq!(tally, result::VoteResult {
+            total: 36,
+            per_candidate: [("Kitten", 7), ("Doge", 17), ("Bunny", 8)]
+                .iter()
+                .map(|(candidate, value)| (candidate.to_string(), *value))
+                .collect(),
+        });
+        // The total of 36 ballots cannot be recovered below its value.
+        assert!(matches!(
+            compute_and_verify_vote_result(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &aggregated_decrypted_result,
+                20,
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+
+    #[test]
+    fn test_verify_vote_result_strict() {
+        use wedpr_s_protos::generated::acv::StringToInt64Pair;
+
+        let (counter_secret_list, counter_parameters) =
+            make_counters(&["1001"]);
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let candidate_list = make_candidate_list(&["Kitten", "Doge", "Bunny"]);
+        let poll_parameters = coordinator::make_poll_parameters(
+            &candidate_list,
+            &counter_parameters,
+        )
+        .unwrap();
+        let vote_request =
+            cast_vote(&secret_key, &poll_parameters, &candidate_list, 10, &[
+                1, 2, 3,
+            ]);
+        let mut encrypted_vote_sum = VoteStorage::new();
+        coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut encrypted_vote_sum,
+        )
+        .unwrap();
+        let partially_decrypted_result = counter::count(
+            "1001",
+            &counter_secret_list[0],
+            &encrypted_vote_sum,
+        )
+        .unwrap();
+        let aggregated_decrypted_result =
+            coordinator::aggregate_decrypted_parts(&poll_parameters, &[
+                partially_decrypted_result,
+            ])
+            .unwrap();
+        let vote_result = coordinator::finalize_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            10,
+        )
+        .unwrap();
+        assert!(verify_vote_result_strict(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &vote_result
+        )
+        .unwrap());
+
+        // An injected row is ignored by default and rejected when strict.
+        let mut injected_result = vote_result.clone();
+        let mut pair = StringToInt64Pair::new();
+        pair.set_key("Tiger".to_string());
+        pair.set_value(7);
+        injected_result.mut_result().push(pair);
+        assert!(verify_vote_result(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &aggregated_decrypted_result,
+            &injected_result
+        )
+        .unwrap());
+        assert!(matches!(
+            verify_vote_result_strict(
+                &poll_parameters,
+                &encrypted_vote_sum,
+                &aggregated_decrypted_result,
+                &injected_result
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+    }
+}
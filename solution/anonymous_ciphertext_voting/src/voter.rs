@@ -2,20 +2,31 @@
 
 //! Library for a poll voter.
 
-use crate::utils::{align_scalar_list_if_needed, align_u64_list_if_needed};
+use crate::{
+    candidate::CandidateId,
+    parameters::{parse_candidate_caps, ParsedPollParameters},
+    secret::{zeroize_scalar, zeroize_scalars, SecretScalar},
+    suite::default_suite,
+    utils::{align_scalar_list_if_needed, align_u64_list_if_needed},
+    verifier::{
+        verify_vote_request_with_options, VoteVerifyFailure, VoteVerifyOptions,
+    },
+};
+use std::convert::TryFrom;
 use wedpr_s_protos::generated::acv::CipherPoints;
 
 use curve25519_dalek::{
     ristretto::RistrettoPoint, scalar::Scalar, traits::MultiscalarMul,
 };
 use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
-    prove_either_equality_relationship_proof, prove_format_proof,
+    prove_either_equality_relationship_proof,
+    prove_equality_relationship_proof, prove_format_proof,
     prove_sum_relationship,
 };
 use wedpr_l_crypto_zkp_range_proof::prove_value_range_in_batch;
 use wedpr_l_crypto_zkp_utils::{
     bytes_to_point, bytes_to_scalar, get_random_scalar, point_to_bytes,
-    scalar_to_bytes, Serialize, BASEPOINT_G1, BASEPOINT_G2,
+    Serialize, BASEPOINT_G1, BASEPOINT_G2,
 };
 use wedpr_l_utils::error::WedprError;
 use wedpr_s_protos::generated::acv::{
@@ -28,9 +39,9 @@ use wedpr_s_protos::generated::acv::{
 
 /// Makes secrets used by a voter.
 pub fn make_voter_secret() -> VoterSecret {
-    let vote_secret = get_random_scalar();
+    let vote_secret = SecretScalar::new(get_random_scalar());
     VoterSecret {
-        voter_secret: scalar_to_bytes(&vote_secret),
+        voter_secret: vote_secret.to_bytes(),
         unknown_fields: Default::default(),
         cached_size: Default::default(),
     }
@@ -40,10 +51,10 @@ pub fn generate_registration_blinding_point(
     secret: &VoterSecret,
     poll_parameters: &PollParametersStorage,
 ) -> Result<RegistrationBlindingPoint, WedprError> {
-    let voter_secret = bytes_to_scalar(secret.get_voter_secret())?;
-    let blinding_basepoint_g2 = voter_secret * *BASEPOINT_G2;
+    let voter_secret = SecretScalar::from_bytes(secret.get_voter_secret())?;
+    let blinding_basepoint_g2 = voter_secret.mul_point(&BASEPOINT_G2);
     let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
-    let blinding_poll_point = voter_secret * poll_point;
+    let blinding_poll_point = voter_secret.mul_point(&poll_point);
     let mut registration_blinding_point = RegistrationBlindingPoint::new();
     registration_blinding_point
         .set_blinding_basepoint_g2(point_to_bytes(&blinding_basepoint_g2));
@@ -127,6 +138,7 @@ pub fn vote(
     poll_parameters: &PollParametersStorage,
 ) -> Result<VoteRequest, WedprError> {
     let mut vote_request = VoteRequest::new();
+    vote_request.set_poll_id(poll_parameters.get_poll_id().to_vec());
 
     // Compute for each choice.
     let mut blinding_sum = Scalar::zero();
@@ -192,17 +204,49 @@ pub fn vote(
     // Prove the balance.
     let used_vote_weight_sum = (registration_response.get_voter_weight()
         - unused_vote_weight as u32) as u64;
-    let vote_secret = bytes_to_scalar(voter_secret.get_voter_secret())?;
+    let vote_secret =
+        SecretScalar::from_bytes(voter_secret.get_voter_secret())?;
     let balance_proof = prove_sum_relationship(
         used_vote_weight_sum,
         unused_vote_weight as u64,
         &blinding_sum,
         &blinding_rest,
-        &vote_secret,
+        vote_secret.as_scalar(),
         &BASEPOINT_G1,
         &poll_point,
     );
 
+    // Prove that capped candidates receive no more than their caps, as the
+    // range of cap * G1 - ciphertext1 for each of them.
+    let candidate_caps = parse_candidate_caps(poll_parameters)?;
+    if !candidate_caps.is_empty() {
+        let mut cap_rest_list: Vec<u64> = Vec::new();
+        let mut cap_blinding_list: Vec<Scalar> = Vec::new();
+        for (choice, blinding) in
+            vote_choices.get_choice().iter().zip(&blinding_list)
+        {
+            if let Some(cap) =
+                candidate_caps.get(&CandidateId::new(choice.get_candidate()))
+            {
+                let value = choice.get_value() as u64;
+                if value > *cap {
+                    return Err(WedprError::ArgumentError);
+                }
+                cap_rest_list.push(cap - value);
+                cap_blinding_list.push(-blinding);
+            }
+        }
+        align_u64_list_if_needed(&mut cap_rest_list);
+        align_scalar_list_if_needed(&mut cap_blinding_list);
+        let (cap_range_proof, _) = prove_value_range_in_batch(
+            &cap_rest_list,
+            &cap_blinding_list,
+            &poll_point,
+        )?;
+        zeroize_scalars(&mut cap_blinding_list);
+        vote_request.set_cap_range_proof(cap_range_proof);
+    }
+
     // Prove the range.
     choice_list.push(unused_vote_weight as u64);
     blinding_list.push(blinding_rest);
@@ -210,6 +254,9 @@ pub fn vote(
     align_scalar_list_if_needed(&mut blinding_list);
     let (range_proof, _) =
         prove_value_range_in_batch(&choice_list, &blinding_list, &poll_point)?;
+    // Blindings are no longer needed once all proofs are made.
+    zeroize_scalars(&mut blinding_list);
+    zeroize_scalar(&mut blinding_sum);
 
     // Write back.
     vote_request.set_sum_balance_proof(balance_proof.serialize());
@@ -222,6 +269,69 @@ pub fn vote(
     Ok(vote_request)
 }
 
+/// Checks a vote request locally before sending it, running exactly the
+/// checks of the verifier so that a malformed ballot is reported with the
+/// reason why it would be rejected.
+pub fn self_verify_vote_request(
+    poll_parameters: &PollParametersStorage,
+    vote_request: &VoteRequest,
+    public_key: &[u8],
+) -> Result<(), VoteVerifyFailure> {
+    let parsed_parameters = ParsedPollParameters::try_from(poll_parameters)?;
+    verify_vote_request_with_options(
+        &parsed_parameters,
+        vote_request,
+        public_key,
+        VoteVerifyOptions::new(&default_suite()),
+    )
+}
+
+/// Checks in plaintext that the voted values and the rest value add up to the
+/// blank value, for sanity-checking a ballot construction with known secrets
+/// before generating proofs.
+///
+/// This is a debugging aid only and not part of the security-critical path:
+/// verifiers must rely on the balance proof of the vote request instead.
+#[cfg(any(test, feature = "debug-checks"))]
+pub fn debug_check_ballot_balance(
+    blank_value: u64,
+    voted_values: &[u64],
+    rest_value: u64,
+) -> bool {
+    voted_values
+        .iter()
+        .try_fold(rest_value, |sum, value| sum.checked_add(*value))
+        == Some(blank_value)
+}
+
+/// Votes the ciphertext ballots like [`vote`], and additionally proves that
+/// the blank ballot commits to the voter weight assigned by the coordinator.
+pub fn vote_weighted(
+    voter_secret: &VoterSecret,
+    vote_choices: &VoteChoices,
+    registration_response: &RegistrationResponse,
+    poll_parameters: &PollParametersStorage,
+) -> Result<VoteRequest, WedprError> {
+    let mut vote_request = vote(
+        voter_secret,
+        vote_choices,
+        registration_response,
+        poll_parameters,
+    )?;
+    let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
+    let secret = SecretScalar::from_bytes(voter_secret.get_voter_secret())?;
+    // The blank ballot is (r * poll_point + weight * G1, r * G2), so proving
+    // the same r for both parts after removing the weight binds the weight.
+    let weight_proof = prove_equality_relationship_proof(
+        secret.as_scalar(),
+        &BASEPOINT_G2,
+        &poll_point,
+    );
+    vote_request.set_voter_weight(registration_response.get_voter_weight());
+    vote_request.set_weight_proof(weight_proof.serialize());
+    Ok(vote_request)
+}
+
 pub fn generate_ballot_proof(
     vote_value: u64,
     registration_response: &RegistrationResponse,
@@ -273,6 +383,7 @@ pub fn vote_unbounded(
     poll_parameters: &PollParametersStorage,
 ) -> Result<VoteRequest, WedprError> {
     let mut vote_request = VoteRequest::new();
+    vote_request.set_poll_id(poll_parameters.get_poll_id().to_vec());
     let poll_point = bytes_to_point(poll_parameters.get_poll_point())?;
     // generate ballot for every vote choice
     for vote_choice in vote_choices.get_choice() {
@@ -374,3 +485,19 @@ pub fn vote_unbounded_unlisted(
     }
     Ok(vote_request)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_check_ballot_balance() {
+        assert!(debug_check_ballot_balance(10, &[1, 2, 3], 4));
+        assert!(debug_check_ballot_balance(10, &[], 10));
+        assert!(debug_check_ballot_balance(0, &[0, 0], 0));
+        assert!(!debug_check_ballot_balance(10, &[1, 2, 3], 3));
+        assert!(!debug_check_ballot_balance(10, &[11], 0));
+        // An overflowing sum never balances.
+        assert!(!debug_check_ballot_balance(1, &[u64::MAX, 2], 0));
+    }
+}
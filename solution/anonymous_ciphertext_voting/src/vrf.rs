@@ -0,0 +1,127 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of a verifiable random function (VRF) for anonymous voter
+//! eligibility.
+//!
+//! For a secret key x with public key x * G1, the input is hashed to a point
+//! H and the output is derived from gamma = x * H. The proof carries gamma and
+//! an equality proof that the same x links G1 to the public key and H to
+//! gamma, so the output is deterministic for a key and input and cannot be
+//! forged without the secret key.
+
+use crate::{
+    config::{VRF_HASH_TO_POINT_DOMAIN, VRF_OUTPUT_DOMAIN},
+    generators::hash_to_ristretto,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use wedpr_l_crypto_zkp_discrete_logarithm_proof::{
+    prove_equality_relationship_proof, verify_equality_relationship_proof,
+};
+use wedpr_l_crypto_zkp_utils::{
+    bytes_to_point, bytes_to_scalar, get_random_scalar, point_to_bytes,
+    scalar_to_bytes, Deserialize, Serialize, BASEPOINT_G1,
+};
+use wedpr_l_utils::{error::WedprError, traits::Hash};
+use wedpr_s_protos::config::HASH;
+
+const POINT_LENGTH: usize = 32;
+
+/// Makes a VRF key pair, returned as (public key, secret key).
+pub fn vrf_make_keypair() -> (Vec<u8>, Vec<u8>) {
+    let secret_key = get_random_scalar();
+    let public_key = secret_key * *BASEPOINT_G1;
+    (point_to_bytes(&public_key), scalar_to_bytes(&secret_key))
+}
+
+/// Computes the VRF output for an input and a proof of its correctness,
+/// returned as (output, proof).
+pub fn vrf_prove(
+    secret_key: &[u8],
+    input: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), WedprError> {
+    let secret_scalar = bytes_to_scalar(secret_key)?;
+    let input_point =
+        hash_to_ristretto(VRF_HASH_TO_POINT_DOMAIN.as_bytes(), input);
+    let gamma = secret_scalar * input_point;
+    let equality_proof = prove_equality_relationship_proof(
+        &secret_scalar,
+        &BASEPOINT_G1,
+        &input_point,
+    );
+
+    let mut proof = point_to_bytes(&gamma);
+    proof.append(&mut equality_proof.serialize());
+    Ok((gamma_to_output(&gamma), proof))
+}
+
+/// Verifies whether the output and proof were computed from the input with
+/// the secret key of the public key.
+pub fn vrf_verify(
+    public_key: &[u8],
+    input: &[u8],
+    output: &[u8],
+    proof: &[u8],
+) -> Result<bool, WedprError> {
+    if proof.len() <= POINT_LENGTH {
+        return Err(WedprError::FormatError);
+    }
+    let public_key_point = bytes_to_point(public_key)?;
+    let gamma = bytes_to_point(&proof[..POINT_LENGTH])?;
+    let equality_proof = Deserialize::deserialize(&proof[POINT_LENGTH..])?;
+    let input_point =
+        hash_to_ristretto(VRF_HASH_TO_POINT_DOMAIN.as_bytes(), input);
+    if !verify_equality_relationship_proof(
+        &public_key_point,
+        &gamma,
+        &equality_proof,
+        &BASEPOINT_G1,
+        &input_point,
+    )? {
+        return Ok(false);
+    }
+    Ok(gamma_to_output(&gamma) == output)
+}
+
+fn gamma_to_output(gamma: &RistrettoPoint) -> Vec<u8> {
+    let mut hash_vec = Vec::new();
+    hash_vec.extend_from_slice(VRF_OUTPUT_DOMAIN.as_bytes());
+    hash_vec.append(&mut point_to_bytes(gamma));
+    HASH.hash(&hash_vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vrf() {
+        let (public_key, secret_key) = vrf_make_keypair();
+        let (other_public_key, _) = vrf_make_keypair();
+        let input1 = "poll_1001".as_bytes();
+        let input2 = "poll_1002".as_bytes();
+
+        let (output1, proof1) = vrf_prove(&secret_key, input1).unwrap();
+        let (output1_again, proof1_again) =
+            vrf_prove(&secret_key, input1).unwrap();
+        let (output2, proof2) = vrf_prove(&secret_key, input2).unwrap();
+        // Outputs are deterministic, and differ across inputs.
+        assert_eq!(output1, output1_again);
+        assert_ne!(output1, output2);
+
+        assert!(vrf_verify(&public_key, input1, &output1, &proof1).unwrap());
+        assert!(
+            vrf_verify(&public_key, input1, &output1_again, &proof1_again)
+                .unwrap()
+        );
+        assert!(vrf_verify(&public_key, input2, &output2, &proof2).unwrap());
+        // Proofs do not carry over to other inputs, outputs or keys.
+        assert!(!vrf_verify(&public_key, input2, &output1, &proof1).unwrap());
+        assert!(!vrf_verify(&public_key, input1, &output2, &proof1).unwrap());
+        assert!(
+            !vrf_verify(&other_public_key, input1, &output1, &proof1).unwrap()
+        );
+        assert!(
+            vrf_verify(&public_key, input1, &output1, &proof1[..32]).is_err()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! End-to-end tests running the full anonymous ciphertext voting flow through
+//! the public API.
+
+use wedpr_l_crypto_zkp_utils::bytes_to_point;
+use wedpr_l_utils::traits::Signature;
+use wedpr_s_anonymous_ciphertext_voting::{
+    config::POLL_RESULT_KEY_TOTAL_BALLOTS, coordinator, counter, verifier,
+    voter,
+};
+use wedpr_s_protos::{
+    config::SIGNATURE,
+    generated::acv::{
+        CandidateList, CounterParametersStorage, DecryptedResultPartStorage,
+        VoteStorage,
+    },
+};
+
+/// Deterministic choice of a voter for a candidate.
+fn vote_choice(voter_index: usize, candidate_index: usize) -> u32 {
+    ((voter_index + candidate_index) % 5 + 1) as u32
+}
+
+fn run_end_to_end(voter_count: usize, candidate_count: usize) {
+    // System setup.
+    let counter_id_list = ["1001", "1002", "1003"];
+    let mut counter_secret_list = vec![];
+    let mut counter_parameters = CounterParametersStorage::default();
+    for counter_id in &counter_id_list {
+        let counter_secret = counter::make_counter_secret();
+        counter_parameters.mut_counter_parameters_share().push(
+            counter::make_parameters_share(counter_id, &counter_secret)
+                .unwrap(),
+        );
+        counter_secret_list.push(counter_secret);
+    }
+    let (public_key, secret_key) = SIGNATURE.generate_keypair();
+    let mut candidate_list = CandidateList::new();
+    for candidate_index in 0..candidate_count {
+        candidate_list
+            .mut_candidate()
+            .push(format!("candidate_{}", candidate_index));
+    }
+    let poll_parameters =
+        coordinator::make_poll_parameters(&candidate_list, &counter_parameters)
+            .unwrap();
+
+    // Voters build requests, which are verified and aggregated.
+    let mut encrypted_vote_sum = VoteStorage::new();
+    let mut total_weight = 0;
+    for voter_index in 0..voter_count {
+        let choices: Vec<u32> = (0..candidate_count)
+            .map(|candidate_index| vote_choice(voter_index, candidate_index))
+            .collect();
+        let voter_weight = choices.iter().sum::<u32>() + 10;
+        total_weight += voter_weight;
+        let voter_secret = voter::make_voter_secret();
+        let registration_request =
+            voter::make_registration_request(&voter_secret, &poll_parameters)
+                .unwrap();
+        let registration_response = coordinator::certify_voter(
+            &secret_key,
+            &registration_request,
+            voter_weight,
+        )
+        .unwrap();
+        let vote_choices = voter::make_vote_choices(&choices, &candidate_list);
+        let vote_request = voter::vote(
+            &voter_secret,
+            &vote_choices,
+            &registration_response,
+            &poll_parameters,
+        )
+        .unwrap();
+        assert!(verifier::verify_vote_request(
+            &poll_parameters,
+            &vote_request,
+            &public_key
+        )
+        .unwrap());
+        assert!(coordinator::aggregate_vote_sum_response(
+            &poll_parameters,
+            vote_request.get_vote(),
+            &mut encrypted_vote_sum
+        )
+        .unwrap());
+    }
+
+    // Counters decrypt their parts, which are verified and aggregated.
+    let mut aggregated_decrypted_result = DecryptedResultPartStorage::new();
+    for (index, counter_id) in counter_id_list.iter().enumerate() {
+        let partially_decrypted_result = counter::count(
+            counter_id,
+            &counter_secret_list[index],
+            &encrypted_vote_sum,
+        )
+        .unwrap();
+        let counter_share = bytes_to_point(
+            counter_parameters.get_counter_parameters_share()[index]
+                .get_poll_point_share(),
+        )
+        .unwrap();
+        assert!(verifier::verify_count_request(
+            &poll_parameters,
+            &encrypted_vote_sum,
+            &counter_share,
+            &partially_decrypted_result
+        )
+        .unwrap());
+        assert!(coordinator::aggregate_decrypted_part_sum(
+            &poll_parameters,
+            &partially_decrypted_result,
+            &mut aggregated_decrypted_result
+        )
+        .unwrap());
+    }
+
+    // The coordinator finalizes the result, which is verified against the
+    // known plaintext votes. The limit must cover the total weight as well.
+    let max_vote_limit = total_weight as i64;
+    let vote_result = coordinator::finalize_vote_result(
+        &poll_parameters,
+        &encrypted_vote_sum,
+        &aggregated_decrypted_result,
+        max_vote_limit,
+    )
+    .unwrap();
+    assert!(verifier::verify_vote_result(
+        &poll_parameters,
+        &encrypted_vote_sum,
+        &aggregated_decrypted_result,
+        &vote_result,
+    )
+    .unwrap());
+    let total_ballots = vote_result
+        .get_result()
+        .iter()
+        .find(|pair| pair.get_key() == POLL_RESULT_KEY_TOTAL_BALLOTS)
+        .unwrap()
+        .get_value();
+    assert_eq!(total_ballots, total_weight as i64);
+    for (candidate_index, candidate) in
+        candidate_list.get_candidate().iter().enumerate()
+    {
+        let expected_tally: u32 = (0..voter_count)
+            .map(|voter_index| vote_choice(voter_index, candidate_index))
+            .sum();
+        let tally = vote_result
+            .get_result()
+            .iter()
+            .find(|pair| pair.get_key() == candidate)
+            .unwrap()
+            .get_value();
+        assert_eq!(tally, expected_tally as i64);
+    }
+}
+
+#[test]
+fn test_end_to_end_3_voters_4_candidates() {
+    run_end_to_end(3, 4);
+}
+
+#[test]
+fn test_end_to_end_1_voter_1_candidate() {
+    run_end_to_end(1, 1);
+}
+
+#[test]
+fn test_end_to_end_5_voters_7_candidates() {
+    run_end_to_end(5, 7);
+}
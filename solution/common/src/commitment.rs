@@ -0,0 +1,77 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of Pedersen commitments shared by the solutions.
+//!
+//! A value is committed as value * G1 + blinding * G2, which is also the
+//! commitment checked by the range proofs over BASEPOINT_G2 blindings.
+
+use curve25519_dalek::{
+    ristretto::RistrettoPoint, scalar::Scalar, traits::MultiscalarMul,
+};
+use wedpr_l_crypto_zkp_utils::{BASEPOINT_G1, BASEPOINT_G2};
+
+/// Makes the Pedersen commitment value * BASEPOINT_G1 + blinding *
+/// BASEPOINT_G2.
+pub fn pedersen_commit(value: u64, blinding: &Scalar) -> RistrettoPoint {
+    pedersen_commit_with(value, blinding, &BASEPOINT_G1, &BASEPOINT_G2)
+}
+
+/// Makes the Pedersen commitment value * value_basepoint + blinding *
+/// blinding_basepoint, for solutions with their own generators.
+pub fn pedersen_commit_with(
+    value: u64,
+    blinding: &Scalar,
+    value_basepoint: &RistrettoPoint,
+    blinding_basepoint: &RistrettoPoint,
+) -> RistrettoPoint {
+    RistrettoPoint::multiscalar_mul(&[Scalar::from(value), *blinding], &[
+        *value_basepoint,
+        *blinding_basepoint,
+    ])
+}
+
+/// Checks whether the commitment opens to the value and the blinding.
+pub fn pedersen_verify_open(
+    commitment: &RistrettoPoint,
+    value: u64,
+    blinding: &Scalar,
+) -> bool {
+    pedersen_commit(value, blinding) == *commitment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::traits::Identity;
+    use wedpr_l_crypto_zkp_utils::get_random_scalar;
+
+    #[test]
+    fn test_pedersen_commit() {
+        // Test vectors on the basepoints.
+        assert_eq!(
+            pedersen_commit(0, &Scalar::zero()),
+            RistrettoPoint::identity()
+        );
+        assert_eq!(pedersen_commit(1, &Scalar::zero()), *BASEPOINT_G1);
+        assert_eq!(pedersen_commit(0, &Scalar::one()), *BASEPOINT_G2);
+        assert_eq!(
+            pedersen_commit(2, &Scalar::from(3u64)),
+            *BASEPOINT_G1 + *BASEPOINT_G1 + Scalar::from(3u64) * *BASEPOINT_G2
+        );
+
+        let blinding = get_random_scalar();
+        let commitment = pedersen_commit(30, &blinding);
+        assert!(pedersen_verify_open(&commitment, 30, &blinding));
+        assert!(!pedersen_verify_open(&commitment, 31, &blinding));
+        assert!(!pedersen_verify_open(&commitment, 30, &get_random_scalar()));
+
+        // Commitments add up to the commitment of the sum.
+        let other_blinding = get_random_scalar();
+        let other_commitment = pedersen_commit(12, &other_blinding);
+        assert!(pedersen_verify_open(
+            &(commitment + other_commitment),
+            42,
+            &(blinding + other_blinding)
+        ));
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of crypto helpers shared by the solutions. Protobuf definitions
+//! and their message helpers stay in wedpr_s_protos.
+
+pub mod commitment;
@@ -0,0 +1,45 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for an auctioneer.
+
+use crate::{
+    bidder::BidSecret,
+    comparison::greater_than_gap,
+    utils::{align_scalar_list_if_needed, align_u64_list_if_needed},
+};
+use curve25519_dalek::scalar::Scalar;
+use wedpr_l_crypto_zkp_range_proof::prove_value_range_in_batch;
+use wedpr_l_crypto_zkp_utils::BASEPOINT_G2;
+use wedpr_l_utils::error::WedprError;
+
+/// Proves that the winning bid is greater than every other bid, without
+/// revealing any of them, with the comparisons of
+/// [`crate::comparison::prove_greater_than`] batched into one range proof.
+pub fn prove_auction_winner(
+    winner_secret: &BidSecret,
+    other_secrets: &[BidSecret],
+) -> Result<Vec<u8>, WedprError> {
+    // A sole bidder wins without any comparison.
+    if other_secrets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut gap_list: Vec<u64> = Vec::with_capacity(other_secrets.len());
+    let mut blinding_list: Vec<Scalar> =
+        Vec::with_capacity(other_secrets.len());
+    for other_secret in other_secrets {
+        // Ties are not wins.
+        let (gap, gap_blinding) = greater_than_gap(
+            winner_secret.bid,
+            other_secret.bid,
+            &winner_secret.blinding,
+            &other_secret.blinding,
+        )?;
+        gap_list.push(gap);
+        blinding_list.push(gap_blinding);
+    }
+    align_u64_list_if_needed(&mut gap_list);
+    align_scalar_list_if_needed(&mut blinding_list);
+    let (proof, _) =
+        prove_value_range_in_batch(&gap_list, &blinding_list, &BASEPOINT_G2)?;
+    Ok(proof)
+}
@@ -0,0 +1,63 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for an auction bidder.
+
+use crate::utils::hash_bid_message;
+use curve25519_dalek::scalar::Scalar;
+use wedpr_l_crypto_zkp_range_proof::prove_value_range_in_batch;
+use wedpr_l_crypto_zkp_utils::{
+    get_random_scalar, point_to_bytes, BASEPOINT_G2,
+};
+use wedpr_l_utils::{error::WedprError, traits::Signature};
+use wedpr_s_common::commitment::pedersen_commit;
+use wedpr_s_protos::config::SIGNATURE;
+
+/// Public parameters of an auction.
+#[derive(Default, Debug, Clone)]
+pub struct AuctionParameters {
+    /// Identifier signed into every bid, so that a bid cannot be replayed in
+    /// another auction.
+    pub auction_id: String,
+}
+
+/// Sealed bid sent by a bidder.
+#[derive(Default, Debug, Clone)]
+pub struct BidRequest {
+    /// Pedersen commitment of the bid, bid * G1 + blinding * G2.
+    pub commitment: Vec<u8>,
+    /// Range proof that the committed bid fits in 32 bits.
+    pub range_proof: Vec<u8>,
+    /// Bidder signature on the auction id and the commitment.
+    pub signature: Vec<u8>,
+}
+
+/// Opening of a sealed bid, kept by the bidder and revealed only to the
+/// auctioneer.
+#[derive(Default, Debug, Clone)]
+pub struct BidSecret {
+    pub bid: u64,
+    pub blinding: Scalar,
+}
+
+/// Makes a sealed bid signed with the bidder secret key.
+pub fn make_bid(
+    auction_parameters: &AuctionParameters,
+    bid: u64,
+    secret_key: &[u8],
+) -> Result<(BidRequest, BidSecret), WedprError> {
+    let blinding = get_random_scalar();
+    let commitment = pedersen_commit(bid, &blinding);
+    let (range_proof, _) =
+        prove_value_range_in_batch(&vec![bid], &vec![blinding], &BASEPOINT_G2)?;
+    let commitment = point_to_bytes(&commitment);
+    let message_hash = hash_bid_message(auction_parameters, &commitment);
+    let signature = SIGNATURE.sign(secret_key, &message_hash)?;
+    Ok((
+        BidRequest {
+            commitment,
+            range_proof,
+            signature,
+        },
+        BidSecret { bid, blinding },
+    ))
+}
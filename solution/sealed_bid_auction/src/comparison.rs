@@ -0,0 +1,70 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of comparison proofs between Pedersen commitments.
+//!
+//! For commitments a * G1 + r_a * G2 and b * G1 + r_b * G2, their difference
+//! minus G1 commits to a - b - 1 with blinding r_a - r_b. A range proof that
+//! this gap is non-negative proves a > b without revealing a, b or the gap.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_crypto_zkp_range_proof::{
+    prove_value_range_in_batch, verify_value_range_in_batch,
+};
+use wedpr_l_crypto_zkp_utils::{BASEPOINT_G1, BASEPOINT_G2};
+use wedpr_l_utils::error::WedprError;
+
+/// Proves that the value committed by A is greater than the value committed
+/// by B. Both values must fit in 32 bits.
+pub fn prove_greater_than(
+    value_a: u64,
+    value_b: u64,
+    blinding_a: &Scalar,
+    blinding_b: &Scalar,
+) -> Result<Vec<u8>, WedprError> {
+    let (gap, gap_blinding) =
+        greater_than_gap(value_a, value_b, blinding_a, blinding_b)?;
+    let (proof, _) = prove_value_range_in_batch(
+        &vec![gap],
+        &vec![gap_blinding],
+        &BASEPOINT_G2,
+    )?;
+    Ok(proof)
+}
+
+/// Verifies whether the value committed by A is greater than the value
+/// committed by B.
+pub fn verify_greater_than(
+    commitment_a: &RistrettoPoint,
+    commitment_b: &RistrettoPoint,
+    proof: &[u8],
+) -> bool {
+    verify_value_range_in_batch(
+        &vec![gap_commitment(commitment_a, commitment_b)],
+        proof,
+        &BASEPOINT_G2,
+    )
+}
+
+/// Computes the opening of the gap commitment of A over B, failing when A is
+/// not greater than B.
+pub fn greater_than_gap(
+    value_a: u64,
+    value_b: u64,
+    blinding_a: &Scalar,
+    blinding_b: &Scalar,
+) -> Result<(u64, Scalar), WedprError> {
+    // Equal values are not greater.
+    if value_a <= value_b {
+        wedpr_println!("compared value is not greater");
+        return Err(WedprError::ArgumentError);
+    }
+    Ok((value_a - value_b - 1, blinding_a - blinding_b))
+}
+
+/// Computes the commitment of a - b - 1 from the commitments of a and b.
+pub fn gap_commitment(
+    commitment_a: &RistrettoPoint,
+    commitment_b: &RistrettoPoint,
+) -> RistrettoPoint {
+    commitment_a - commitment_b - *BASEPOINT_G1
+}
@@ -0,0 +1,169 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of sealed-bid auction (SBA) solution.
+
+#[macro_use]
+extern crate wedpr_l_macros;
+
+pub mod auctioneer;
+pub mod bidder;
+pub mod comparison;
+mod utils;
+pub mod verifier;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wedpr_l_crypto_zkp_utils::get_random_scalar;
+    use wedpr_l_utils::{error::WedprError, traits::Signature};
+    use wedpr_s_common::commitment::pedersen_commit;
+    use wedpr_s_protos::config::SIGNATURE;
+
+    #[test]
+    fn test_sealed_bid_auction() {
+        let auction_parameters = bidder::AuctionParameters {
+            auction_id: "auction_1001".to_string(),
+        };
+        let other_auction_parameters = bidder::AuctionParameters {
+            auction_id: "auction_1002".to_string(),
+        };
+
+        // All bidders seal their bids.
+        let bid_list = [300u64, 500, 100];
+        let mut public_key_list = vec![];
+        let mut request_list = vec![];
+        let mut secret_list = vec![];
+        for bid in bid_list {
+            let (public_key, secret_key) = SIGNATURE.generate_keypair();
+            let (bid_request, bid_secret) =
+                bidder::make_bid(&auction_parameters, bid, &secret_key)
+                    .unwrap();
+            assert!(verifier::verify_bid_request(
+                &auction_parameters,
+                &bid_request,
+                &public_key
+            )
+            .unwrap());
+            // A bid cannot be replayed in another auction.
+            assert!(!verifier::verify_bid_request(
+                &other_auction_parameters,
+                &bid_request,
+                &public_key
+            )
+            .unwrap());
+            public_key_list.push(public_key);
+            request_list.push(bid_request);
+            secret_list.push(bid_secret);
+        }
+        // A bid is only valid under its bidder key.
+        assert!(!verifier::verify_bid_request(
+            &auction_parameters,
+            &request_list[0],
+            &public_key_list[1]
+        )
+        .unwrap());
+
+        // The auctioneer proves that the highest bid wins.
+        let winner = 1;
+        let other_secrets = [secret_list[0].clone(), secret_list[2].clone()];
+        let other_requests = [request_list[0].clone(), request_list[2].clone()];
+        let proof = auctioneer::prove_auction_winner(
+            &secret_list[winner],
+            &other_secrets,
+        )
+        .unwrap();
+        assert!(verifier::verify_auction_winner(
+            &request_list[winner],
+            &other_requests,
+            &proof
+        )
+        .unwrap());
+
+        // The proof does not crown another bidder.
+        let wrong_requests = [request_list[1].clone(), request_list[2].clone()];
+        assert!(!verifier::verify_auction_winner(
+            &request_list[0],
+            &wrong_requests,
+            &proof
+        )
+        .unwrap());
+
+        // A lower bid cannot be proved to win, and neither can a tie.
+        let higher_secrets = [secret_list[1].clone()];
+        assert!(matches!(
+            auctioneer::prove_auction_winner(&secret_list[0], &higher_secrets),
+            Err(WedprError::ArgumentError)
+        ));
+        let (_, secret_key) = SIGNATURE.generate_keypair();
+        let (_, tied_secret) =
+            bidder::make_bid(&auction_parameters, 500, &secret_key).unwrap();
+        let tied_secrets = [tied_secret];
+        assert!(matches!(
+            auctioneer::prove_auction_winner(
+                &secret_list[winner],
+                &tied_secrets
+            ),
+            Err(WedprError::ArgumentError)
+        ));
+
+        // A sole bidder wins without any comparison.
+        assert!(verifier::verify_auction_winner(
+            &request_list[0],
+            &[],
+            &auctioneer::prove_auction_winner(&secret_list[0], &[]).unwrap()
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_greater_than_proof() {
+        let blinding_a = get_random_scalar();
+        let blinding_b = get_random_scalar();
+
+        // a > b, including the smallest gap.
+        for (value_a, value_b) in [(100u64, 1u64), (43, 42), (1, 0)] {
+            let proof = comparison::prove_greater_than(
+                value_a,
+                value_b,
+                &blinding_a,
+                &blinding_b,
+            )
+            .unwrap();
+            let commitment_a = pedersen_commit(value_a, &blinding_a);
+            let commitment_b = pedersen_commit(value_b, &blinding_b);
+            assert!(comparison::verify_greater_than(
+                &commitment_a,
+                &commitment_b,
+                &proof
+            ));
+            // The proof does not hold in the opposite direction.
+            assert!(!comparison::verify_greater_than(
+                &commitment_b,
+                &commitment_a,
+                &proof
+            ));
+        }
+
+        // a == b and a < b cannot be proved.
+        for (value_a, value_b) in [(42u64, 42u64), (41, 42)] {
+            assert!(matches!(
+                comparison::prove_greater_than(
+                    value_a,
+                    value_b,
+                    &blinding_a,
+                    &blinding_b
+                ),
+                Err(WedprError::ArgumentError)
+            ));
+        }
+        // Nor can a proof for other values be reused for equal ones.
+        let proof =
+            comparison::prove_greater_than(43, 42, &blinding_a, &blinding_b)
+                .unwrap();
+        assert!(!comparison::verify_greater_than(
+            &pedersen_commit(42, &blinding_a),
+            &pedersen_commit(42, &blinding_b),
+            &proof
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of SBA utility functions.
+
+use crate::bidder::AuctionParameters;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_utils::traits::Hash;
+use wedpr_s_protos::config::HASH;
+
+/// Hashes the message signed by a bidder, which binds the bid commitment to
+/// the auction.
+pub fn hash_bid_message(
+    auction_parameters: &AuctionParameters,
+    commitment: &[u8],
+) -> Vec<u8> {
+    let mut message = auction_parameters.auction_id.as_bytes().to_vec();
+    message.extend_from_slice(commitment);
+    HASH.hash(&message)
+}
+
+/// Pads the commitments with identity points up to the next power of two, as
+/// the batch range proof requires.
+pub fn align_commitment_list_if_needed(list: &mut Vec<RistrettoPoint>) {
+    while !list.is_empty() && !list.len().is_power_of_two() {
+        list.push(RistrettoPoint::default());
+    }
+}
+
+pub fn align_u64_list_if_needed(list: &mut Vec<u64>) {
+    while !list.is_empty() && !list.len().is_power_of_two() {
+        list.push(0u64);
+    }
+}
+
+pub fn align_scalar_list_if_needed(list: &mut Vec<Scalar>) {
+    while !list.is_empty() && !list.len().is_power_of_two() {
+        list.push(Scalar::default());
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library for an auction verifier.
+
+use crate::{
+    bidder::{AuctionParameters, BidRequest},
+    comparison::gap_commitment,
+    utils::{align_commitment_list_if_needed, hash_bid_message},
+};
+use wedpr_l_crypto_zkp_range_proof::verify_value_range_in_batch;
+use wedpr_l_crypto_zkp_utils::{bytes_to_point, BASEPOINT_G2};
+use wedpr_l_utils::{error::WedprError, traits::Signature};
+use wedpr_s_protos::config::SIGNATURE;
+
+/// Verifies whether a sealed bid is signed by the bidder and commits to a
+/// bid in range.
+pub fn verify_bid_request(
+    auction_parameters: &AuctionParameters,
+    bid_request: &BidRequest,
+    public_key: &[u8],
+) -> Result<bool, WedprError> {
+    let commitment = bytes_to_point(&bid_request.commitment)?;
+    let message_hash =
+        hash_bid_message(auction_parameters, &bid_request.commitment);
+    if !SIGNATURE.verify(public_key, &message_hash, &bid_request.signature) {
+        wedpr_println!("verify bid signature failed!");
+        return Ok(false);
+    }
+    if !verify_value_range_in_batch(
+        &vec![commitment],
+        &bid_request.range_proof,
+        &BASEPOINT_G2,
+    ) {
+        wedpr_println!("verify bid range failed!");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Verifies whether the winning bid is greater than every other bid, as
+/// proved by [`crate::auctioneer::prove_auction_winner`].
+pub fn verify_auction_winner(
+    winner_request: &BidRequest,
+    other_requests: &[BidRequest],
+    proof: &[u8],
+) -> Result<bool, WedprError> {
+    if other_requests.is_empty() {
+        return Ok(true);
+    }
+    let winner_commitment = bytes_to_point(&winner_request.commitment)?;
+    let mut gap_commitments = Vec::with_capacity(other_requests.len());
+    for other_request in other_requests {
+        gap_commitments.push(gap_commitment(
+            &winner_commitment,
+            &bytes_to_point(&other_request.commitment)?,
+        ));
+    }
+    align_commitment_list_if_needed(&mut gap_commitments);
+    if !verify_value_range_in_batch(&gap_commitments, proof, &BASEPOINT_G2) {
+        wedpr_println!("verify auction winner failed!");
+        return Ok(false);
+    }
+    Ok(true)
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 WeDPR Lab Project Authors. Licensed under Apache-2.0.
+
+//! Library of selective disclosure predicates over committed attributes,
+//! built on range proofs instead of certificate signatures.
+//!
+//! An attribute is committed as value * G1 + blinding * G2. Proving that the
+//! value is at least a threshold is a range proof over value - threshold,
+//! which the verifier checks against the commitment shifted by
+//! threshold * G1.
+
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use wedpr_l_crypto_zkp_range_proof::{
+    prove_value_range_in_batch, verify_value_range_in_batch,
+};
+use wedpr_l_crypto_zkp_utils::{BASEPOINT_G1, BASEPOINT_G2};
+use wedpr_l_utils::error::WedprError;
+use wedpr_s_common::commitment::pedersen_commit;
+
+/// Makes the commitment of an attribute value.
+pub fn make_attribute_commitment(
+    value: u64,
+    blinding: &Scalar,
+) -> RistrettoPoint {
+    pedersen_commit(value, blinding)
+}
+
+/// Proves that the committed value is at least the threshold. Values below
+/// the threshold cannot be proven, and neither can differences beyond the
+/// 32-bit range covered by the range proof.
+pub fn prove_attribute_ge(
+    value: u64,
+    threshold: u64,
+    blinding: &Scalar,
+) -> Result<Vec<u8>, WedprError> {
+    let difference = match value.checked_sub(threshold) {
+        Some(v) if v <= u32::MAX as u64 => v,
+        _ => {
+            wedpr_println!("prove_attribute_ge failed: value out of range!");
+            return Err(WedprError::ArgumentError);
+        },
+    };
+    let (proof, _) = prove_value_range_in_batch(
+        &vec![difference],
+        &vec![*blinding],
+        &BASEPOINT_G2,
+    )?;
+    Ok(proof)
+}
+
+/// Verifies whether the committed value is at least the threshold.
+pub fn verify_attribute_ge(
+    commitment: &RistrettoPoint,
+    threshold: u64,
+    proof: &[u8],
+) -> bool {
+    let shifted_commitment =
+        commitment - Scalar::from(threshold) * *BASEPOINT_G1;
+    verify_value_range_in_batch(&vec![shifted_commitment], proof, &BASEPOINT_G2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wedpr_l_crypto_zkp_utils::get_random_scalar;
+
+    #[test]
+    fn test_attribute_ge() {
+        let blinding = get_random_scalar();
+        let commitment = make_attribute_commitment(30, &blinding);
+
+        let proof = prove_attribute_ge(30, 18, &blinding).unwrap();
+        assert!(verify_attribute_ge(&commitment, 18, &proof));
+        // The proof only holds for the threshold it was made for.
+        assert!(!verify_attribute_ge(&commitment, 25, &proof));
+        let other_commitment =
+            make_attribute_commitment(30, &get_random_scalar());
+        assert!(!verify_attribute_ge(&other_commitment, 18, &proof));
+
+        // A value equal to the threshold satisfies the predicate.
+        let equal_proof = prove_attribute_ge(30, 30, &blinding).unwrap();
+        assert!(verify_attribute_ge(&commitment, 30, &equal_proof));
+
+        // A value below the threshold cannot be proven.
+        assert!(prove_attribute_ge(17, 18, &blinding).is_err());
+        let below_commitment = make_attribute_commitment(17, &blinding);
+        assert!(!verify_attribute_ge(&below_commitment, 18, &equal_proof));
+    }
+}
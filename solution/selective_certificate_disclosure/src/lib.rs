@@ -5,6 +5,7 @@
 #[macro_use]
 extern crate wedpr_l_macros;
 
+pub mod committed_attribute;
 pub mod issuer;
 pub mod user;
 pub mod utils;